@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use zero_v::{compose, zero_v};
+use zero_v::{compose_both, zero_v};
 
 #[zero_v(trait_types)]
 trait IntOp {
@@ -136,6 +136,7 @@ fn bench_trait_objects(input: usize, ops: &Vec<Box<dyn IntOp>>) -> usize {
     ops.iter().map(|op| op.execute(input)).sum()
 }
 
+#[allow(clippy::identity_op)]
 fn bench_baseline(input: usize) -> usize {
     (input + 0)
         + (input << 1)
@@ -156,41 +157,8 @@ fn bench_baseline(input: usize) -> usize {
 pub fn criterion_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("Integer Ops");
 
-    let ops_dyn: Vec<Box<dyn IntOp>> = vec![
-        Box::new(Adder::new(0)),
-        Box::new(LShifter::new(1)),
-        Box::new(Adder::new(2)),
-        Box::new(Multiplier::new(3)),
-        Box::new(Adder::new(4)),
-        Box::new(Multiplier::new(5)),
-        Box::new(Adder::new(6)),
-        Box::new(Multiplier::new(7)),
-        Box::new(Adder::new(8)),
-        Box::new(Multiplier::new(9)),
-        Box::new(Adder::new(10)),
-        Box::new(RShifter::new(11)),
-        Box::new(Adder::new(12)),
-        Box::new(RShifter::new(13)),
-    ];
-
-    let ops_dyn_const: Vec<Box<dyn IntOp>> = vec![
-        Box::new(ConstAdder::<0>::new()),
-        Box::new(ConstLShifter::<1>::new()),
-        Box::new(ConstAdder::<2>::new()),
-        Box::new(ConstMultiplier::<3>::new()),
-        Box::new(ConstAdder::<4>::new()),
-        Box::new(ConstMultiplier::<5>::new()),
-        Box::new(ConstAdder::<6>::new()),
-        Box::new(ConstMultiplier::<7>::new()),
-        Box::new(ConstAdder::<8>::new()),
-        Box::new(ConstMultiplier::<9>::new()),
-        Box::new(ConstAdder::<10>::new()),
-        Box::new(ConstRShifter::<11>::new()),
-        Box::new(ConstAdder::<12>::new()),
-        Box::new(ConstRShifter::<13>::new()),
-    ];
-
-    let ops = compose!(
+    let (ops, ops_dyn) = compose_both!(
+        IntOp;
         Adder::new(0),
         LShifter::new(1),
         Adder::new(2),
@@ -207,7 +175,8 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         RShifter::new(13)
     );
 
-    let ops_const = compose!(
+    let (ops_const, ops_dyn_const) = compose_both!(
+        IntOp;
         ConstAdder::<0>::new(),
         ConstLShifter::<1>::new(),
         ConstAdder::<2>::new(),
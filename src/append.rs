@@ -0,0 +1,162 @@
+use core::ops::Add;
+
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+Append splices one chain of nodes onto the end of another. Because the chain
+is encoded in the type, the result is a new type: the nodes of the first
+chain are rebuilt one at a time on the way back up the recursion, with the
+second chain taking the place of the unit type at the bottom.
+*/
+
+/// Chains of nodes which can have another chain spliced onto their end.
+pub trait Append<Other: NextNode> {
+    /// The combined chain, holding the nodes of `self` followed by those of
+    /// `Other`.
+    type Output: NextNode;
+
+    /// Consume both chains and return the combined chain.
+    fn append(self, other: Other) -> Self::Output;
+}
+
+impl<Other: NextNode> Append<Other> for () {
+    type Output = Other;
+
+    #[inline]
+    fn append(self, other: Other) -> Self::Output {
+        other
+    }
+}
+
+impl<A, B: NextNode + Append<Other>, Other: NextNode> Append<Other> for Node<A, B> {
+    type Output = Node<A, B::Output>;
+
+    #[inline]
+    fn append(self, other: Other) -> Self::Output {
+        let (data, next) = self.into_inner();
+        Node::new(data, next.append(other))
+    }
+}
+
+/// Types which can be spliced into a node chain with `..` in
+/// [`compose!`](crate::compose): composites and node chains.
+pub trait IntoNodes {
+    /// The node chain holding the objects.
+    type Nodes: NextNode;
+
+    /// Consume the value and return its node chain.
+    fn into_nodes(self) -> Self::Nodes;
+}
+
+impl IntoNodes for () {
+    type Nodes = ();
+
+    #[inline]
+    fn into_nodes(self) -> Self::Nodes {}
+}
+
+impl<A, B: NextNode> IntoNodes for Node<A, B> {
+    type Nodes = Self;
+
+    #[inline]
+    fn into_nodes(self) -> Self::Nodes {
+        self
+    }
+}
+
+impl<A: NextNode> IntoNodes for Composite<A> {
+    type Nodes = A;
+
+    #[inline]
+    fn into_nodes(self) -> Self::Nodes {
+        self.into_head()
+    }
+}
+
+impl<A: NextNode> Composite<A> {
+    /// Consume two composites and return one holding the objects of this
+    /// composite followed by those of `other`.
+    ///
+    /// Composites can also be concatenated with `+`.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let combined = compose!(1u8, 2u16).concat(compose!(3u32));
+    /// assert_eq!(combined, compose!(1u8, 2u16, 3u32));
+    /// assert_eq!(compose!(1u8) + compose!(2u16), compose!(1u8, 2u16));
+    /// ```
+    pub fn concat<B: NextNode>(self, other: Composite<B>) -> Composite<A::Output>
+    where
+        A: Append<B>,
+    {
+        Composite::new(self.into_head().append(other.into_head()))
+    }
+
+    /// Consume the composite and return one with `item` added after its
+    /// last object.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::{compose, Composite};
+    ///
+    /// let composite = Composite::new(()).push(1u8).push("two");
+    /// assert_eq!(composite, compose!(1u8, "two"));
+    /// ```
+    pub fn push<T>(self, item: T) -> Composite<A::Output>
+    where
+        A: Append<Node<T, ()>>,
+    {
+        Composite::new(self.into_head().append(Node::base(item)))
+    }
+
+    /// Consume the composite and return one with `item` added at level 0,
+    /// ahead of every other object.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let composite = compose!("two", 3.0f64).prepend(1u8);
+    /// assert_eq!(composite, compose!(1u8, "two", 3.0f64));
+    /// ```
+    pub fn prepend<T>(self, item: T) -> Composite<Node<T, A>> {
+        Composite::new(Node::new(item, self.into_head()))
+    }
+}
+
+impl<A: NextNode + Append<B>, B: NextNode> Add<Composite<B>> for Composite<A> {
+    type Output = Composite<A::Output>;
+
+    fn add(self, other: Composite<B>) -> Self::Output {
+        self.concat(other)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{compose, Composite};
+
+    #[test]
+    fn can_concatenate_composites() {
+        let inputs = compose!(1u8, "two");
+        let outputs = compose!(3.0f64);
+        assert_eq!(inputs.concat(outputs), compose!(1u8, "two", 3.0f64));
+
+        let empty = Composite::new(());
+        assert_eq!(empty + compose!(1u8), compose!(1u8));
+    }
+
+    #[test]
+    fn can_push_objects() {
+        let composite = compose!(1u8).push("two").push(3.0f64);
+        assert_eq!(composite, compose!(1u8, "two", 3.0f64));
+    }
+
+    #[test]
+    fn can_prepend_objects() {
+        let composite = compose!(3.0f64).prepend("two").prepend(1u8);
+        assert_eq!(composite, compose!(1u8, "two", 3.0f64));
+    }
+}
@@ -0,0 +1,84 @@
+use core::fmt::Debug;
+
+/*
+Assertions over the outputs of a composite. A plain assert_eq! on collected
+outputs reports the position of the first difference, which still leaves the
+reader to count through the compose! call to find the object responsible.
+The iterators generated by the zero_v macro implement NamedOutputs, so these
+assertions can name that object directly.
+*/
+
+/// Iterators over the outputs of a composite which can report which object
+/// will produce their next output.
+pub trait NamedOutputs {
+    /// The type name of the object which will produce the next output, or
+    /// None if the iterator is exhausted.
+    fn next_type_name(&self) -> Option<&'static str>;
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_outputs_eq<I, E>(mut outputs: I, expected: E)
+where
+    I: Iterator + NamedOutputs,
+    I::Item: PartialEq<E::Item> + Debug,
+    E: IntoIterator,
+    E::Item: Debug,
+{
+    let mut expected = expected.into_iter();
+    let mut index = 0;
+    loop {
+        let type_name = outputs.next_type_name().unwrap_or("<unknown>");
+        match (outputs.next(), expected.next()) {
+            (None, None) => return,
+            (Some(left), Some(right)) => {
+                if left != right {
+                    panic!(
+                        "outputs differ at index {} ({}):\n  left: {:?}\n right: {:?}",
+                        index, type_name, left, right
+                    );
+                }
+            }
+            (Some(left), None) => panic!(
+                "unexpected output at index {} ({}): {:?}",
+                index, type_name, left
+            ),
+            (None, Some(right)) => panic!(
+                "missing output at index {}: expected {:?}",
+                index, right
+            ),
+        }
+        index += 1;
+    }
+}
+
+/// Asserts that an iterator over a composite's outputs yields exactly the
+/// expected values. On failure, the panic message gives the index of the
+/// first mismatched output along with the type name of the object which
+/// produced it.
+///
+/// ```
+/// use zero_v::{assert_outputs_eq, compose, zero_v};
+///
+/// #[zero_v(trait_types)]
+/// trait IntOp {
+///     fn execute(&self, input: usize) -> usize;
+/// }
+///
+/// struct Adder(usize);
+///
+/// impl IntOp for Adder {
+///     fn execute(&self, input: usize) -> usize {
+///         input + self.0
+///     }
+/// }
+///
+/// let ops = compose!(Adder(1), Adder(2));
+/// assert_outputs_eq!(ops.iter_execute(1), [2, 3]);
+/// ```
+#[macro_export]
+macro_rules! assert_outputs_eq {
+    ($outputs: expr, $expected: expr $(,)?) => {
+        $crate::__assert_outputs_eq($outputs, $expected)
+    };
+}
@@ -0,0 +1,236 @@
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+At provides compile time access to the object at a given level. Where the
+level execution traits walk the node chain at runtime (checking level == 0
+at each node on the way down), At resolves the target node entirely through
+the type system, so the compiler knows exactly which type lives at the
+requested level and there are no branches left at runtime.
+
+Stable Rust can't do arithmetic on const generic parameters in trait bounds
+(we can't write At<{ LEVEL - 1 }>), so each level's impl is written out by
+the impl_at macro, deferring to the previous level on the next node. That
+puts an upper bound on the levels which can be accessed this way, which is
+MAX_AT_LEVEL.
+*/
+
+/// The highest level supported by [`At`].
+pub const MAX_AT_LEVEL: usize = 31;
+
+/// Compile time access to the object at a given level of a collection.
+pub trait At<const LEVEL: usize> {
+    /// The type of the object stored at `LEVEL`.
+    type Output;
+
+    /// Get a reference to the object stored at `LEVEL`.
+    fn at(&self) -> &Self::Output;
+
+    /// Get a mutable reference to the object stored at `LEVEL`.
+    fn at_mut(&mut self) -> &mut Self::Output;
+}
+
+impl<A, B: NextNode> At<0> for Node<A, B> {
+    type Output = A;
+
+    #[inline]
+    fn at(&self) -> &Self::Output {
+        self.data()
+    }
+
+    #[inline]
+    fn at_mut(&mut self) -> &mut Self::Output {
+        self.data_mut()
+    }
+}
+
+macro_rules! impl_at {
+    ($($level: literal => $previous: literal),+) => {
+        $(
+            impl<A, B: NextNode + At<$previous>> At<$level> for Node<A, B> {
+                type Output = <B as At<$previous>>::Output;
+
+                #[inline]
+                fn at(&self) -> &Self::Output {
+                    self.next().at()
+                }
+
+                #[inline]
+                fn at_mut(&mut self) -> &mut Self::Output {
+                    self.next_mut().at_mut()
+                }
+            }
+        )+
+    };
+}
+
+impl_at!(
+    1 => 0, 2 => 1, 3 => 2, 4 => 3, 5 => 4, 6 => 5, 7 => 6, 8 => 7,
+    9 => 8, 10 => 9, 11 => 10, 12 => 11, 13 => 12, 14 => 13, 15 => 14,
+    16 => 15, 17 => 16, 18 => 17, 19 => 18, 20 => 19, 21 => 20, 22 => 21,
+    23 => 22, 24 => 23, 25 => 24, 26 => 25, 27 => 26, 28 => 27, 29 => 28,
+    30 => 29, 31 => 30
+);
+
+impl<A: NextNode + At<LEVEL>, const LEVEL: usize> At<LEVEL> for Composite<A> {
+    type Output = A::Output;
+
+    #[inline]
+    fn at(&self) -> &Self::Output {
+        self.head().at()
+    }
+
+    #[inline]
+    fn at_mut(&mut self) -> &mut Self::Output {
+        self.head_mut().at_mut()
+    }
+}
+
+impl<A: NextNode> Composite<A> {
+    /// Get a reference to the object at `LEVEL`, with its concrete type.
+    ///
+    /// `LEVEL` counts the composite's own nodes, so a nested composite is a
+    /// single level here and `get` returns the whole nested composite. The
+    /// levels used by the methods zero_v generates count each of its objects
+    /// separately instead.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let composite = compose!(1u8, "two", 3.0f64);
+    /// let second: &&str = composite.get::<1>();
+    /// assert_eq!(*second, "two");
+    /// ```
+    #[inline]
+    pub fn get<const LEVEL: usize>(&self) -> &<Self as At<LEVEL>>::Output
+    where
+        Self: At<LEVEL>,
+    {
+        self.at()
+    }
+
+    /// Get a mutable reference to the object at `LEVEL`, so it can be
+    /// reconfigured in place.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let mut composite = compose!(1u8, String::from("two"));
+    /// composite.get_mut::<1>().push_str("!");
+    /// assert_eq!(composite.get::<1>(), "two!");
+    /// ```
+    #[inline]
+    pub fn get_mut<const LEVEL: usize>(&mut self) -> &mut <Self as At<LEVEL>>::Output
+    where
+        Self: At<LEVEL>,
+    {
+        self.at_mut()
+    }
+
+    /// Run `f` on a mutable reference to the object at `LEVEL` and return
+    /// its result, so the borrow ends as soon as `f` does.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let mut composite = compose!(1u8, String::from("two"));
+    /// let len = composite.with_node_mut::<1, _>(|second| {
+    ///     second.push_str("!");
+    ///     second.len()
+    /// });
+    /// assert_eq!((len, composite.get::<1>().as_str()), (4, "two!"));
+    /// ```
+    #[inline]
+    pub fn with_node_mut<const LEVEL: usize, R>(
+        &mut self,
+        f: impl FnOnce(&mut <Self as At<LEVEL>>::Output) -> R,
+    ) -> R
+    where
+        Self: At<LEVEL>,
+    {
+        f(self.at_mut())
+    }
+}
+
+impl<A, B: NextNode> Node<A, B> {
+    /// Get a reference to the object at `LEVEL` below this node, with its
+    /// concrete type. Like [`Composite::get`], `LEVEL` counts nodes.
+    #[inline]
+    pub fn get<const LEVEL: usize>(&self) -> &<Self as At<LEVEL>>::Output
+    where
+        Self: At<LEVEL>,
+    {
+        self.at()
+    }
+
+    /// Get a mutable reference to the object at `LEVEL` below this node.
+    #[inline]
+    pub fn get_mut<const LEVEL: usize>(&mut self) -> &mut <Self as At<LEVEL>>::Output
+    where
+        Self: At<LEVEL>,
+    {
+        self.at_mut()
+    }
+
+    /// Run `f` on a mutable reference to the object at `LEVEL` below this
+    /// node and return its result.
+    #[inline]
+    pub fn with_node_mut<const LEVEL: usize, R>(
+        &mut self,
+        f: impl FnOnce(&mut <Self as At<LEVEL>>::Output) -> R,
+    ) -> R
+    where
+        Self: At<LEVEL>,
+    {
+        f(self.at_mut())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::At;
+    use crate::compose;
+
+    #[test]
+    fn can_access_objects_by_const_level() {
+        let composite = compose!(1u8, "two", 3.0f64);
+        let first: &u8 = At::<0>::at(&composite);
+        let second: &&str = At::<1>::at(&composite);
+        let third: &f64 = At::<2>::at(&composite);
+        assert_eq!((*first, *second, *third), (1, "two", 3.0));
+    }
+
+    #[test]
+    fn can_get_objects_by_const_level() {
+        let composite = compose!(1u8, "two", 3.0f64);
+        assert_eq!(*composite.get::<0>(), 1);
+        assert_eq!(*composite.head().get::<2>(), 3.0);
+    }
+
+    #[test]
+    fn can_mutate_objects_by_const_level() {
+        let mut composite = compose!(1u8, "two", 3.0f64);
+        *composite.get_mut::<0>() += 1;
+        *composite.head_mut().get_mut::<2>() *= 2.0;
+        assert_eq!(composite, compose!(2u8, "two", 6.0f64));
+    }
+
+    #[test]
+    fn can_mutate_objects_in_a_closure() {
+        let mut composite = compose!(1u8, "two", 3.0f64);
+        let old = composite.with_node_mut::<2, _>(|third| std::mem::replace(third, 4.0));
+        composite.head_mut().with_node_mut::<0, _>(|first| *first += 1);
+        assert_eq!((old, composite), (3.0, compose!(2u8, "two", 4.0f64)));
+    }
+
+    #[test]
+    fn can_access_up_to_the_max_level() {
+        let composite = compose!(
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22,
+            23, 24, 25, 26, 27, 28, 29, 30, 31
+        );
+        assert_eq!(*At::<31>::at(&composite), 31);
+    }
+}
@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::fmt;
+use core::mem::{align_of, size_of};
+
+/*
+Structural reports on the layout of a composite. The zero_v macro generates an
+audit() method for composites of objects implementing your trait, which walks
+the nodes (including those of nested composites) and records where each
+object lives, so it's easy to see which object is responsible when a
+composite turns out larger than expected.
+*/
+
+/// The layout of a single object in a composite.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NodeAudit {
+    /// The object's level in the composite.
+    pub level: usize,
+    /// The object's type name.
+    pub type_name: &'static str,
+    /// The object's size in bytes.
+    pub size: usize,
+    /// The object's alignment in bytes.
+    pub align: usize,
+    /// The object's offset in bytes from the start of the audited
+    /// composite.
+    pub offset: usize,
+    /// How many composites the object is nested in, below the audited one.
+    pub depth: usize,
+}
+
+impl NodeAudit {
+    /// Describe `object`, which is stored inside the composite starting at
+    /// `origin`.
+    pub fn of<T>(object: &T, origin: *const u8, level: usize, depth: usize) -> Self {
+        Self {
+            level,
+            type_name: type_name::<T>(),
+            size: size_of::<T>(),
+            align: align_of::<T>(),
+            offset: object as *const T as usize - origin as usize,
+            depth,
+        }
+    }
+}
+
+/// The layout of a composite and every object in it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Audit {
+    /// The composite's type name.
+    pub type_name: &'static str,
+    /// The composite's size in bytes.
+    pub size: usize,
+    /// The composite's alignment in bytes.
+    pub align: usize,
+    /// Every object in the composite, by level.
+    pub nodes: Vec<NodeAudit>,
+}
+
+impl Audit {
+    /// Build the report for a composite from its objects.
+    pub fn new<C>(_composite: &C, nodes: Vec<NodeAudit>) -> Self {
+        Self {
+            type_name: type_name::<C>(),
+            size: size_of::<C>(),
+            align: align_of::<C>(),
+            nodes,
+        }
+    }
+
+    /// The number of bytes in the composite not taken up by its objects
+    /// (padding, along with any objects of zero sized types).
+    pub fn padding(&self) -> usize {
+        self.size - self.nodes.iter().map(|node| node.size).sum::<usize>()
+    }
+}
+
+impl fmt::Display for Audit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "composite: {} bytes, align {}, {} padding",
+            self.size,
+            self.align,
+            self.padding()
+        )?;
+        writeln!(f, "{:>5} {:>6} {:>6} {:>5}  type", "level", "offset", "size", "align")?;
+        for node in &self.nodes {
+            writeln!(
+                f,
+                "{:>5} {:>6} {:>6} {:>5}  {}{}",
+                node.level,
+                node.offset,
+                node.size,
+                node.align,
+                "  ".repeat(node.depth),
+                node.type_name
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Audit, NodeAudit};
+    use crate::compose;
+
+    #[test]
+    fn can_describe_objects() {
+        let composite = compose!(1u8, 2u64);
+        let origin = &composite as *const _ as *const u8;
+        let first = NodeAudit::of(composite.head().data(), origin, 0, 0);
+        let second = NodeAudit::of(composite.head().next().data(), origin, 1, 0);
+        let audit = Audit::new(&composite, vec![first, second]);
+
+        assert_eq!((first.type_name, first.size, first.align), ("u8", 1, 1));
+        assert_eq!((second.type_name, second.size, second.align), ("u64", 8, 8));
+        assert_eq!(audit.size, std::mem::size_of_val(&composite));
+        assert_eq!(audit.padding(), 7);
+    }
+}
@@ -0,0 +1,172 @@
+/*!
+A quick, dependency free way to check whether Zero_V is paying for itself
+on a given workload.
+
+This is not a replacement for a proper benchmark suite (see the criterion
+benchmarks in this repository for that). It runs each variant of a workload
+in a calibrated loop and reports the best observed time per call, which is
+usually enough to tell whether static dispatch is making a difference
+before you invest in wiring up criterion.
+*/
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// The minimum amount of time a single timing round should take. Rounds much
+/// shorter than this are dominated by timer resolution.
+const ROUND_TARGET: Duration = Duration::from_millis(5);
+
+/// The number of timed rounds per workload. The fastest round is reported.
+const ROUNDS: usize = 5;
+
+/// Timings for the three variants of a workload measured by [`compare`].
+///
+/// Times are in (fractional) nanoseconds, since a cheap workload can take
+/// well under a nanosecond per call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    /// Best observed time per call of the static (Zero_V) workload.
+    pub static_nanos: f64,
+    /// Best observed time per call of the dynamic (vtable) workload.
+    pub dyn_nanos: f64,
+    /// Best observed time per call of the baseline (hand-written) workload.
+    pub baseline_nanos: f64,
+}
+
+impl Comparison {
+    /// How many times faster the static workload ran than the dynamic one.
+    /// Values above 1.0 mean Zero_V came out ahead.
+    pub fn static_speedup(&self) -> f64 {
+        self.dyn_nanos / self.static_nanos
+    }
+
+    /// How many times slower the static workload ran than the baseline.
+    /// Values close to 1.0 mean there's little left to gain.
+    pub fn static_overhead(&self) -> f64 {
+        self.static_nanos / self.baseline_nanos
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "static:   {:.2}ns", self.static_nanos)?;
+        writeln!(
+            f,
+            "dyn:      {:.2}ns (static is {:.2}x faster)",
+            self.dyn_nanos,
+            self.static_speedup()
+        )?;
+        write!(
+            f,
+            "baseline: {:.2}ns (static is {:.2}x slower)",
+            self.baseline_nanos,
+            self.static_overhead()
+        )
+    }
+}
+
+/// Time the static, dynamic and baseline variants of a workload and report
+/// their relative performance.
+///
+/// Each closure should perform one unit of the workload and return its
+/// output (outputs are passed through [`black_box`] so the work can't be
+/// optimized away).
+///
+/// # Arguments
+///
+/// * `static_fn` - The workload using a Zero_V composite.
+/// * `dyn_fn` - The same workload using trait objects.
+/// * `baseline_fn` - The same workload written out by hand.
+///
+/// # Example usage
+/// ```
+/// use zero_v::bench::compare;
+///
+/// let values = vec![1usize, 2, 3];
+/// let comparison = compare(
+///     || values.iter().sum::<usize>(),
+///     || values.iter().fold(0, |acc, value| acc + value),
+///     || 6usize,
+/// );
+/// println!("{}", comparison);
+/// ```
+pub fn compare<S, SR, D, DR, B, BR>(static_fn: S, dyn_fn: D, baseline_fn: B) -> Comparison
+where
+    S: FnMut() -> SR,
+    D: FnMut() -> DR,
+    B: FnMut() -> BR,
+{
+    Comparison {
+        static_nanos: nanos_per_call(static_fn),
+        dyn_nanos: nanos_per_call(dyn_fn),
+        baseline_nanos: nanos_per_call(baseline_fn),
+    }
+}
+
+/// The best time per call over ROUNDS rounds. The fastest round is picked
+/// before dividing, in floating point, so sub-nanosecond calls don't
+/// truncate to zero.
+fn nanos_per_call<F: FnMut() -> R, R>(mut f: F) -> f64 {
+    let iterations = calibrate(&mut f);
+
+    let fastest = (0..ROUNDS)
+        .map(|_| time_round(&mut f, iterations))
+        .min()
+        .unwrap_or_default();
+    fastest.as_nanos() as f64 / f64::from(iterations)
+}
+
+/// Find an iteration count large enough for a round to take at least
+/// ROUND_TARGET.
+fn calibrate<F: FnMut() -> R, R>(f: &mut F) -> u32 {
+    let mut iterations = 1;
+    while time_round(f, iterations) < ROUND_TARGET && iterations < u32::MAX / 2 {
+        iterations *= 2;
+    }
+    iterations
+}
+
+fn time_round<F: FnMut() -> R, R>(f: &mut F, iterations: u32) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(f());
+    }
+    start.elapsed()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compare, Comparison};
+    use std::cell::Cell;
+    use std::hint::black_box;
+
+    #[test]
+    fn can_compare_workloads() {
+        let calls = Cell::new(0usize);
+        // Cheap enough to take well under a nanosecond per call in release
+        // builds, which still has to come out as a usable time.
+        let comparison = compare(
+            || black_box(1) + 1,
+            || calls.set(calls.get() + 1),
+            || 2,
+        );
+        assert!(calls.get() > 0);
+        for nanos in [comparison.static_nanos, comparison.dyn_nanos, comparison.baseline_nanos] {
+            assert!(nanos.is_finite() && nanos > 0.0);
+        }
+        assert!(comparison.static_speedup().is_finite());
+        assert!(comparison.static_overhead().is_finite());
+    }
+
+    #[test]
+    fn can_compute_ratios() {
+        let comparison = Comparison {
+            static_nanos: 10.0,
+            dyn_nanos: 30.0,
+            baseline_nanos: 5.0,
+        };
+        assert_eq!(comparison.static_speedup(), 3.0);
+        assert_eq!(comparison.static_overhead(), 2.0);
+    }
+}
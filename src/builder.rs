@@ -0,0 +1,87 @@
+use crate::append::{Append, IntoNodes};
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+A method chaining alternative to compose!, for code which builds up a
+composite across several functions (or generates the calls). Each call
+appends to the builder's node chain, so like Append, it returns a builder of
+a new type.
+*/
+
+/// Builds a composite one object at a time, in order.
+///
+/// # Example usage
+/// ```
+/// use zero_v::{compose, ComposeBuilder};
+///
+/// fn with_defaults<A: zero_v::NextNode>(
+///     builder: ComposeBuilder<A>,
+/// ) -> ComposeBuilder<<A as zero_v::Append<zero_v::Node<u16, ()>>>::Output>
+/// where
+///     A: zero_v::Append<zero_v::Node<u16, ()>>,
+/// {
+///     builder.with(2u16)
+/// }
+///
+/// let composite = with_defaults(ComposeBuilder::new().with(1u8)).with("three").build();
+/// assert_eq!(composite, compose!(1u8, 2u16, "three"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComposeBuilder<A: NextNode> {
+    nodes: A,
+}
+
+impl ComposeBuilder<()> {
+    /// A builder with no objects.
+    pub fn new() -> Self {
+        Self { nodes: () }
+    }
+}
+
+impl<A: NextNode> ComposeBuilder<A> {
+    /// Add an object after the ones added so far.
+    #[inline]
+    pub fn with<T>(self, item: T) -> ComposeBuilder<A::Output>
+    where
+        A: Append<Node<T, ()>>,
+    {
+        ComposeBuilder {
+            nodes: self.nodes.append(Node::base(item)),
+        }
+    }
+
+    /// Add every object of a composite (or node chain) after the ones added
+    /// so far, like `..` in `compose!`.
+    #[inline]
+    pub fn with_all<B: IntoNodes>(self, nodes: B) -> ComposeBuilder<A::Output>
+    where
+        A: Append<B::Nodes>,
+    {
+        ComposeBuilder {
+            nodes: self.nodes.append(nodes.into_nodes()),
+        }
+    }
+
+    /// Finish building and return the composite.
+    #[inline]
+    pub fn build(self) -> Composite<A> {
+        Composite::new(self.nodes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ComposeBuilder;
+    use crate::compose;
+
+    #[test]
+    fn can_build_composites() {
+        let composite = ComposeBuilder::new()
+            .with(1u8)
+            .with_all(compose!("two", 3.0f64))
+            .with(4u32)
+            .build();
+        assert_eq!(composite, compose!(1u8, "two", 3.0f64, 4u32));
+        assert_eq!(ComposeBuilder::new().build(), compose!());
+    }
+}
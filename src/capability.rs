@@ -0,0 +1,266 @@
+use alloc::vec::Vec;
+use core::any::type_name;
+use core::ops::BitOr;
+
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+Capability checks for composites. Each object declares the capabilities it
+needs from its host with a const, and the capabilities of a whole composite
+are gathered from those consts when its type is known. A host can then check
+the composite against what it provides, either at compile time with
+assert_capabilities!, or at runtime with check_capabilities, which reports
+exactly which objects are missing what.
+*/
+
+/// A set of up to 64 capabilities, each identified by a bit index.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Capabilities(u64);
+
+impl Capabilities {
+    /// The empty set of capabilities.
+    pub const NONE: Self = Self(0);
+
+    /// The set holding only the capability at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is 64 or more.
+    pub const fn bit(index: u32) -> Self {
+        assert!(index < 64, "capability indices must be less than 64");
+        Self(1 << index)
+    }
+
+    /// Build a set from its raw bits.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// The raw bits of the set.
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// The capabilities in either set.
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// The capabilities in this set but not `other`.
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Whether every capability in `other` is also in this set.
+    pub const fn contains(self, other: Self) -> bool {
+        other.0 & !self.0 == 0
+    }
+
+    /// Whether the set is empty.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl BitOr for Capabilities {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        self.union(other)
+    }
+}
+
+/// Objects which need capabilities from the host running them.
+pub trait Requires {
+    /// The capabilities this type of object needs.
+    const REQUIRES: Capabilities;
+
+    /// The number of levels the object takes up in a composite. Only nested
+    /// composites take up more than one.
+    const LEVELS: usize = 1;
+
+    /// Record the object, at `level`, if its requirements aren't covered by
+    /// `provided`. Nested composites record each of their objects instead.
+    fn record_unmet(provided: Capabilities, level: usize, unmet: &mut Vec<UnmetRequirement>) {
+        let missing = Self::REQUIRES.difference(provided);
+        if !missing.is_empty() {
+            unmet.push(UnmetRequirement {
+                level,
+                type_name: type_name::<Self>(),
+                missing,
+            });
+        }
+    }
+}
+
+// A nested composite needs whatever its objects need, and reports them one
+// level each, like the levels the zero_v macro generates.
+impl<A: NextNode + RequiredCapabilities> Requires for Composite<A> {
+    const REQUIRES: Capabilities = A::REQUIRED;
+
+    const LEVELS: usize = <A as RequiredCapabilities>::LEVELS;
+
+    fn record_unmet(provided: Capabilities, level: usize, unmet: &mut Vec<UnmetRequirement>) {
+        A::unmet_requirements(provided, level, unmet)
+    }
+}
+
+/// An object which needs capabilities its host doesn't provide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnmetRequirement {
+    /// The object's level in the composite.
+    pub level: usize,
+    /// The object's type name.
+    pub type_name: &'static str,
+    /// The capabilities the object needs which aren't provided.
+    pub missing: Capabilities,
+}
+
+/// The capabilities needed by every object in a chain of nodes.
+pub trait RequiredCapabilities {
+    /// Every capability needed by some object in the chain.
+    const REQUIRED: Capabilities;
+
+    /// The number of levels in the chain, counting each object in a nested
+    /// composite as a level of its own.
+    const LEVELS: usize;
+
+    /// Record every object from `level` down whose requirements aren't
+    /// covered by `provided`.
+    fn unmet_requirements(provided: Capabilities, level: usize, unmet: &mut Vec<UnmetRequirement>);
+}
+
+impl RequiredCapabilities for () {
+    const REQUIRED: Capabilities = Capabilities::NONE;
+
+    const LEVELS: usize = 0;
+
+    fn unmet_requirements(
+        _provided: Capabilities,
+        _level: usize,
+        _unmet: &mut Vec<UnmetRequirement>,
+    ) {
+    }
+}
+
+impl<A: Requires, B: NextNode + RequiredCapabilities> RequiredCapabilities for Node<A, B> {
+    const REQUIRED: Capabilities = A::REQUIRES.union(B::REQUIRED);
+
+    const LEVELS: usize = <A as Requires>::LEVELS + <B as RequiredCapabilities>::LEVELS;
+
+    fn unmet_requirements(provided: Capabilities, level: usize, unmet: &mut Vec<UnmetRequirement>) {
+        A::record_unmet(provided, level, unmet);
+        B::unmet_requirements(provided, level + <A as Requires>::LEVELS, unmet);
+    }
+}
+
+impl<A: NextNode + RequiredCapabilities> RequiredCapabilities for Composite<A> {
+    const REQUIRED: Capabilities = A::REQUIRED;
+
+    const LEVELS: usize = <A as RequiredCapabilities>::LEVELS;
+
+    fn unmet_requirements(provided: Capabilities, level: usize, unmet: &mut Vec<UnmetRequirement>) {
+        A::unmet_requirements(provided, level, unmet)
+    }
+}
+
+/// Check that `provided` covers the requirements of every object in a
+/// composite, returning the objects which need more otherwise.
+pub fn check_capabilities<C: RequiredCapabilities>(
+    _composite: &C,
+    provided: Capabilities,
+) -> Result<(), Vec<UnmetRequirement>> {
+    if provided.contains(C::REQUIRED) {
+        return Ok(());
+    }
+    let mut unmet = Vec::new();
+    C::unmet_requirements(provided, 0, &mut unmet);
+    Err(unmet)
+}
+
+#[doc(hidden)]
+pub struct CapabilityCheck<C, const PROVIDED: u64>(core::marker::PhantomData<C>);
+
+impl<C: RequiredCapabilities, const PROVIDED: u64> CapabilityCheck<C, PROVIDED> {
+    pub const SATISFIED: () = assert!(
+        Capabilities::from_bits(PROVIDED).contains(C::REQUIRED),
+        "the composite needs capabilities which aren't provided"
+    );
+}
+
+/// Checks at compile time that a set of capabilities, given as a const
+/// expression, covers the requirements of every object in a composite.
+///
+/// ```
+/// use zero_v::{assert_capabilities, compose, Capabilities, Requires};
+///
+/// const NETWORK: Capabilities = Capabilities::bit(0);
+/// const DISK: Capabilities = Capabilities::bit(1);
+///
+/// struct Uploader;
+///
+/// impl Requires for Uploader {
+///     const REQUIRES: Capabilities = NETWORK;
+/// }
+///
+/// let plugins = compose!(Uploader);
+/// assert_capabilities!(plugins, NETWORK.union(DISK));
+/// ```
+#[macro_export]
+macro_rules! assert_capabilities {
+    ($composite: expr, $provided: expr $(,)?) => {{
+        const PROVIDED: u64 = $crate::Capabilities::bits($provided);
+        fn check<C: $crate::RequiredCapabilities>(_composite: &C) {
+            let () = $crate::CapabilityCheck::<C, PROVIDED>::SATISFIED;
+        }
+        check(&$composite)
+    }};
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_capabilities, Capabilities, Requires, UnmetRequirement};
+    use crate::compose;
+
+    const NETWORK: Capabilities = Capabilities::bit(0);
+    const DISK: Capabilities = Capabilities::bit(1);
+
+    struct Uploader;
+    struct Archiver;
+
+    impl Requires for Uploader {
+        const REQUIRES: Capabilities = NETWORK;
+    }
+
+    impl Requires for Archiver {
+        const REQUIRES: Capabilities = NETWORK.union(DISK);
+    }
+
+    #[test]
+    fn can_check_capabilities() {
+        let plugins = compose!(Uploader, compose!(Archiver));
+        assert_eq!(check_capabilities(&plugins, NETWORK | DISK), Ok(()));
+        assert_eq!(
+            check_capabilities(&plugins, NETWORK),
+            Err(vec![UnmetRequirement {
+                level: 1,
+                type_name: std::any::type_name::<Archiver>(),
+                missing: DISK,
+            }])
+        );
+        assert_capabilities!(plugins, NETWORK.union(DISK));
+    }
+
+    #[test]
+    fn can_check_objects_after_nested_composites() {
+        let plugins = compose!(Uploader, compose!(Uploader, Archiver), Archiver);
+        let unmet = check_capabilities(&plugins, NETWORK).unwrap_err();
+        assert_eq!(
+            unmet.iter().map(|u| (u.level, u.type_name)).collect::<Vec<_>>(),
+            vec![
+                (2, std::any::type_name::<Archiver>()),
+                (3, std::any::type_name::<Archiver>()),
+            ]
+        );
+    }
+}
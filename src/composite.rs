@@ -1,4 +1,6 @@
-use crate::level::Level;
+use core::ops::{Bound, Range, RangeBounds};
+
+use crate::tuple::IntoParts;
 
 /*
 The current structure of the composite system is
@@ -24,48 +26,196 @@ to the application writer.
 */
 
 /// A type representing a collection of zero or more objects.
-#[derive(Debug, PartialEq)]
+///
+/// Composites are compared (and hashed) object by object in level order, so
+/// they order the same way tuples of their objects would.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "ffi", repr(C))]
 pub struct Composite<A: NextNode> {
     /// Can be of any type implementing the NextNode trait. Typically this will
     /// be a node whose `next` field implements NextNode (representing a
     /// collection of one or more nested nodes) or the unit type
     /// (representing an empty composite).
+    #[deprecated(note = "use the `head` or `head_mut` methods instead")]
     pub head: A,
 }
 
+// The fields are public for backwards compatibility, but are deprecated in
+// favour of the accessors so the layout of the node chain is free to change.
+// Everything in this crate goes through the accessors except the accessors
+// themselves and the constructors.
+#[allow(deprecated)]
 impl<A: NextNode> Composite<A> {
     /// Generates a new Composite
     ///
     /// # Arguments
     ///
     /// * `head` - The first node in the data structure or the unit type.
-    pub fn new(head: A) -> Self {
+    pub const fn new(head: A) -> Self {
         Self { head }
     }
 
+    /// Get a reference to the first node (or the unit type for an empty
+    /// composite).
+    #[inline]
+    pub const fn head(&self) -> &A {
+        &self.head
+    }
+
+    /// Get a mutable reference to the first node.
+    #[inline]
+    pub fn head_mut(&mut self) -> &mut A {
+        &mut self.head
+    }
+
+    /// Consume the composite and return its first node.
+    #[inline]
+    pub fn into_head(self) -> A {
+        self.head
+    }
+}
+
+impl<A: NextNode> Composite<A> {
+    /// The length of the composite, for use in const contexts (e.g. as the
+    /// size of an array holding one output per node).
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::{compose, Composite, Node};
+    ///
+    /// type Pair = Composite<Node<u8, Node<u16, ()>>>;
+    /// let outputs = [0usize; Pair::LEN];
+    /// assert_eq!(outputs.len(), compose!(1u8, 2u16).len());
+    /// ```
+    pub const LEN: usize = A::LEN;
+
     /// Get the length of the composite
-    pub fn len(&self) -> usize {
-        self.head.get_len()
+    pub const fn len(&self) -> usize {
+        A::LEN
+    }
+
+    /// Check whether the composite holds no objects
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Resolve a range of levels (e.g. `2..`, `..=3`) into a concrete
+    /// start..end range over this composite.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range starts after it ends or extends past the end of
+    /// the composite, matching the behavior of slice indexing.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let composite = compose!(1, 2, 3, 4);
+    /// assert_eq!(composite.level_range(1..), 1..4);
+    /// assert_eq!(composite.level_range(..=1), 0..2);
+    /// ```
+    pub fn level_range<R: RangeBounds<usize>>(&self, range: R) -> Range<usize> {
+        resolve_level_range(range, self.len())
+    }
+}
+
+/// Resolve a range of levels into a concrete start..end range over a
+/// collection with `len` levels. This is what [`Composite::level_range`] uses
+/// under the hood, exposed for collections whose level count differs from
+/// their node count (such as composites with nested composites as nodes).
+///
+/// # Panics
+///
+/// Panics if the range starts after it ends or ends after `len`.
+///
+/// # Example usage
+/// ```
+/// use zero_v::resolve_level_range;
+///
+/// assert_eq!(resolve_level_range(2.., 5), 2..5);
+/// ```
+pub fn resolve_level_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).unwrap_or_else(|| {
+            panic!("level range starts after {}, past the end of any composite", start)
+        }),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).unwrap_or_else(|| {
+            panic!(
+                "level range end {} (inclusive) out of range for composite of length {}",
+                end, len
+            )
+        }),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    assert!(
+        start <= end,
+        "level range starts at {} but ends at {}",
+        start,
+        end
+    );
+    assert!(
+        end <= len,
+        "level range end {} out of range for composite of length {}",
+        end,
+        len
+    );
+
+    start..end
+}
+
+impl<A: NextNode + IntoParts> Composite<A> {
+    /// Consume the composite and return the objects it holds as a tuple,
+    /// in composition order.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let (a, b) = compose!(1, "two").into_parts();
+    /// assert_eq!((a, b), (1, "two"));
+    /// ```
+    pub fn into_parts(self) -> A::Parts {
+        self.into_head().into_parts()
     }
 
-    /// Iterate over the level values of the composite
-    pub fn iter_levels(&self) -> impl Iterator<Item=Level<Self>> {
-        (0..self.len()).map(|value| Level::new(value))
+    /// Consume the composite and return the objects it holds as a tuple.
+    /// The reverse of building a composite from a tuple with `From`.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::Composite;
+    ///
+    /// let composite = Composite::from((1, "two"));
+    /// assert_eq!(composite.into_tuple(), (1, "two"));
+    /// ```
+    pub fn into_tuple(self) -> A::Parts {
+        self.into_parts()
     }
 }
 
 /// Represents a collection of one or more objects.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "ffi", repr(C))]
 pub struct Node<A, B: NextNode> {
     /// The object held in this node
+    #[deprecated(note = "use the `data` or `data_mut` methods instead")]
     pub data: A,
     /// Next is any type implementing the NextNode trait. Typically this will
     /// be a node whose next field also implements NextNode (representing a
     /// collection of one or more nested node) or the unit type
     /// (representing an empty composite).
+    #[deprecated(note = "use the `next` or `next_mut` methods instead")]
     pub next: B,
 }
 
+#[allow(deprecated)]
 impl<A, B: NextNode> Node<A, B> {
     /// Build a new node
     ///
@@ -73,15 +223,67 @@ impl<A, B: NextNode> Node<A, B> {
     ///
     /// * `data` - The object held in this node in the composite
     /// * `next` - The next node in the data structure.
-    pub fn new(data: A, next: B) -> Self {
+    pub const fn new(data: A, next: B) -> Self {
         Self { data, next }
     }
+
+    /// Get a reference to the object held in this node.
+    #[inline]
+    pub const fn data(&self) -> &A {
+        &self.data
+    }
+
+    /// Get a mutable reference to the object held in this node.
+    #[inline]
+    pub fn data_mut(&mut self) -> &mut A {
+        &mut self.data
+    }
+
+    /// Get a reference to the rest of the node chain.
+    #[inline]
+    pub const fn next(&self) -> &B {
+        &self.next
+    }
+
+    /// Get a mutable reference to the rest of the node chain.
+    #[inline]
+    pub fn next_mut(&mut self) -> &mut B {
+        &mut self.next
+    }
+
+    /// Mutable references to the object held in this node and to the rest
+    /// of the node chain at the same time.
+    #[inline]
+    pub(crate) fn parts_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.data, &mut self.next)
+    }
+
+    /// Consume the node and return the object it holds along with the rest
+    /// of the node chain.
+    #[inline]
+    pub fn into_inner(self) -> (A, B) {
+        (self.data, self.next)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<A: NextNode + defmt::Format> defmt::Format for Composite<A> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "Composite {{ head: {} }}", self.head())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<A: defmt::Format, B: NextNode + defmt::Format> defmt::Format for Node<A, B> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "Node {{ data: {}, next: {} }}", self.data(), self.next())
+    }
 }
 
 impl<A> Node<A, ()> {
     /// Build a new Node where the next field is the unit type.
-    pub fn base(data: A) -> Self {
-        Self { data, next: () }
+    pub const fn base(data: A) -> Self {
+        Self::new(data, ())
     }
 }
 
@@ -94,9 +296,23 @@ impl<A> Node<A, ()> {
 // X or Y at compile time. In this case, we don't know this information while
 // writing this library, but the library user will know the exact type of
 // NextNode at compile time.
-pub trait NextNode: HasLength {}
-impl NextNode for () {}
-impl<A, B: NextNode> NextNode for Node<A, B> {}
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a chain of zero_v nodes",
+    label = "expected `()` or a `Node` ending in `()`",
+    note = "build collections with `compose!` rather than nesting nodes by hand"
+)]
+pub trait NextNode: HasLength {
+    /// The number of nodes in the chain, for use in const contexts.
+    const LEN: usize;
+}
+
+impl NextNode for () {
+    const LEN: usize = 0;
+}
+
+impl<A, B: NextNode> NextNode for Node<A, B> {
+    const LEN: usize = B::LEN + 1;
+}
 
 pub trait HasLength {
     fn get_len(&self) -> usize;
@@ -110,7 +326,7 @@ impl HasLength for () {
 
 impl<A, B: NextNode> HasLength for Node<A, B> {
     fn get_len(&self) -> usize {
-        self.next.get_len() + 1
+        self.next().get_len() + 1
     }
 }
 
@@ -124,16 +340,52 @@ impl<A, B: NextNode> HasLength for Node<A, B> {
 /// let nodes = compose_nodes!(1, 2);
 /// assert_eq!(nodes, Node::new(1, Node::new(2, ())));
 /// ```
+///
+/// An existing node chain or composite can be spliced in with `..`, as in
+/// `compose_nodes!(0, ..nodes, 3)`.
+///
+/// Objects can be gated with `#[cfg(...)]` as in
+/// `compose_nodes!(0, #[cfg(feature = "metrics")] 1)`, see
+/// [`compose!`](crate::compose).
 #[macro_export]
 macro_rules! compose_nodes {
     () => {
         ()
     };
-    ($val: expr) => {
+    (.. $spread: expr $(,)?) => {
+        $crate::IntoNodes::into_nodes($spread)
+    };
+    (.. $spread: expr, $($right: tt)+) => {
+        $crate::Append::append(
+            $crate::IntoNodes::into_nodes($spread),
+            $crate::compose_nodes!($($right)+)
+        )
+    };
+    (for $var: ident in $start: literal .. $end: literal { $($item: tt)* } $(, $($right: tt)*)?) => {
+        $crate::compose_nodes!(
+            ..$crate::compose_repeat!($($item)* for $var in $start..$end) $(, $($right)*)?
+        )
+    };
+    (for $var: ident in $start: literal ..= $end: literal { $($item: tt)* } $(, $($right: tt)*)?) => {
+        $crate::compose_nodes!(
+            ..$crate::compose_repeat!($($item)* for $var in $start..=$end) $(, $($right)*)?
+        )
+    };
+    (#[cfg($cfg: meta)] $val: expr $(, $($right: tt)*)?) => {{
+        // The rest of the chain is only expanded once either way, and the
+        // object is still built before the objects after it.
+        #[cfg($cfg)]
+        let data = $val;
+        let next = $crate::compose_nodes!($($($right)*)?);
+        #[cfg($cfg)]
+        let next = $crate::Node::new(data, next);
+        next
+    }};
+    ($val: expr $(,)?) => {
        $crate::Node::base($val)
     };
-    ($left: expr, $($right: expr), +) => {
-        $crate::Node::new($left, $crate::compose_nodes!( $($right), +))
+    ($left: expr, $($right: tt)+) => {
+        $crate::Node::new($left, $crate::compose_nodes!($($right)+))
     };
 }
 
@@ -148,16 +400,219 @@ macro_rules! compose_nodes {
 /// let nodes = compose!(1, 2);
 /// assert_eq!(nodes, Composite::new(Node::new(1, Node::base(2))));
 /// ```
+///
+/// The objects of an existing composite (or node chain) can be spliced in
+/// with `..`, which makes it easy to extend a bundle of defaults:
+///
+/// ```
+/// use zero_v::compose;
+///
+/// let defaults = compose!(2, 3);
+/// assert_eq!(compose!(1, ..defaults, 4), compose!(1, 2, 3, 4));
+/// ```
+///
+/// A composite passed without the `..` is held whole, as a single node. The
+/// iteration methods generated by the zero_v macro treat the objects of a
+/// nested composite as levels of the outer one, so a pre-built bundle like
+/// `default_plugins()` executes the same either way, but only a spliced
+/// bundle's objects can be reached with `get` and the other level based
+/// accessors. (`compose!` can't splice composites automatically, since a
+/// macro can't tell what type an argument has.)
+///
+/// Note that this means a `RangeTo` can't be composed without wrapping it
+/// in parentheses (`compose!((..5))`).
+///
+/// Objects can be gated with a `#[cfg(...)]` attribute, in which case they
+/// are left out of the composite entirely (along with their level) when the
+/// condition is false:
+///
+/// ```
+/// use zero_v::compose;
+///
+/// let nodes = compose!(1, #[cfg(any())] "disabled", #[cfg(all())] 2);
+/// assert_eq!(nodes, compose!(1, 2));
+/// ```
+///
+/// Without any `..` splicing, the expansion only calls const constructors,
+/// so a composite of const constructible objects can be built in a `static`:
+///
+/// ```
+/// use zero_v::{compose, Composite, Node};
+///
+/// static LIMITS: Composite<Node<u8, Node<u16, ()>>> = compose!(8, 16);
+/// assert_eq!(*LIMITS.head().data(), 8);
+/// ```
+///
+/// With the `gen` feature, `for i in start..end { expr }` adds one object for
+/// each value of `i`, with every `#i` in the expression replaced by the value
+/// (see `compose_repeat!`):
+///
+/// ```
+/// # #[cfg(feature = "gen")]
+/// # {
+/// use zero_v::compose;
+///
+/// assert_eq!(compose!(0, for i in 1..4 { #i * 10 }, 40), compose!(0, 10, 20, 30, 40));
+/// # }
+/// ```
 #[macro_export]
 macro_rules! compose {
-    ($($right: expr), *) => {
-        $crate::Composite::new($crate::compose_nodes!( $($right), *))
+    ($($items: tt)*) => {
+        $crate::Composite::new($crate::compose_nodes!($($items)*))
+    };
+}
+
+/// Names the type of a composite holding objects of the given types, in
+/// order, as a type alias. This saves writing out the nested node types by
+/// hand, e.g. to store a composite in a struct field.
+///
+/// # Example usage
+/// ```
+/// use zero_v::{compose, composed_type, Composite, Node};
+///
+/// composed_type!(pub Limits = [u8, u16, u32]);
+///
+/// struct Config {
+///     limits: Limits,
+/// }
+///
+/// let config = Config { limits: compose!(8, 16, 32) };
+/// let nodes: Composite<Node<u8, Node<u16, Node<u32, ()>>>> = config.limits;
+/// assert_eq!(nodes, compose!(8, 16, 32));
+/// ```
+#[macro_export]
+macro_rules! composed_type {
+    (@nodes) => {
+        ()
+    };
+    (@nodes $head: ty $(, $tail: ty)*) => {
+        $crate::Node<$head, $crate::composed_type!(@nodes $($tail),*)>
+    };
+    ($(#[$attr: meta])* $vis: vis $name: ident = [$($item: ty),* $(,)?]) => {
+        $(#[$attr])*
+        $vis type $name = $crate::Composite<$crate::composed_type!(@nodes $($item),*)>;
+    };
+}
+
+/// Builds a composite like [`compose!`](crate::compose), but first checks
+/// that every object implements the given trait.
+///
+/// Without the check, a missing trait impl only shows up once the composite
+/// is used, as an error about the generated level traits for the whole node
+/// chain. With it, the compiler points at the offending element and names
+/// the trait it's missing.
+///
+/// # Example usage
+/// ```
+/// use std::fmt::Display;
+/// use zero_v::{compose, compose_typed};
+///
+/// let nodes = compose_typed!(Display; 1, "two");
+/// assert_eq!(nodes, compose!(1, "two"));
+/// ```
+///
+/// ```compile_fail
+/// use std::fmt::Display;
+/// use zero_v::compose_typed;
+///
+/// // error[E0277]: `Vec<i32>` doesn't implement `std::fmt::Display`
+/// let nodes = compose_typed!(Display; 1, vec![2]);
+/// ```
+#[macro_export]
+macro_rules! compose_typed {
+    ($trait_: path; $($item: expr),*) => {{
+        #[inline(always)]
+        fn __zero_v_check_member<T: $trait_>(item: T) -> T {
+            item
+        }
+
+        $crate::compose!($(__zero_v_check_member($item)),*)
+    }};
+}
+
+/// Builds a composite and a `Vec` of boxed trait objects from the same list
+/// of objects, returning them as a `(composite, vec)` pair. Handy for
+/// benchmarks and A/B tests comparing static and dynamic dispatch, which
+/// would otherwise have to keep two copies of the list in sync.
+///
+/// Each expression is evaluated twice, once for each collection, so they
+/// should be cheap constructors without side effects.
+///
+/// # Example usage
+/// ```
+/// use std::fmt::Display;
+/// use zero_v::{compose, compose_both};
+///
+/// let (nodes, objects) = compose_both!(Display; 1, "two");
+/// assert_eq!(nodes, compose!(1, "two"));
+/// assert_eq!(objects[1].to_string(), "two");
+/// ```
+#[macro_export]
+macro_rules! compose_both {
+    ($trait_: path; $($item: expr),* $(,)?) => {{
+        let objects: $crate::__private::Vec<$crate::__private::Box<dyn $trait_>> =
+            <[_]>::into_vec($crate::__private::Box::new([
+                $($crate::__private::Box::new($item) as $crate::__private::Box<dyn $trait_>),*
+            ]));
+        ($crate::compose!($($item),*), objects)
+    }};
+}
+
+/// Builds a composite of references to objects owned elsewhere, checking
+/// that every element is a reference.
+///
+/// Traits declared with `#[zero_v(trait_types, refs)]` are implemented for
+/// references to their implementors, so the composite can be iterated like
+/// one holding the objects themselves.
+///
+/// # Example usage
+/// ```
+/// use zero_v::{compose, compose_refs};
+///
+/// let (first, second) = (1, String::from("two"));
+/// let nodes = compose_refs!(&first, &second);
+/// assert_eq!(nodes, compose!(&1, &String::from("two")));
+/// ```
+///
+/// ```compile_fail
+/// use zero_v::compose_refs;
+///
+/// let nodes = compose_refs!(1, 2);
+/// ```
+#[macro_export]
+macro_rules! compose_refs {
+    ($($item: expr),* $(,)?) => {{
+        #[inline(always)]
+        fn __zero_v_check_ref<T: ?Sized>(item: &T) -> &T {
+            item
+        }
+
+        $crate::compose!($(__zero_v_check_ref($item)),*)
+    }};
+}
+
+/// Builds a composite from a list of types, with each object built by its
+/// type's `Default` impl.
+///
+/// # Example usage
+/// ```
+/// use zero_v::{compose, compose_default};
+///
+/// #[derive(Debug, Default, PartialEq)]
+/// struct Scale<const N: usize>;
+///
+/// let nodes = compose_default!(Scale<2>, u8, String);
+/// assert_eq!(nodes, compose!(Scale::<2>, 0u8, String::new()));
+/// ```
+#[macro_export]
+macro_rules! compose_default {
+    ($($item: ty),* $(,)?) => {
+        $crate::compose!($(<$item as ::core::default::Default>::default()),*)
     };
 }
 
 #[cfg(test)]
 mod test {
-    use crate::Level;
     use super::{Composite, Node};
 
     #[test]
@@ -172,16 +627,167 @@ mod test {
     }
 
     #[test]
-    fn can_iterate_collection_levels() {
-        let test_case_empty = compose!();
-        let observed: Vec<_> = test_case_empty.iter_levels().collect();
-        let expected: Vec<_> = vec![];
-        assert_eq!(observed, expected);
+    fn can_build_composites_in_const_contexts() {
+        type Pipeline = Composite<Node<u8, Node<&'static str, ()>>>;
+        const COMPOSITE: Pipeline = compose!(1, "two");
+        const FIRST: u8 = *COMPOSITE.head().data();
+        static PIPELINE: Pipeline = COMPOSITE;
+        assert_eq!(PIPELINE, compose!(1, "two"));
+        assert_eq!(FIRST, 1);
+    }
 
-        let test_case_filled = compose!("a", 27, "b");
-        let observed: Vec<_> = test_case_filled.iter_levels().collect();
-        let expected: Vec<_> = vec![0, 1, 2].into_iter().map(|value| Level::new(value)).collect();
-        assert_eq!(observed, expected);
+    #[test]
+    fn can_spread_composites_with_compose_macro() {
+        let defaults = compose!(1, 2);
+        assert_eq!(compose!(..defaults), compose!(1, 2));
+        assert_eq!(compose!(0, ..defaults, 3), compose!(0, 1, 2, 3));
+        assert_eq!(compose!(..defaults, ..compose_nodes!(3)), compose!(1, 2, 3));
+        assert_eq!(compose!(0, ..compose!()), compose!(0));
+    }
+
+    #[test]
+    fn can_gate_composite_elements_with_cfg() {
+        assert_eq!(compose!(#[cfg(any())] 0), compose!());
+        assert_eq!(compose!(#[cfg(all())] 0,), compose!(0));
+        assert_eq!(
+            compose!(#[cfg(test)] 0, #[cfg(not(test))] "one", 2, #[cfg(any())] 3),
+            compose!(0, 2)
+        );
+
+        const GATED: Composite<Node<u8, ()>> = compose!(#[cfg(any())] "a", 1);
+        assert_eq!(GATED, compose!(1u8));
+    }
+
+    #[test]
+    fn can_build_composites_with_compose_typed_macro() {
+        use std::fmt::Debug;
+
+        assert_eq!(compose_typed!(Debug;), Composite::new(()));
+        assert_eq!(
+            compose_typed!(Debug; 0, "a"),
+            Composite::new(Node::new(0, Node::base("a")))
+        );
+    }
+
+    #[test]
+    fn can_compare_and_hash_composites() {
+        use std::collections::HashSet;
+
+        assert!(compose!(1, "b") < compose!(1, "c"));
+        assert!(compose!(2, "a") > compose!(1, "c"));
+
+        let keys: HashSet<_> = vec![compose!(1, "b"), compose!(1, "b"), compose!(2, "b")]
+            .into_iter()
+            .collect();
+        assert_eq!(keys.len(), 2);
+    }
+
+    #[test]
+    fn can_name_composite_types() {
+        composed_type!(Empty = []);
+        composed_type!(
+            /// Documented aliases work too.
+            Pair = [u8, &'static str,]
+        );
+
+        let empty: Empty = compose!();
+        let pair: Pair = compose!(1, "two");
+        assert_eq!(empty, Composite::new(()));
+        assert_eq!(pair, Composite::new(Node::new(1u8, Node::base("two"))));
+    }
+
+    #[test]
+    fn can_build_static_and_dynamic_collections_together() {
+        use std::fmt::Debug;
+
+        let (nodes, objects) = compose_both!(Debug; 1, "two",);
+        assert_eq!(nodes, compose!(1, "two"));
+        assert_eq!(format!("{:?}", objects), r#"[1, "two"]"#);
+
+        let (empty, no_objects) = compose_both!(Debug;);
+        assert_eq!(empty, Composite::new(()));
+        assert!(no_objects.is_empty());
+    }
+
+    #[test]
+    fn can_build_composites_of_references() {
+        let (first, second) = (1, String::from("two"));
+        let nodes = compose_refs!(&first, &second);
+        assert_eq!(*nodes.head().data(), &1);
+        assert_eq!(compose_refs!(), Composite::new(()));
+    }
+
+    #[test]
+    fn can_build_composites_from_defaults() {
+        assert_eq!(compose_default!(), Composite::new(()));
+        assert_eq!(compose_default!(u8, String), compose!(0u8, String::new()));
+        assert_eq!(
+            Composite::<Node<u8, Node<String, ()>>>::default(),
+            compose_default!(u8, String)
+        );
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn composites_implement_defmt_format() {
+        fn assert_format<T: defmt::Format>(_value: &T) {}
+        assert_format(&compose!(1u8, "two", compose!(3u32)));
+    }
+
+    #[test]
+    fn can_access_nodes_through_accessors() {
+        let mut composite = compose!(1, "two");
+        assert_eq!(*composite.head().data(), 1);
+        assert_eq!(*composite.head().next().data(), "two");
+
+        *composite.head_mut().data_mut() = 3;
+        *composite.head_mut().next_mut().data_mut() = "four";
+        let (first, rest) = composite.into_head().into_inner();
+        assert_eq!((first, rest), (3, Node::base("four")));
+    }
+
+    #[test]
+    fn can_duplicate_composites() {
+        #[derive(Clone, Copy)]
+        struct Zst;
+
+        let composite = compose!(1, String::from("two"));
+        assert_eq!(composite.clone(), composite);
+
+        let copied = compose!(1, "two");
+        let (first, second) = (copied, copied);
+        assert_eq!(first, second);
+
+        fn assert_copy<T: Copy>(_value: T) {}
+        assert_copy(compose!(Zst, Zst));
+    }
+
+    #[test]
+    fn can_resolve_level_ranges() {
+        let composite = compose!("a", "b", "c");
+        assert_eq!(composite.level_range(..), 0..3);
+        assert_eq!(composite.level_range(1..3), 1..3);
+        assert_eq!(composite.level_range(1..=1), 1..2);
+        assert_eq!(composite.level_range(3..), 3..3);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn level_ranges_past_the_end_panic() {
+        compose!("a", "b", "c").level_range(1..4);
+    }
+
+    #[test]
+    #[should_panic(expected = "level range end")]
+    fn level_ranges_ending_at_usize_max_panic() {
+        compose!("a", "b", "c").level_range(..=usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "level range starts after")]
+    fn level_ranges_starting_after_usize_max_panic() {
+        use core::ops::Bound;
 
+        compose!("a", "b", "c").level_range((Bound::Excluded(usize::MAX), Bound::Unbounded));
     }
 }
@@ -0,0 +1,239 @@
+use core::marker::PhantomData;
+
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+Cursors step through a composite one object at a time, for manual
+integrations with traits the zero_v macro can't handle. Each step can land on
+an object of a different type, so the cursor hands out every object behind the
+same type, chosen by a CursorBound (usually a trait object for the user's
+trait). The bound says how to reach that type from each object, which is the
+one piece of per-trait code left for the user to write. Like the iterators
+generated by the macro, the cursor keeps a level and looks the object up from
+the head of the chain each time.
+*/
+
+/// The type a [`Cursor`] hands out the objects of a composite as.
+pub trait CursorBound {
+    /// The type every object is viewed as, usually `dyn YourTrait + 'a`,
+    /// where `'a` is how long the objects are borrowed for.
+    type Target<'a>: ?Sized + 'a;
+}
+
+/// Bounds under which objects of type `T` can be viewed.
+pub trait CursorBoundFor<T>: CursorBound {
+    /// View an object as the bound's target.
+    fn bind<'a>(item: &'a T) -> &'a Self::Target<'a>
+    where
+        T: 'a;
+}
+
+/// Chains of nodes whose objects can all be viewed under a bound.
+pub trait CursorNodes<L: CursorBound> {
+    /// The object at `level` in the chain, viewed under the bound, or None
+    /// if the chain is shorter than that.
+    fn bound_at_level(&self, level: usize) -> Option<&L::Target<'_>>;
+}
+
+impl<L: CursorBound> CursorNodes<L> for () {
+    #[inline]
+    fn bound_at_level(&self, _level: usize) -> Option<&L::Target<'_>> {
+        None
+    }
+}
+
+impl<L: CursorBoundFor<A>, A, B: NextNode + CursorNodes<L>> CursorNodes<L> for Node<A, B> {
+    #[inline]
+    fn bound_at_level(&self, level: usize) -> Option<&L::Target<'_>> {
+        match level {
+            0 => Some(L::bind(self.data())),
+            _ => self.next().bound_at_level(level - 1),
+        }
+    }
+}
+
+/// A position in a composite, which can be moved back and forth between its
+/// objects. Iterating over a cursor yields the objects from the current one
+/// onwards.
+pub struct Cursor<'a, L, A> {
+    nodes: &'a A,
+    level: usize,
+    bound: PhantomData<L>,
+}
+
+impl<'a, L: CursorBound, A: NextNode + CursorNodes<L>> Cursor<'a, L, A> {
+    /// The level the cursor is at.
+    #[inline]
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// The object the cursor is at, or None if it's past the last one.
+    #[inline]
+    pub fn current(&self) -> Option<&'a L::Target<'a>> {
+        self.nodes.bound_at_level(self.level)
+    }
+
+    /// Move to the next object. Returns false, leaving the cursor past the
+    /// last object, if there isn't one.
+    #[inline]
+    pub fn move_next(&mut self) -> bool {
+        if self.level < A::LEN {
+            self.level += 1;
+        }
+        self.level < A::LEN
+    }
+
+    /// Move to the previous object. Returns false, leaving the cursor where
+    /// it is, if it's already at the first one.
+    #[inline]
+    pub fn move_prev(&mut self) -> bool {
+        if self.level == 0 {
+            return false;
+        }
+        self.level -= 1;
+        true
+    }
+
+    /// Move to the object at `level`, or past the last object if the
+    /// composite is shorter than that.
+    #[inline]
+    pub fn seek(&mut self, level: usize) {
+        self.level = level.min(A::LEN);
+    }
+}
+
+impl<'a, L: CursorBound, A: NextNode + CursorNodes<L>> Iterator for Cursor<'a, L, A> {
+    type Item = &'a L::Target<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current();
+        self.move_next();
+        current
+    }
+}
+
+impl<L, A> Clone for Cursor<'_, L, A> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes,
+            level: self.level,
+            bound: PhantomData,
+        }
+    }
+}
+
+impl<A: NextNode> Composite<A> {
+    /// A cursor at the first of the composite's objects, which hands them
+    /// out viewed under the bound `L`.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::{compose, CursorBound, CursorBoundFor};
+    ///
+    /// trait Shape {
+    ///     fn area(&self) -> f64;
+    /// }
+    ///
+    /// struct Square(f64);
+    ///
+    /// impl Shape for Square {
+    ///     fn area(&self) -> f64 {
+    ///         self.0 * self.0
+    ///     }
+    /// }
+    ///
+    /// struct Rectangle(f64, f64);
+    ///
+    /// impl Shape for Rectangle {
+    ///     fn area(&self) -> f64 {
+    ///         self.0 * self.1
+    ///     }
+    /// }
+    ///
+    /// struct AsShape;
+    ///
+    /// impl CursorBound for AsShape {
+    ///     type Target<'a> = dyn Shape + 'a;
+    /// }
+    ///
+    /// impl<T: Shape> CursorBoundFor<T> for AsShape {
+    ///     fn bind<'a>(item: &'a T) -> &'a (dyn Shape + 'a)
+    ///     where
+    ///         T: 'a,
+    ///     {
+    ///         item
+    ///     }
+    /// }
+    ///
+    /// let shapes = compose!(Square(2.0), Rectangle(2.0, 3.0));
+    /// let mut cursor = shapes.cursor::<AsShape>();
+    /// assert_eq!(cursor.current().map(|shape| shape.area()), Some(4.0));
+    /// assert!(cursor.move_next());
+    /// assert_eq!(cursor.current().map(|shape| shape.area()), Some(6.0));
+    /// assert_eq!(shapes.cursor::<AsShape>().map(|shape| shape.area()).sum::<f64>(), 10.0);
+    /// ```
+    #[inline]
+    pub fn cursor<L: CursorBound>(&self) -> Cursor<'_, L, A>
+    where
+        A: CursorNodes<L>,
+    {
+        Cursor {
+            nodes: self.head(),
+            level: 0,
+            bound: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::fmt::Debug;
+
+    use super::{CursorBound, CursorBoundFor};
+    use crate::compose;
+
+    struct AsDebug;
+
+    impl CursorBound for AsDebug {
+        type Target<'a> = dyn Debug + 'a;
+    }
+
+    impl<T: Debug> CursorBoundFor<T> for AsDebug {
+        fn bind<'a>(item: &'a T) -> &'a (dyn Debug + 'a)
+        where
+            T: 'a,
+        {
+            item
+        }
+    }
+
+    fn debug(item: Option<&dyn Debug>) -> Option<String> {
+        item.map(|item| format!("{:?}", item))
+    }
+
+    #[test]
+    fn can_move_cursors() {
+        let composite = compose!(1u8, "two", 3.0f64);
+        let mut cursor = composite.cursor::<AsDebug>();
+        assert_eq!(debug(cursor.current()), Some("1".to_string()));
+        assert!(!cursor.move_prev());
+
+        assert!(cursor.move_next() && cursor.move_next());
+        assert_eq!((cursor.level(), debug(cursor.current())), (2, Some("3.0".to_string())));
+        assert!(!cursor.move_next());
+        assert_eq!((cursor.level(), debug(cursor.current())), (3, None));
+        assert!(!cursor.move_next());
+
+        assert!(cursor.move_prev());
+        cursor.seek(1);
+        let rest: Vec<_> = cursor.clone().map(|item| format!("{:?}", item)).collect();
+        assert_eq!(rest, vec!["\"two\"", "3.0"]);
+        assert_eq!(cursor.level(), 1);
+
+        cursor.seek(10);
+        assert_eq!(cursor.level(), 3);
+        assert!(compose!().cursor::<AsDebug>().current().is_none());
+    }
+}
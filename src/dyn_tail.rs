@@ -0,0 +1,141 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FromIterator;
+
+use crate::append::IntoNodes;
+use crate::composite::{HasLength, NextNode};
+
+/*
+A dynamic tail lets a composite end in a list of boxed trait objects in place
+of the unit type, so the built-in objects at the front stay statically
+dispatched while plugins loaded at runtime go through vtables at the end.
+With the `dyn_tail` option, the zero_v macro implements its level trait for
+DynTail<dyn Trait>, giving the tail's objects the levels after the static
+ones. The number of objects in the tail isn't known until runtime, so it
+doesn't count towards the const length of the composite.
+*/
+
+/// The end of a node chain holding any number of boxed objects, usually
+/// trait objects.
+pub struct DynTail<T: ?Sized> {
+    objects: Vec<Box<T>>,
+}
+
+impl<T: ?Sized> DynTail<T> {
+    /// An empty tail.
+    pub fn new() -> Self {
+        Self {
+            objects: Vec::new(),
+        }
+    }
+
+    /// Add an object to the end of the tail.
+    pub fn push(&mut self, object: Box<T>) {
+        self.objects.push(object);
+    }
+
+    /// The number of objects in the tail.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Whether the tail holds no objects.
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Get a reference to the object at the given index, if there is one.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.objects.get(index).map(|object| &**object)
+    }
+
+    /// Get a mutable reference to the object at the given index, if there is
+    /// one.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.objects.get_mut(index).map(|object| &mut **object)
+    }
+
+    /// Iterate over the objects, in order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.objects.iter().map(|object| &**object)
+    }
+
+    /// Iterate mutably over the objects, in order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.objects.iter_mut().map(|object| &mut **object)
+    }
+
+    /// Unwrap the objects.
+    pub fn into_inner(self) -> Vec<Box<T>> {
+        self.objects
+    }
+}
+
+impl<T: ?Sized> Default for DynTail<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ?Sized> From<Vec<Box<T>>> for DynTail<T> {
+    fn from(objects: Vec<Box<T>>) -> Self {
+        Self { objects }
+    }
+}
+
+impl<T: ?Sized> FromIterator<Box<T>> for DynTail<T> {
+    fn from_iter<I: IntoIterator<Item = Box<T>>>(iter: I) -> Self {
+        Self {
+            objects: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for DynTail<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynTail")
+            .field("len", &self.objects.len())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> HasLength for DynTail<T> {
+    fn get_len(&self) -> usize {
+        self.objects.len()
+    }
+}
+
+impl<T: ?Sized> NextNode for DynTail<T> {
+    const LEN: usize = 0;
+}
+
+impl<T: ?Sized> IntoNodes for DynTail<T> {
+    type Nodes = Self;
+
+    #[inline]
+    fn into_nodes(self) -> Self::Nodes {
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fmt::Display;
+
+    use super::DynTail;
+    use crate::compose;
+
+    #[test]
+    fn can_end_composites_with_a_dyn_tail() {
+        let tail: DynTail<dyn Display> = vec![Box::new(2) as Box<dyn Display>].into();
+        let mut composite = compose!(1, ..tail);
+        assert_eq!(composite.len(), 1);
+
+        composite.head_mut().next_mut().push(Box::new("three"));
+        let objects = composite.head().next().iter().map(|o| o.to_string());
+        assert_eq!(objects.collect::<Vec<_>>(), vec!["2", "three"]);
+    }
+}
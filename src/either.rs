@@ -0,0 +1,61 @@
+/*
+Either lets a composite hold one of two object types in a given position,
+picked at runtime (from config, say), without boxing. The position still has
+a single concrete type, so the rest of the composite stays statically
+dispatched, and the only dynamic part is a branch on the variant. The
+`either` option on the zero_v macro implements a trait for Either<L, R>
+wherever both L and R implement it.
+*/
+
+/// An object of one of two types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Either<L, R> {
+    /// An object of the first type.
+    Left(L),
+    /// An object of the second type.
+    Right(R),
+}
+
+impl<L, R> Either<L, R> {
+    /// Whether this holds an object of the first type.
+    pub fn is_left(&self) -> bool {
+        matches!(self, Either::Left(_))
+    }
+
+    /// Whether this holds an object of the second type.
+    pub fn is_right(&self) -> bool {
+        matches!(self, Either::Right(_))
+    }
+
+    /// Get a reference to the object of the first type, if that's what this
+    /// holds.
+    pub fn left(&self) -> Option<&L> {
+        match self {
+            Either::Left(left) => Some(left),
+            Either::Right(_) => None,
+        }
+    }
+
+    /// Get a reference to the object of the second type, if that's what
+    /// this holds.
+    pub fn right(&self) -> Option<&R> {
+        match self {
+            Either::Left(_) => None,
+            Either::Right(right) => Some(right),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Either;
+
+    #[test]
+    fn can_inspect_either_variants() {
+        let left: Either<u8, &str> = Either::Left(1);
+        let right: Either<u8, &str> = Either::Right("two");
+        assert!(left.is_left() && right.is_right());
+        assert_eq!((left.left(), left.right()), (Some(&1), None));
+        assert_eq!((right.left(), right.right()), (None, Some(&"two")));
+    }
+}
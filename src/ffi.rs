@@ -0,0 +1,140 @@
+/*
+With the ffi feature enabled, Node and Composite are laid out as repr(C), so
+a composite's layout is fixed by the types it holds rather than left to the
+compiler. That's what a C host embedding a composite (or being handed a
+pointer to one) needs.
+
+extern_c builds the other half: an extern "C" entry point for one specific,
+fully monomorphized composite, which runs an iteration method over it and
+copies the outputs into a buffer owned by the caller. Everything generic
+stays on the Rust side of the boundary.
+*/
+
+/// Generates an `extern "C"` function which executes an iteration method over
+/// a specific composite and writes each output into a caller-provided buffer.
+///
+/// The generated function takes the given arguments followed by an `out`
+/// pointer and an `out_len` capacity, writes at most `out_len` outputs, and
+/// returns the number of outputs written. The composite expression is
+/// evaluated on every call, so it can either build the composite (which is
+/// usually free for composites of small, simple objects) or borrow one which
+/// lives elsewhere.
+///
+/// Only available with the `ffi` feature.
+///
+/// # Safety
+///
+/// The generated function is `unsafe` to call: `out` must be valid for
+/// writes of `out_len` outputs. The argument and output types should be
+/// FFI-safe for the function to be useful from C.
+///
+/// # Example usage
+/// ```
+/// use zero_v::{compose, extern_c, zero_v};
+///
+/// #[zero_v(trait_types)]
+/// trait IntOp {
+///     fn execute(&self, input: usize) -> usize;
+/// }
+///
+/// struct Adder(usize);
+///
+/// impl IntOp for Adder {
+///     fn execute(&self, input: usize) -> usize {
+///         input + self.0
+///     }
+/// }
+///
+/// extern_c! {
+///     /// Run the pipeline over an input.
+///     fn run_pipeline(input: usize) -> usize {
+///         composite: compose!(Adder(1), Adder(2)),
+///         method: iter_execute,
+///     }
+/// }
+///
+/// let mut out = [0usize; 4];
+/// let written = unsafe { run_pipeline(10, out.as_mut_ptr(), out.len()) };
+/// assert_eq!(&out[..written], &[11, 12]);
+/// ```
+#[macro_export]
+macro_rules! extern_c {
+    ($(#[$attr: meta])* fn $name: ident($($arg: ident: $arg_ty: ty),* $(,)?) -> $out: ty {
+        composite: $composite: expr,
+        method: $method: ident $(,)?
+    }) => {
+        $(#[$attr])*
+        #[no_mangle]
+        pub unsafe extern "C" fn $name($($arg: $arg_ty,)* out: *mut $out, out_len: usize) -> usize {
+            let composite = $composite;
+            let mut written = 0;
+            for output in composite.$method($($arg),*).take(out_len) {
+                out.add(written).write(output);
+                written += 1;
+            }
+            written
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{compose, Composite, Node};
+
+    trait Double {
+        fn double(&self, input: u32) -> u32;
+    }
+
+    impl Double for u32 {
+        fn double(&self, input: u32) -> u32 {
+            self * input * 2
+        }
+    }
+
+    trait IterDouble {
+        fn iter_double(&self, input: u32) -> std::vec::IntoIter<u32>;
+    }
+
+    impl IterDouble for Composite<Node<u32, Node<u32, Node<u32, ()>>>> {
+        fn iter_double(&self, input: u32) -> std::vec::IntoIter<u32> {
+            let first = self.head();
+            let second = first.next();
+            let third = second.next();
+            vec![
+                first.data().double(input),
+                second.data().double(input),
+                third.data().double(input),
+            ]
+            .into_iter()
+        }
+    }
+
+    extern_c! {
+        fn run_double(input: u32) -> u32 {
+            composite: compose!(1u32, 2u32, 3u32),
+            method: iter_double,
+        }
+    }
+
+    #[test]
+    fn can_execute_through_extern_c_shim() {
+        let mut out = [0u32; 4];
+        let written = unsafe { run_double(5, out.as_mut_ptr(), out.len()) };
+        assert_eq!(&out[..written], &[10, 20, 30]);
+
+        let mut out = [0u32; 2];
+        let written = unsafe { run_double(5, out.as_mut_ptr(), out.len()) };
+        assert_eq!(&out[..written], &[10, 20]);
+    }
+
+    #[test]
+    fn nodes_are_laid_out_in_declaration_order() {
+        let composite = compose!(1u8, 2u32, 3u8);
+        let head = composite.head();
+        let base = head as *const _ as usize;
+        assert_eq!(&composite as *const _ as usize, base);
+        assert_eq!(head.data() as *const _ as usize, base);
+        assert!((head.next() as *const _ as usize) > base);
+        assert_eq!(std::mem::size_of_val(head), 12);
+    }
+}
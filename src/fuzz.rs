@@ -0,0 +1,50 @@
+use arbitrary::{size_hint, Arbitrary, Result, Unstructured};
+
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+arbitrary support for composites, behind the arbitrary feature. The shape of
+a composite is fixed by its type, so only the objects are generated, one
+after another in level order. This lets fuzzers and property tests (e.g.
+proptest through proptest-arbitrary-interop) try random configurations of
+each object in a fixed pipeline.
+*/
+
+impl<'a, A: Arbitrary<'a>, B: NextNode + Arbitrary<'a>> Arbitrary<'a> for Node<A, B> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let data = A::arbitrary(u)?;
+        Ok(Self::new(data, B::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        size_hint::and(A::size_hint(depth), B::size_hint(depth))
+    }
+}
+
+impl<'a, A: NextNode + Arbitrary<'a>> Arbitrary<'a> for Composite<A> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self::new(A::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        A::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use crate::{compose, Composite, Node};
+
+    type Pipeline = Composite<Node<u8, Node<bool, Node<u16, ()>>>>;
+
+    #[test]
+    fn can_generate_arbitrary_composites() {
+        let bytes = [7, 1, 0x34, 0x12];
+        let mut u = Unstructured::new(&bytes);
+        let pipeline = Pipeline::arbitrary(&mut u).unwrap();
+        assert_eq!(pipeline, compose!(7, true, 0x1234));
+        assert_eq!(Pipeline::size_hint(0), (4, Some(4)));
+    }
+}
@@ -0,0 +1,133 @@
+use frunk::hlist::{HCons, HNil};
+
+use crate::append::IntoNodes;
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+Conversions between composites and frunk's HLists, behind the frunk feature.
+An HList is the same shape as a node chain (a head object and the rest of the
+list, ending in HNil), so each conversion just moves the objects across one
+cons cell at a time. HLists also implement IntoNodes, so they can be spliced
+into compose! with `..`.
+
+The orphan rules don't allow From<Composite<_>> for HCons<_, _>, which is
+frunk's type, so that direction is an Into impl.
+*/
+
+impl IntoNodes for HNil {
+    type Nodes = ();
+
+    #[inline]
+    fn into_nodes(self) -> Self::Nodes {}
+}
+
+impl<H, T: IntoNodes> IntoNodes for HCons<H, T> {
+    type Nodes = Node<H, T::Nodes>;
+
+    #[inline]
+    fn into_nodes(self) -> Self::Nodes {
+        Node::new(self.head, self.tail.into_nodes())
+    }
+}
+
+/// Node chains which can be converted into frunk HLists.
+pub trait IntoHList {
+    /// The HList holding the objects.
+    type HList;
+
+    /// Consume the chain and return an HList holding its objects, in order.
+    fn into_hlist(self) -> Self::HList;
+}
+
+impl IntoHList for () {
+    type HList = HNil;
+
+    #[inline]
+    fn into_hlist(self) -> Self::HList {
+        HNil
+    }
+}
+
+impl<A, B: NextNode + IntoHList> IntoHList for Node<A, B> {
+    type HList = HCons<A, B::HList>;
+
+    #[inline]
+    fn into_hlist(self) -> Self::HList {
+        let (head, next) = self.into_inner();
+        HCons {
+            head,
+            tail: next.into_hlist(),
+        }
+    }
+}
+
+impl<A: NextNode + IntoHList> Composite<A> {
+    /// Consume the composite and return a frunk HList holding its objects,
+    /// in order.
+    ///
+    /// # Example usage
+    /// ```
+    /// use frunk::hlist;
+    /// use zero_v::compose;
+    ///
+    /// assert_eq!(compose!(1u8, "two").into_hlist(), hlist![1u8, "two"]);
+    /// ```
+    #[inline]
+    pub fn into_hlist(self) -> A::HList {
+        self.into_head().into_hlist()
+    }
+}
+
+impl From<HNil> for Composite<()> {
+    #[inline]
+    fn from(_list: HNil) -> Self {
+        Composite::new(())
+    }
+}
+
+impl<H, T: IntoNodes> From<HCons<H, T>> for Composite<Node<H, T::Nodes>> {
+    #[inline]
+    fn from(list: HCons<H, T>) -> Self {
+        Composite::new(list.into_nodes())
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl Into<HNil> for Composite<()> {
+    #[inline]
+    fn into(self) -> HNil {
+        self.into_hlist()
+    }
+}
+
+#[allow(clippy::from_over_into)]
+impl<A, B: NextNode + IntoHList> Into<HCons<A, B::HList>> for Composite<Node<A, B>> {
+    #[inline]
+    fn into(self) -> HCons<A, B::HList> {
+        self.into_hlist()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use frunk::hlist::{HCons, HNil};
+    use frunk::{hlist, HList};
+
+    use crate::{compose, Composite};
+
+    #[test]
+    fn can_convert_between_composites_and_hlists() {
+        let list: HList![u8, &str, f64] = compose!(1u8, "two", 3.0f64).into();
+        assert_eq!(list, hlist![1u8, "two", 3.0f64]);
+        assert_eq!(Composite::from(list), compose!(1u8, "two", 3.0f64));
+
+        let empty: HNil = compose!().into();
+        assert_eq!(Composite::from(empty), compose!());
+    }
+
+    #[test]
+    fn can_splice_hlists_into_composites() {
+        let list: HCons<u8, HCons<&str, HNil>> = hlist![1u8, "two"];
+        assert_eq!(compose!(0u8, ..list, 3.0f64), compose!(0u8, 1u8, "two", 3.0f64));
+    }
+}
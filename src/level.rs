@@ -1,49 +1,175 @@
-use std::marker::PhantomData;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+
+use crate::composite::{Composite, NextNode};
 
 /*
 Level is a wrapper around an array index. The purpose is to make
 zero_v function execute_at_level function signatures robust to users
 mixing up argument order in the case that their function signature
-takes a usize argument. It also guarantees that the index you're passing in
-maps to a valid level for your composite.
+takes a usize argument. It also keeps the index you're passing in within the
+levels of your composite.
 
 For example, lets imagine you have a trait that has a function with signature
 do_x(&self, input: usize). Then the derived do_x_at_level function
 would have signature do_x_at_level(&self, input: usize, level: usize).
 This is the problem. There is nothing at the type system level preventing
 the library user from mixing these two arguments up since they both have the
-same type. Level is designed to act as a slot-in replacement, so you end up
-with signature do_x_at_level(&self, input: usize, level: Level) and the
-compiler can protect you if you ever mix the order up.
+same type. Level is designed to act as a slot-in replacement, so the macro
+also generates do_x_at(&self, input: usize, level: Level<Self>) and the
+compiler can protect you if you ever mix the order up. The usize version is
+kept for backwards compatibility.
 
 Making the level generic over your type solves another problem. If level
 wasn't specific to the type it will be used on, then you could take a level
 from composite A and use it on composite B. If the level from composite
 A didn't exist in composite B, you could get a value of None (if using Option
-returns) or a panic (if unwrapping). To prevent this, the only way to
-get a level for a composite is to call iter_levels() on an instance. This means
-that, as long as the levels returned by iter_level for a composite are always
-valid for that type, you can safely unwrap your return value/ are guaranteed
-there is an element at that level.
+returns) or a panic (if unwrapping). To prevent this, the only ways to
+get a level for a composite are to call iter_levels() on an instance or
+level_of() for a type the composite holds, both generated by the zero_v
+macro, which counts each object in a nested composite as a level of its own.
+The levels they return are always within the composite's levels, with one
+exception: a dyn tail's objects only have levels on the instance they came
+from, since tails of the same type can hold different numbers of objects.
+
+Being in range doesn't mean there's always an output at a level though. An
+empty Option or a disabled Toggle still takes up a level, but running it
+returns None, which is why the level based methods still return Options.
 */
 
 /// The level of an object in a collection
-#[derive(Clone, Debug, PartialEq)]
+///
+/// Levels only compare against (and hash like) their index, so they can be
+/// stored, sorted and used as map keys whatever the collection type is.
 #[repr(transparent)]
-pub struct Level<T> {
+pub struct Level<T: ?Sized> {
     ///The index of the target object
     value: usize,
     phantom: PhantomData<T>,
 }
 
-impl<T> Level<T> {
-    pub(crate) fn new(value: usize) -> Self {
+impl<T: ?Sized> Level<T> {
+    pub(crate) const fn new(value: usize) -> Self {
         Self {
             value,
             phantom: PhantomData {},
         }
     }
-    pub fn value(&self) -> usize {
+
+    /// The index of the object this level refers to.
+    pub const fn value(&self) -> usize {
         self.value
     }
 }
+
+impl<A: NextNode> Level<Composite<A>> {
+    /// The level before this one, or `None` if this is the composite's first
+    /// level.
+    ///
+    /// Stepping the other way is done by the `next()` method generated by the
+    /// zero_v macro, since only the generated traits know how many levels a
+    /// composite's objects take up.
+    pub const fn prev(&self) -> Option<Self> {
+        match self.value.checked_sub(1) {
+            Some(value) => Some(Self::new(value)),
+            None => None,
+        }
+    }
+}
+
+// The trait impls are written by hand so they don't require anything of the
+// collection type, which is only ever used as a marker.
+impl<T: ?Sized> Clone for Level<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for Level<T> {}
+
+impl<T: ?Sized> fmt::Debug for Level<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Level").field(&self.value).finish()
+    }
+}
+
+impl<T: ?Sized> PartialEq for Level<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: ?Sized> Eq for Level<T> {}
+
+impl<T: ?Sized> PartialOrd for Level<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: ?Sized> Ord for Level<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T: ?Sized> Hash for Level<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: ?Sized> defmt::Format for Level<T> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "Level({})", self.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::Level;
+
+    struct NotClone;
+
+    #[test]
+    fn levels_are_copy_ord_and_hash_for_any_collection() {
+        type NotCloneTriple = crate::Composite<
+            crate::Node<NotClone, crate::Node<NotClone, crate::Node<NotClone, ()>>>,
+        >;
+        let mut levels: Vec<_> = (0..3).rev().map(Level::<NotCloneTriple>::new).collect();
+        let first = levels[0];
+        assert_eq!(first, levels[0]);
+
+        levels.sort();
+        assert_eq!(
+            levels.iter().map(|level| level.value()).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert!(levels[0] < levels[1]);
+
+        let unique: HashSet<_> = levels.iter().chain(levels.iter()).copied().collect();
+        assert_eq!(unique.len(), 3);
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn levels_implement_defmt_format() {
+        fn assert_format<T: defmt::Format>(_value: &T) {}
+        assert_format(&Level::<crate::Composite<crate::Node<NotClone, ()>>>::new(0));
+    }
+
+    #[test]
+    fn can_step_back_between_levels() {
+        type Letters = crate::Composite<crate::Node<&'static str, crate::Node<&'static str, ()>>>;
+        let first = Level::<Letters>::new(0);
+        let second = Level::<Letters>::new(1);
+
+        assert_eq!(first.prev(), None);
+        assert_eq!(second.prev(), Some(first));
+    }
+}
@@ -1,4 +1,5 @@
 #![macro_use]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 /*!
 Zero_V is an experiment in defining behavior over collections of
 objects implementing some trait without dynamic polymorphism.
@@ -76,6 +77,18 @@ arguments) then you can use the crate without the zero_v attribute macro with:
 zero_v = { version = "0.2.0", default-features = false }
 ```
 
+zero_v needs rustc 1.78 or later. The zero_v macro attaches
+`#[diagnostic::on_unimplemented]` notes to the traits it generates, which
+older compilers reject, and the manifest relies on features (such as weak
+`dep?/feature` dependencies) from earlier releases.
+
+zero_v only needs `core` and `alloc`, so it supports `no_std` targets with an
+allocator. Turning off the default `std` feature (while keeping the zero_v
+macro, with `default-features = false, features = ["gen"]`) removes the
+things which need the standard library: `MutexNode` (and the `shared`
+option's impls for it) and the `bench` module. The code generated by the
+macro doesn't use std either way.
+
 # Implementing Zero_V for your type with the zero_v macro
 
 If your trait  doesn't involve arguments with lifetimes or generics then the
@@ -135,9 +148,9 @@ trait IntOpAtLevel {
 impl<A: IntOp, B: NextNode + IntOpAtLevel> IntOpAtLevel for Node<A, B> {
     fn execute_at_level(&self, input: usize, level: usize) -> Option<usize> {
         if level == 0 {
-            Some(self.data.execute(input))
+            Some(self.data().execute(input))
         } else {
-            self.next.execute_at_level(input, level - 1)
+            self.next().execute_at_level(input, level - 1)
         }
     }
 }
@@ -153,7 +166,7 @@ impl IntOpAtLevel for () {
 // your collection by index in a way that doesn't fit in with the iterator API.
 impl <A: NextNode + IntOpAtLevel> IntOpAtLevel for Composite<A> {
     fn execute_at_level(&self, input: usize, level: usize) -> Option<usize> {
-        self.head.execute_at_level(input, level)
+        self.head().execute_at_level(input, level)
     }
 }
 
@@ -203,11 +216,19 @@ trait IterExecute<Nodes: NextNode + IntOpAtLevel> {
 // your iterator.
 impl<Nodes: NextNode + IntOpAtLevel>IterExecute<Nodes> for Composite<Nodes> {
     fn iter_execute(&self, input: usize) -> CompositeIterator<'_, Nodes> {
-        CompositeIterator::new(&self.head, input)
+        CompositeIterator::new(self.head(), input)
     }
 }
 ```
 
+With the `optional` option (`#[zero_v(trait_types, optional)]`), the
+generated code also skips over objects composed as `Option`s while they're
+`None`. To do the same by hand, implement the level execution trait
+for `Node<Option<A>, B>` as well (returning
+`self.data().as_ref().map(|data| data.execute(input))` at level 0), and have
+`next` keep moving past `None`s until the level reaches the number of levels
+in the collection, rather than stopping at the first one.
+
 # Benchmarks
 Some example benchmarks for Zero_V are captured below. The source takes two
 sets of objects implementing a simple trait transforming a usize to another usize,
@@ -215,7 +236,8 @@ sets of objects implementing a simple trait transforming a usize to another usiz
 and then implements the benchmark with one dynamic collection (the standard
 vtable way) and one static collection (using Zero_V) for each of those sets.
 Results are given below (Hardware was a Lenovo T430 and benchmarks were
-compiled using rustc 1.52.1, so your mileage may vary)
+compiled using rustc 1.52.1, from before zero_v needed 1.78, so your mileage
+may vary)
 ![alt text](https://raw.githubusercontent.com/fergaljoconnor/zero_v/main/blob/Zero_V_Benchmarks.png)
 Zero_V comes out of this benchmark looking pretty good, but I do want to
 stress the following caveats.
@@ -242,18 +264,115 @@ stress the following caveats.
   that anyone who wants to hardcode an optimized solution like
   that has the tools to do so. If you're good to your compiler, your
   compiler will be good to you (occasional compiler bugs notwithstanding).
+
+If you just want a rough answer before setting up a benchmark suite, the
+`bench` feature adds `zero_v::bench::compare`, which takes closures for the
+static, dynamic and baseline versions of a workload and reports their
+relative timings without pulling in any extra dependencies.
 */
 
+extern crate alloc;
+
+mod append;
+mod assert;
+mod at;
+mod audit;
+#[cfg(feature = "bench")]
+pub mod bench;
+mod builder;
+mod capability;
 mod composite;
+mod cursor;
+mod dyn_tail;
+mod either;
+#[cfg(feature = "ffi")]
+mod ffi;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+#[cfg(feature = "frunk")]
+mod hlist;
 mod level;
+mod map;
+mod named;
+pub mod prelude;
+mod priority;
+mod project;
+mod repeat;
+mod replace;
+mod select;
+#[cfg(feature = "serde")]
+mod serialize;
+mod shared;
+mod signature;
+mod split;
+#[cfg(feature = "stats")]
+pub mod stats;
 #[cfg(test)]
 mod test;
-
-pub use composite::{Composite, HasLength, NextNode, Node};
+pub mod testing;
+mod thread_safety;
+mod toggle;
+mod tuple;
+mod view;
+mod visit;
+
+pub use append::{Append, IntoNodes};
+#[doc(hidden)]
+pub use assert::assert_outputs_eq as __assert_outputs_eq;
+pub use assert::NamedOutputs;
+pub use at::{At, MAX_AT_LEVEL};
+pub use audit::{Audit, NodeAudit};
+pub use builder::ComposeBuilder;
+#[doc(hidden)]
+pub use capability::CapabilityCheck;
+pub use capability::{
+    check_capabilities, Capabilities, RequiredCapabilities, Requires, UnmetRequirement,
+};
+pub use composite::{resolve_level_range, Composite, HasLength, NextNode, Node};
+pub use cursor::{Cursor, CursorBound, CursorBoundFor, CursorNodes};
+pub use dyn_tail::DynTail;
+pub use either::Either;
+#[cfg(feature = "frunk")]
+pub use hlist::IntoHList;
 pub use level::Level;
+pub use map::{MapNodes, Mapper};
+pub use named::{Named, SelectNamed};
+pub use priority::{Priorities, Priority, MAX_PRIORITY_NODES};
+pub use project::{NodeProjection, NodeProjectionRef};
+pub use repeat::Repeat;
+pub use replace::ReplaceAt;
+pub use select::{Here, Select, There};
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serialize::{DeserializeNodes, SerializeNodes};
+pub use shared::RefCellNode;
+#[cfg(feature = "std")]
+pub use shared::{MutexNode, PoisonPolicy};
+pub use signature::{hash_signature, DebugStructure, NodeTypeIds, NodeTypeNames};
+pub use split::SplitAt;
+pub use thread_safety::{SendComposite, SyncComposite};
+pub use toggle::Toggle;
+pub use tuple::{IntoParts, TupleComposite};
+pub use view::NodeViews;
+pub use visit::{Visit, Visitor};
+
+// Paths to the items from alloc used by the generated code, which can't name
+// the alloc crate itself since the crate using the macro might not link it,
+// along with a way for the generated code to make levels.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::boxed::Box;
+    pub use alloc::rc::Rc;
+    pub use alloc::sync::Arc;
+    pub use alloc::vec::Vec;
+
+    pub const fn level<T: ?Sized>(value: usize) -> crate::Level<T> {
+        crate::Level::new(value)
+    }
+}
 
 #[cfg(feature = "gen")]
 extern crate zero_v_gen;
 
 #[cfg(feature = "gen")]
-pub use zero_v_gen::zero_v;
+pub use zero_v_gen::{compose_balanced, compose_from_spec, compose_repeat, zero_v};
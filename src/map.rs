@@ -0,0 +1,123 @@
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+Mapping transforms every object in a composite with the same generic function,
+giving a new composite whose node types are the outputs. Closures can't be
+generic over their input type, so the function is a Mapper, whose output type
+is a generic associated type of the input type (wrapping every plugin in an
+Instrumented<T>, for example). The new chain is rebuilt node by node, like
+Append does.
+*/
+
+/// A transformation which can be applied to objects of any type.
+pub trait Mapper {
+    /// The object a `T` is transformed into.
+    type Out<T>;
+
+    /// Transform one object.
+    fn map<T>(&mut self, item: T) -> Self::Out<T>;
+}
+
+/// Chains of nodes whose objects can be transformed by a [`Mapper`].
+pub trait MapNodes<M: Mapper> {
+    /// The chain holding the transformed objects.
+    type Output: NextNode;
+
+    /// Consume the chain and transform each object in order.
+    fn map_nodes(self, mapper: &mut M) -> Self::Output;
+}
+
+impl<M: Mapper> MapNodes<M> for () {
+    type Output = ();
+
+    #[inline]
+    fn map_nodes(self, _mapper: &mut M) -> Self::Output {}
+}
+
+impl<M: Mapper, A, B: NextNode + MapNodes<M>> MapNodes<M> for Node<A, B> {
+    type Output = Node<M::Out<A>, B::Output>;
+
+    #[inline]
+    fn map_nodes(self, mapper: &mut M) -> Self::Output {
+        let (data, next) = self.into_inner();
+        let data = mapper.map(data);
+        Node::new(data, next.map_nodes(mapper))
+    }
+}
+
+impl<A: NextNode> Composite<A> {
+    /// Consume the composite and return one holding each of its objects
+    /// transformed by `mapper`, in order.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::{compose, Mapper};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Instrumented<T> {
+    ///     inner: T,
+    ///     id: usize,
+    /// }
+    ///
+    /// struct Instrument {
+    ///     next_id: usize,
+    /// }
+    ///
+    /// impl Mapper for Instrument {
+    ///     type Out<T> = Instrumented<T>;
+    ///
+    ///     fn map<T>(&mut self, inner: T) -> Instrumented<T> {
+    ///         self.next_id += 1;
+    ///         Instrumented { inner, id: self.next_id }
+    ///     }
+    /// }
+    ///
+    /// let composite = compose!(1u8, "two").map(Instrument { next_id: 0 });
+    /// assert_eq!(
+    ///     composite,
+    ///     compose!(Instrumented { inner: 1u8, id: 1 }, Instrumented { inner: "two", id: 2 })
+    /// );
+    /// ```
+    pub fn map<M: Mapper>(self, mut mapper: M) -> Composite<A::Output>
+    where
+        A: MapNodes<M>,
+    {
+        Composite::new(self.into_head().map_nodes(&mut mapper))
+    }
+}
+
+impl<A, B: NextNode> Node<A, B> {
+    /// Consume this node and the rest of the chain, and return a chain
+    /// holding each object transformed by `mapper`, in order.
+    pub fn map<M: Mapper>(self, mut mapper: M) -> <Self as MapNodes<M>>::Output
+    where
+        Self: MapNodes<M>,
+    {
+        self.map_nodes(&mut mapper)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Mapper;
+    use crate::{compose, compose_nodes};
+
+    struct Boxed;
+
+    impl Mapper for Boxed {
+        type Out<T> = Box<T>;
+
+        fn map<T>(&mut self, item: T) -> Box<T> {
+            Box::new(item)
+        }
+    }
+
+    #[test]
+    fn can_map_objects() {
+        let composite = compose!(1u8, "two").map(Boxed);
+        assert_eq!(composite, compose!(Box::new(1u8), Box::new("two")));
+
+        let nodes = compose_nodes!(3.0f64).map(Boxed);
+        assert_eq!(*nodes.data(), Box::new(3.0));
+    }
+}
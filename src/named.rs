@@ -0,0 +1,209 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::composite::{Composite, NextNode, Node};
+use crate::select::{Here, There};
+
+/*
+Names let an object be looked up by a key rather than its level, so
+per-plugin configuration keeps working when the plugin list is reordered.
+Stable Rust doesn't allow &str const parameters, so keys are marker types:
+a Named<K, T> node wrapper tags its object with the key K, and SelectNamed
+finds the node tagged with a given key in the same way Select finds a node by
+the type of its object. Named objects are executed like any other object, the
+zero_v macro always forwarding through the wrapper (for traits without type
+parameters), since Named has no other use which could conflict with that.
+*/
+
+/// A node wrapper tagging an object with the marker type `K`, so it can be
+/// looked up with [`Composite::get_named`].
+pub struct Named<K, T> {
+    inner: T,
+    key: PhantomData<fn() -> K>,
+}
+
+impl<K, T> Named<K, T> {
+    /// Tag an object with the key `K`.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            key: PhantomData,
+        }
+    }
+
+    /// Get a reference to the object.
+    #[inline]
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the object.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwrap the object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+// Implemented by hand so the key doesn't need to implement anything.
+impl<K, T: Clone> Clone for Named<K, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone())
+    }
+}
+
+impl<K, T: Copy> Copy for Named<K, T> {}
+
+impl<K, T: fmt::Debug> fmt::Debug for Named<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Named").field(&self.inner).finish()
+    }
+}
+
+impl<K, T: PartialEq> PartialEq for Named<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<K, T: Eq> Eq for Named<K, T> {}
+
+/// Compile time access to the object tagged with the key `K` in a
+/// collection. `I` is the object's position in the chain, which should be
+/// left to be inferred.
+pub trait SelectNamed<K, I> {
+    /// The type of the tagged object.
+    type Output;
+
+    /// Get a reference to the object tagged with `K`.
+    fn select_named(&self) -> &Self::Output;
+
+    /// Get a mutable reference to the object tagged with `K`.
+    fn select_named_mut(&mut self) -> &mut Self::Output;
+}
+
+impl<K, T, B: NextNode> SelectNamed<K, Here> for Node<Named<K, T>, B> {
+    type Output = T;
+
+    #[inline]
+    fn select_named(&self) -> &T {
+        self.data().inner()
+    }
+
+    #[inline]
+    fn select_named_mut(&mut self) -> &mut T {
+        self.data_mut().inner_mut()
+    }
+}
+
+impl<K, I, A, B: NextNode + SelectNamed<K, I>> SelectNamed<K, There<I>> for Node<A, B> {
+    type Output = B::Output;
+
+    #[inline]
+    fn select_named(&self) -> &Self::Output {
+        self.next().select_named()
+    }
+
+    #[inline]
+    fn select_named_mut(&mut self) -> &mut Self::Output {
+        self.next_mut().select_named_mut()
+    }
+}
+
+impl<K, I, A: NextNode + SelectNamed<K, I>> SelectNamed<K, I> for Composite<A> {
+    type Output = A::Output;
+
+    #[inline]
+    fn select_named(&self) -> &Self::Output {
+        self.head().select_named()
+    }
+
+    #[inline]
+    fn select_named_mut(&mut self) -> &mut Self::Output {
+        self.head_mut().select_named_mut()
+    }
+}
+
+impl<A: NextNode> Composite<A> {
+    /// Get a reference to the object tagged with the key `K` (see
+    /// [`compose_named!`](crate::compose_named)). The second type parameter is
+    /// the object's position and should be left as `_`.
+    ///
+    /// This only compiles if exactly one object in the composite is tagged
+    /// with `K`.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose_named;
+    ///
+    /// struct Timestamps;
+    /// struct Host;
+    ///
+    /// let composite = compose_named!(Timestamps => "%Y-%m-%d", Host => "localhost");
+    /// assert_eq!(*composite.get_named::<Host, _>(), "localhost");
+    /// ```
+    #[inline]
+    pub fn get_named<K, I>(&self) -> &<Self as SelectNamed<K, I>>::Output
+    where
+        Self: SelectNamed<K, I>,
+    {
+        self.select_named()
+    }
+
+    /// Get a mutable reference to the object tagged with the key `K`. The
+    /// second type parameter is the object's position and should be left as
+    /// `_`.
+    #[inline]
+    pub fn get_named_mut<K, I>(&mut self) -> &mut <Self as SelectNamed<K, I>>::Output
+    where
+        Self: SelectNamed<K, I>,
+    {
+        self.select_named_mut()
+    }
+}
+
+/// Builds a composite like [`compose!`](crate::compose), tagging each object
+/// with a key so it can be looked up with [`Composite::get_named`] wherever it
+/// ends up in the list. Keys are marker types, usually unit structs.
+///
+/// # Example usage
+/// ```
+/// use zero_v::{compose, compose_named, Named};
+///
+/// struct Timestamps;
+/// struct Host;
+///
+/// let mut composite = compose_named!(Timestamps => 1, Host => 2);
+/// *composite.get_named_mut::<Timestamps, _>() += 10;
+/// assert_eq!(
+///     composite,
+///     compose!(Named::<Timestamps, _>::new(11), Named::<Host, _>::new(2))
+/// );
+/// ```
+#[macro_export]
+macro_rules! compose_named {
+    ($($key: ty => $item: expr),* $(,)?) => {
+        $crate::compose!($($crate::Named::<$key, _>::new($item)),*)
+    };
+}
+
+#[cfg(test)]
+mod test {
+    struct First;
+    struct Second;
+    struct Third;
+
+    #[test]
+    fn can_look_up_objects_by_name() {
+        let mut composite = compose_named!(First => 1u8, Second => "two", Third => 3u8);
+        assert_eq!(*composite.get_named::<Third, _>(), 3);
+        assert_eq!(*composite.get_named::<Second, _>(), "two");
+
+        *composite.get_named_mut::<First, _>() = 4;
+        assert_eq!(*composite.get::<0>().inner(), 4);
+    }
+}
@@ -0,0 +1,29 @@
+//! The most commonly used items of zero_v, for glob importing.
+//!
+//! ```
+//! use zero_v::prelude::*;
+//!
+//! #[zero_v(trait_types)]
+//! trait IntOp {
+//!     fn execute(&self, input: usize) -> usize;
+//! }
+//!
+//! impl IntOp for usize {
+//!     fn execute(&self, input: usize) -> usize {
+//!         input + self
+//!     }
+//! }
+//!
+//! let ops = compose!(1usize, 2usize);
+//! assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 3]);
+//! assert_eq!(*ops.get::<1>(), 2);
+//! ```
+
+pub use crate::{
+    compose, compose_default, compose_named, compose_nodes, compose_refs, compose_typed,
+    composed_type, Append, At, Composite, HasLength, IntoParts, Level, MapNodes, Mapper, NextNode,
+    Node, Priority, ReplaceAt, Requires, Select, SelectNamed, SplitAt, Visit, Visitor,
+};
+
+#[cfg(feature = "gen")]
+pub use crate::{compose_balanced, compose_repeat, zero_v};
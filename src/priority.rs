@@ -0,0 +1,131 @@
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+Stable Rust can't compute a new composite type from the values of associated
+consts, so rather than physically reordering the nodes of a composite, the
+priority of every node is gathered into a const table when the composite's
+type is known, and a second const table holds the levels sorted by those
+priorities. Both are worked out once per composite type by the compiler, so
+the zero_v macro's iter_{method}_by_priority methods only walk the sorted
+table: each step is an index into an array, with no searching or allocation.
+Ties keep the order of the compose! call.
+
+Like At, the table has a fixed upper size, which is MAX_PRIORITY_NODES.
+*/
+
+/// The largest number of nodes a composite can hold and still be ordered by
+/// [`Priorities`].
+pub const MAX_PRIORITY_NODES: usize = 32;
+
+/// Objects which declare where they should run relative to the other objects
+/// in a composite. Lower priorities run first.
+pub trait Priority {
+    /// The priority of this type of object.
+    const PRIORITY: usize;
+}
+
+/// The priorities of every object in a chain of nodes, indexed by level.
+pub trait Priorities {
+    /// The priority of the object at each level, padded with `usize::MAX`
+    /// past the end of the chain.
+    const PRIORITIES: [usize; MAX_PRIORITY_NODES];
+
+    /// The levels of the chain sorted by priority, lowest first. Levels with
+    /// equal priorities keep their order in the chain. Entries past the end
+    /// of the chain are unspecified.
+    const ORDER: [usize; MAX_PRIORITY_NODES] = order_by_priority(Self::PRIORITIES);
+
+    /// The priority of the object at `level`, or `usize::MAX` past the end
+    /// of the chain.
+    #[inline]
+    fn priority_at(level: usize) -> usize {
+        Self::PRIORITIES.get(level).copied().unwrap_or(usize::MAX)
+    }
+}
+
+const fn prepend(
+    first: usize,
+    rest: [usize; MAX_PRIORITY_NODES],
+    rest_len: usize,
+) -> [usize; MAX_PRIORITY_NODES] {
+    assert!(
+        rest_len < MAX_PRIORITY_NODES,
+        "composites ordered by priority can hold at most MAX_PRIORITY_NODES nodes"
+    );
+    let mut priorities = [usize::MAX; MAX_PRIORITY_NODES];
+    priorities[0] = first;
+    let mut level = 0;
+    while level < rest_len {
+        priorities[level + 1] = rest[level];
+        level += 1;
+    }
+    priorities
+}
+
+// An insertion sort, as it's stable and const fns can't call sort_by_key.
+// The usize::MAX padding sorts after every level in the chain.
+const fn order_by_priority(
+    priorities: [usize; MAX_PRIORITY_NODES],
+) -> [usize; MAX_PRIORITY_NODES] {
+    let mut order = [0; MAX_PRIORITY_NODES];
+    let mut level = 0;
+    while level < MAX_PRIORITY_NODES {
+        let mut rank = level;
+        while rank > 0 && priorities[order[rank - 1]] > priorities[level] {
+            order[rank] = order[rank - 1];
+            rank -= 1;
+        }
+        order[rank] = level;
+        level += 1;
+    }
+    order
+}
+
+impl Priorities for () {
+    const PRIORITIES: [usize; MAX_PRIORITY_NODES] = [usize::MAX; MAX_PRIORITY_NODES];
+}
+
+impl<A: Priority, B: NextNode + Priorities> Priorities for Node<A, B> {
+    const PRIORITIES: [usize; MAX_PRIORITY_NODES] = prepend(A::PRIORITY, B::PRIORITIES, B::LEN);
+}
+
+impl<A: NextNode + Priorities> Priorities for Composite<A> {
+    const PRIORITIES: [usize; MAX_PRIORITY_NODES] = A::PRIORITIES;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Priorities, Priority};
+    use crate::compose;
+
+    struct Early;
+    struct Late;
+
+    impl Priority for Early {
+        const PRIORITY: usize = 1;
+    }
+
+    impl Priority for Late {
+        const PRIORITY: usize = 10;
+    }
+
+    fn priorities_of<C: Priorities>(_composite: &C, levels: usize) -> Vec<usize> {
+        (0..levels).map(C::priority_at).collect()
+    }
+
+    #[test]
+    fn can_collect_priorities_by_level() {
+        let composite = compose!(Late, Early, Late);
+        assert_eq!(priorities_of(&composite, 4), vec![10, 1, 10, usize::MAX]);
+    }
+
+    fn order_of<C: Priorities>(_composite: &C, levels: usize) -> Vec<usize> {
+        C::ORDER[..levels].to_vec()
+    }
+
+    #[test]
+    fn can_order_levels_by_priority() {
+        let composite = compose!(Late, Early, Late, Early);
+        assert_eq!(order_of(&composite, 4), vec![1, 3, 0, 2]);
+    }
+}
@@ -0,0 +1,133 @@
+use core::pin::Pin;
+
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+Pin projection lets a pinned composite hand out pinned references to the
+objects it holds, so nodes can own futures (or anything else which must not
+move once pinned) and still be polled in place.
+
+This is only sound because of how nodes are laid out and used inside this
+crate: a node never moves its data or next fields out from behind a
+reference, Node and Composite have no Drop impls, they aren't
+repr(packed) and they're only Unpin when all of their contents are. That's
+why projection lives here rather than in user code.
+*/
+
+/// Pinned references to the contents of a pinned [`Node`].
+#[derive(Debug)]
+pub struct NodeProjection<'a, A, B> {
+    /// The object held in the node
+    pub data: Pin<&'a mut A>,
+    /// The rest of the node chain
+    pub next: Pin<&'a mut B>,
+}
+
+/// Pinned shared references to the contents of a pinned [`Node`].
+#[derive(Debug)]
+pub struct NodeProjectionRef<'a, A, B> {
+    /// The object held in the node
+    pub data: Pin<&'a A>,
+    /// The rest of the node chain
+    pub next: Pin<&'a B>,
+}
+
+impl<A, B: NextNode> Node<A, B> {
+    /// Get pinned mutable references to the object held in this node and to
+    /// the rest of the node chain.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose_nodes;
+    ///
+    /// let mut nodes = Box::pin(compose_nodes!(1, 2));
+    /// let projection = nodes.as_mut().project();
+    /// *projection.data.get_mut() += 10;
+    /// assert_eq!(*nodes.data(), 11);
+    /// ```
+    #[inline]
+    pub fn project(self: Pin<&mut Self>) -> NodeProjection<'_, A, B> {
+        // Safety: see the notes at the top of this module. The node is
+        // pinned, so neither field will be moved again, and nothing in this
+        // crate moves a field out of a node it only has a reference to.
+        unsafe {
+            let (data, next) = self.get_unchecked_mut().parts_mut();
+            NodeProjection {
+                data: Pin::new_unchecked(data),
+                next: Pin::new_unchecked(next),
+            }
+        }
+    }
+
+    /// Get pinned shared references to the object held in this node and to
+    /// the rest of the node chain.
+    #[inline]
+    pub fn project_ref(self: Pin<&Self>) -> NodeProjectionRef<'_, A, B> {
+        // Safety: as for project.
+        unsafe {
+            let node = self.get_ref();
+            NodeProjectionRef {
+                data: Pin::new_unchecked(node.data()),
+                next: Pin::new_unchecked(node.next()),
+            }
+        }
+    }
+}
+
+impl<A: NextNode> Composite<A> {
+    /// Get a pinned mutable reference to the first node of a pinned
+    /// composite, which can then be projected with [`Node::project`].
+    #[inline]
+    pub fn project(self: Pin<&mut Self>) -> Pin<&mut A> {
+        // Safety: as for Node::project.
+        unsafe { self.map_unchecked_mut(|composite| composite.head_mut()) }
+    }
+
+    /// Get a pinned shared reference to the first node of a pinned
+    /// composite.
+    #[inline]
+    pub fn project_ref(self: Pin<&Self>) -> Pin<&A> {
+        // Safety: as for Node::project.
+        unsafe { self.map_unchecked(|composite| composite.head()) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::future::Future;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    use crate::compose;
+
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+        // Safety: the vtable functions don't touch the (null) data pointer.
+        unsafe { Waker::from_raw(clone(std::ptr::null())) }
+    }
+
+    #[test]
+    fn can_poll_futures_in_place() {
+        // Async blocks are !Unpin, so they can only be polled through a pin.
+        let mut futures = Box::pin(compose!(async { 1 }, async { "two" }));
+        let waker = noop_waker();
+        let mut context = Context::from_waker(&waker);
+
+        let first = futures.as_mut().project().project();
+        assert_eq!(first.data.poll(&mut context), Poll::Ready(1));
+        let second = first.next.project();
+        assert_eq!(second.data.poll(&mut context), Poll::Ready("two"));
+    }
+
+    #[test]
+    fn can_project_shared_references() {
+        let composite = Box::pin(compose!(1, 2));
+        let first = composite.as_ref().project_ref().project_ref();
+        assert_eq!(*first.data, 1);
+        assert_eq!(*first.next.project_ref().data, 2);
+    }
+}
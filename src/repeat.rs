@@ -0,0 +1,45 @@
+/*
+A repeat runs a single object several times in a row, like an iterative pass
+which should be applied k times. The generated code (with the `blocks` option)
+gives the object N consecutive levels, while the composite only holds it once
+(and only nests one node for it).
+*/
+
+/// A node wrapper which behaves like `N` consecutive copies of an object.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Repeat<T, const N: usize> {
+    inner: T,
+}
+
+impl<T, const N: usize> Repeat<T, N> {
+    /// The number of times the object is repeated.
+    pub const TIMES: usize = N;
+
+    /// Wrap an object.
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Get a reference to the object.
+    #[inline]
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the object.
+    #[inline]
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwrap the object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T, const N: usize> From<T> for Repeat<T, N> {
+    fn from(inner: T) -> Self {
+        Self::new(inner)
+    }
+}
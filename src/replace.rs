@@ -0,0 +1,101 @@
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+ReplaceAt swaps the object at a given level of a chain of nodes for a new
+one, which can be of a different type. The nodes above that level have to be
+rebuilt around the new object, so as with SplitAt the new chain's type is
+computed from the level, and each level's impl is written out by the
+impl_replace_at macro, deferring to the previous level on the next node.
+*/
+
+/// Replacement of the object at `LEVEL` in a chain of nodes with a `T`.
+pub trait ReplaceAt<const LEVEL: usize, T> {
+    /// The type of the object being replaced.
+    type Old;
+    /// The chain with the new object in place of the old one.
+    type Output: NextNode;
+
+    /// Consume the chain and return the old object along with the new
+    /// chain.
+    fn replace_at(self, item: T) -> (Self::Old, Self::Output);
+}
+
+impl<A, B: NextNode, T> ReplaceAt<0, T> for Node<A, B> {
+    type Old = A;
+    type Output = Node<T, B>;
+
+    #[inline]
+    fn replace_at(self, item: T) -> (Self::Old, Self::Output) {
+        let (data, next) = self.into_inner();
+        (data, Node::new(item, next))
+    }
+}
+
+macro_rules! impl_replace_at {
+    ($($level: literal => $previous: literal),+) => {
+        $(
+            impl<A, B: NextNode + ReplaceAt<$previous, T>, T> ReplaceAt<$level, T> for Node<A, B> {
+                type Old = <B as ReplaceAt<$previous, T>>::Old;
+                type Output = Node<A, <B as ReplaceAt<$previous, T>>::Output>;
+
+                #[inline]
+                fn replace_at(self, item: T) -> (Self::Old, Self::Output) {
+                    let (data, next) = self.into_inner();
+                    let (old, next) = next.replace_at(item);
+                    (old, Node::new(data, next))
+                }
+            }
+        )+
+    };
+}
+
+impl_replace_at!(
+    1 => 0, 2 => 1, 3 => 2, 4 => 3, 5 => 4, 6 => 5, 7 => 6, 8 => 7,
+    9 => 8, 10 => 9, 11 => 10, 12 => 11, 13 => 12, 14 => 13, 15 => 14,
+    16 => 15, 17 => 16, 18 => 17, 19 => 18, 20 => 19, 21 => 20, 22 => 21,
+    23 => 22, 24 => 23, 25 => 24, 26 => 25, 27 => 26, 28 => 27, 29 => 28,
+    30 => 29, 31 => 30
+);
+
+impl<A: NextNode> Composite<A> {
+    /// Consume the composite and return the object at `LEVEL`, along with a
+    /// composite holding `item` in its place. `item` doesn't need to be the
+    /// same type as the object it replaces. `LEVEL` can be at most
+    /// [`MAX_AT_LEVEL`](crate::MAX_AT_LEVEL).
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let (old, composite) = compose!(1u8, "two", 3.0f64).replace::<1, _>(String::from("2"));
+    /// assert_eq!(old, "two");
+    /// assert_eq!(composite, compose!(1u8, String::from("2"), 3.0f64));
+    /// ```
+    pub fn replace<const LEVEL: usize, T>(
+        self,
+        item: T,
+    ) -> (
+        <A as ReplaceAt<LEVEL, T>>::Old,
+        Composite<<A as ReplaceAt<LEVEL, T>>::Output>,
+    )
+    where
+        A: ReplaceAt<LEVEL, T>,
+    {
+        let (old, head) = self.into_head().replace_at(item);
+        (old, Composite::new(head))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compose;
+
+    #[test]
+    fn can_replace_objects() {
+        let (old, composite) = compose!(1u8, "two", 3.0f64).replace::<0, _>(4u16);
+        assert_eq!((old, composite), (1u8, compose!(4u16, "two", 3.0f64)));
+
+        let (old, composite) = compose!(1u8, "two", 3.0f64).replace::<2, _>(compose!(5u8));
+        assert_eq!((old, composite), (3.0, compose!(1u8, "two", compose!(5u8))));
+    }
+}
@@ -0,0 +1,215 @@
+use core::any::TypeId;
+use core::marker::PhantomData;
+
+use crate::composite::{Composite, NextNode, Node};
+use crate::signature::NodeTypeIds;
+
+/*
+Select finds an object in a composite by its type rather than its level, so a
+composite can double as a heterogeneous type map. Like At, the lookup is
+resolved entirely through the type system.
+
+Stable Rust has no way to say "the first node whose type isn't T" in a trait
+bound (that would need specialization or negative impls), so the impls are
+told apart by an index type instead: Here for the node holding the object and
+There<I> for any node above it. Callers leave the index to be inferred. As a
+consequence the lookup only compiles when the requested type appears exactly
+once in the chain, since otherwise there's more than one index to choose from.
+
+The same restriction means there's no way to compute whether an arbitrary type
+is present as a const bool (TypeIds can't be compared in const contexts
+either), so assert_single_type is bound on Select and simply fails to compile
+when the type is missing or repeated. contains_type gives the plain presence
+check as a bool instead, by comparing TypeIds at runtime, so it only works on
+composites of 'static types.
+
+The index types count top-level nodes, not levels. The traits generated by the
+zero_v macro count each object in a nested composite as a level of its own,
+so the generated level_of walks the same index types with each node's level
+count instead.
+*/
+
+/// The index of an object held by the current node.
+pub struct Here;
+
+/// The index of an object held by a node below the current one.
+pub struct There<I>(PhantomData<I>);
+
+/// Compile time access to the object of type `T` in a collection. `I` is the
+/// object's position in the chain, which should be left to be inferred.
+pub trait Select<T, I> {
+    /// Get a reference to the object of type `T`.
+    fn select(&self) -> &T;
+
+    /// Get a mutable reference to the object of type `T`.
+    fn select_mut(&mut self) -> &mut T;
+}
+
+impl<T, B: NextNode> Select<T, Here> for Node<T, B> {
+    #[inline]
+    fn select(&self) -> &T {
+        self.data()
+    }
+
+    #[inline]
+    fn select_mut(&mut self) -> &mut T {
+        self.data_mut()
+    }
+}
+
+impl<T, I, A, B: NextNode + Select<T, I>> Select<T, There<I>> for Node<A, B> {
+    #[inline]
+    fn select(&self) -> &T {
+        self.next().select()
+    }
+
+    #[inline]
+    fn select_mut(&mut self) -> &mut T {
+        self.next_mut().select_mut()
+    }
+}
+
+impl<T, I, A: NextNode + Select<T, I>> Select<T, I> for Composite<A> {
+    #[inline]
+    fn select(&self) -> &T {
+        self.head().select()
+    }
+
+    #[inline]
+    fn select_mut(&mut self) -> &mut T {
+        self.head_mut().select_mut()
+    }
+}
+
+impl<A: NextNode> Composite<A> {
+    /// Get a reference to the object of type `T`. The second type parameter
+    /// is the object's position and should be left as `_`.
+    ///
+    /// This only compiles if the composite holds exactly one object of type
+    /// `T`.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// struct Threshold(u32);
+    /// struct Label(&'static str);
+    ///
+    /// let composite = compose!(Threshold(10), Label("plugin"));
+    /// assert_eq!(composite.get_type::<Label, _>().0, "plugin");
+    /// ```
+    #[inline]
+    pub fn get_type<T, I>(&self) -> &T
+    where
+        Self: Select<T, I>,
+    {
+        self.select()
+    }
+
+    /// Get a mutable reference to the object of type `T`. The second type
+    /// parameter is the object's position and should be left as `_`.
+    #[inline]
+    pub fn get_type_mut<T, I>(&mut self) -> &mut T
+    where
+        Self: Select<T, I>,
+    {
+        self.select_mut()
+    }
+
+    /// Assert, at compile time, that the composite holds exactly one object
+    /// of type `T`. The second type parameter is the object's position and
+    /// should be left as `_`.
+    ///
+    /// There's no answer to check at runtime: if the composite holds no
+    /// object of type `T` (or more than one), the call fails to compile, so a
+    /// library can check that a user's composition includes a plugin it
+    /// relies on.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::{Composite, Node};
+    ///
+    /// struct Logger;
+    /// struct Tracer;
+    ///
+    /// type Plugins = Composite<Node<Tracer, Node<Logger, ()>>>;
+    /// const _: () = Plugins::assert_single_type::<Logger, _>();
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use zero_v::{Composite, Node};
+    ///
+    /// struct Logger;
+    /// struct Tracer;
+    ///
+    /// type Plugins = Composite<Node<Tracer, ()>>;
+    /// const _: () = Plugins::assert_single_type::<Logger, _>();
+    /// ```
+    #[inline]
+    pub const fn assert_single_type<T, I>()
+    where
+        Self: Select<T, I>,
+    {
+    }
+}
+
+impl<A: NextNode + NodeTypeIds> Composite<A> {
+    /// Whether the composite holds at least one object of type `T`.
+    ///
+    /// Unlike [`Composite::assert_single_type`], this is checked at runtime
+    /// (by comparing `TypeId`s), so it gives an answer for types which are
+    /// missing or repeated.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::{Composite, Node};
+    ///
+    /// struct Logger;
+    /// struct Tracer;
+    ///
+    /// type Plugins = Composite<Node<Tracer, Node<Logger, Node<Logger, ()>>>>;
+    /// assert!(Plugins::contains_type::<Logger>());
+    /// assert!(!Plugins::contains_type::<u32>());
+    /// ```
+    pub fn contains_type<T: 'static>() -> bool {
+        let mut found = false;
+        A::for_each_type_id(&mut |id| found |= id == TypeId::of::<T>());
+        found
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compose;
+
+    #[derive(Debug, PartialEq)]
+    struct Threshold(u32);
+
+    #[derive(Debug, PartialEq)]
+    struct Label(&'static str);
+
+    #[test]
+    fn can_select_objects_by_type() {
+        let mut composite = compose!(Threshold(10), Label("plugin"), 3.0f64);
+        assert_eq!(composite.get_type::<Label, _>(), &Label("plugin"));
+        assert_eq!(*composite.get_type::<f64, _>(), 3.0);
+
+        composite.get_type_mut::<Threshold, _>().0 = 20;
+        assert_eq!(composite.get::<0>(), &Threshold(20));
+    }
+
+    #[test]
+    fn can_assert_single_types_in_const_contexts() {
+        type Plugins = crate::Composite<crate::Node<Threshold, crate::Node<Label, ()>>>;
+        const _: () = Plugins::assert_single_type::<Label, _>();
+        const _: () = Plugins::assert_single_type::<Threshold, _>();
+    }
+
+    #[test]
+    fn can_check_for_types_at_runtime() {
+        type Plugins = crate::Composite<crate::Node<Label, crate::Node<Label, ()>>>;
+        assert!(Plugins::contains_type::<Label>());
+        assert!(!Plugins::contains_type::<Threshold>());
+        assert!(!crate::Composite::<()>::contains_type::<Label>());
+    }
+}
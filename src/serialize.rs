@@ -0,0 +1,136 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, Error, Expected, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+serde support for composites, behind the serde feature. The shape of a
+composite is fixed by its type, so rather than exposing the nested node
+structure, a composite (or a node chain) is serialized as a flat tuple with
+one element per node, in level order. Nested composites become nested tuples.
+*/
+
+#[doc(hidden)]
+pub trait SerializeNodes {
+    fn serialize_nodes<S: SerializeTuple>(&self, tuple: &mut S) -> Result<(), S::Error>;
+}
+
+impl SerializeNodes for () {
+    fn serialize_nodes<S: SerializeTuple>(&self, _tuple: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+impl<A: Serialize, B: NextNode + SerializeNodes> SerializeNodes for Node<A, B> {
+    fn serialize_nodes<S: SerializeTuple>(&self, tuple: &mut S) -> Result<(), S::Error> {
+        tuple.serialize_element(self.data())?;
+        self.next().serialize_nodes(tuple)
+    }
+}
+
+impl<A: NextNode + SerializeNodes> Serialize for Composite<A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(A::LEN)?;
+        self.head().serialize_nodes(&mut tuple)?;
+        tuple.end()
+    }
+}
+
+impl<A: Serialize, B: NextNode + SerializeNodes> Serialize for Node<A, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(Self::LEN)?;
+        self.serialize_nodes(&mut tuple)?;
+        tuple.end()
+    }
+}
+
+#[doc(hidden)]
+pub trait DeserializeNodes<'de>: Sized {
+    fn deserialize_nodes<S: SeqAccess<'de>>(
+        seq: &mut S,
+        level: usize,
+        expected: &dyn Expected,
+    ) -> Result<Self, S::Error>;
+}
+
+impl<'de> DeserializeNodes<'de> for () {
+    fn deserialize_nodes<S: SeqAccess<'de>>(
+        _seq: &mut S,
+        _level: usize,
+        _expected: &dyn Expected,
+    ) -> Result<Self, S::Error> {
+        Ok(())
+    }
+}
+
+impl<'de, A: Deserialize<'de>, B: NextNode + DeserializeNodes<'de>> DeserializeNodes<'de>
+    for Node<A, B>
+{
+    fn deserialize_nodes<S: SeqAccess<'de>>(
+        seq: &mut S,
+        level: usize,
+        expected: &dyn Expected,
+    ) -> Result<Self, S::Error> {
+        let data = seq
+            .next_element()?
+            .ok_or_else(|| S::Error::invalid_length(level, expected))?;
+        let next = B::deserialize_nodes(seq, level + 1, expected)?;
+        Ok(Node::new(data, next))
+    }
+}
+
+struct NodesVisitor<A>(PhantomData<A>);
+
+impl<'de, A: NextNode + DeserializeNodes<'de>> Visitor<'de> for NodesVisitor<A> {
+    type Value = A;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a tuple of {} objects", A::LEN)
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        A::deserialize_nodes(&mut seq, 0, &self)
+    }
+}
+
+impl<'de, A: NextNode + DeserializeNodes<'de>> Deserialize<'de> for Composite<A> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer
+            .deserialize_tuple(A::LEN, NodesVisitor(PhantomData))
+            .map(Composite::new)
+    }
+}
+
+impl<'de, A: Deserialize<'de>, B: NextNode + DeserializeNodes<'de>> Deserialize<'de>
+    for Node<A, B>
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_tuple(Self::LEN, NodesVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{compose, Composite, Node};
+
+    #[test]
+    fn can_round_trip_composites() {
+        let composite = compose!(1u8, String::from("two"), compose!(3.0f64));
+        let json = serde_json::to_string(&composite).unwrap();
+        assert_eq!(json, r#"[1,"two",[3.0]]"#);
+        assert_eq!(serde_json::from_str::<Composite<_>>(&json).unwrap(), composite);
+
+        let empty: Composite<()> = serde_json::from_str("[]").unwrap();
+        assert_eq!(empty, compose!());
+    }
+
+    #[test]
+    fn rejects_tuples_of_the_wrong_length() {
+        let short = serde_json::from_str::<Composite<Node<u8, Node<u8, ()>>>>("[1]");
+        let message = short.unwrap_err().to_string();
+        assert!(message.contains("a tuple of 2 objects"), "{}", message);
+    }
+}
@@ -0,0 +1,200 @@
+use core::any::type_name;
+use core::cell::RefCell;
+#[cfg(feature = "std")]
+use std::sync::{Mutex, PoisonError};
+
+/*
+Wrappers giving objects in a composite interior mutability. The generated
+iteration methods only ever hand out &self references to the objects in a
+collection, so an object which needs to update its own state on every call
+has to do so through a cell or a lock. With the `shared` option, the zero_v
+macro generates a `{TraitName}Mut` trait mirroring the trait with `&mut self`
+receivers, and implements the trait for both wrappers around any type
+implementing it, so stateful objects can be written as if they had exclusive
+access to themselves. MutexNode needs a lock from std, so it's only
+available with the std feature.
+*/
+
+/// A node wrapper which lets a single threaded object mutate itself from
+/// `&self` execution paths.
+#[derive(Debug, Default)]
+pub struct RefCellNode<T> {
+    cell: RefCell<T>,
+}
+
+impl<T> RefCellNode<T> {
+    /// Wrap an object.
+    pub fn new(value: T) -> Self {
+        Self {
+            cell: RefCell::new(value),
+        }
+    }
+
+    /// Run `f` with exclusive access to the wrapped object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object is already borrowed, which can only happen if it
+    /// re-enters itself (for instance by executing a composite it belongs
+    /// to).
+    #[inline]
+    #[track_caller]
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        match self.cell.try_borrow_mut() {
+            Ok(mut value) => f(&mut value),
+            Err(_) => panic!(
+                "RefCellNode<{}> was re-entered while already in use",
+                type_name::<T>()
+            ),
+        }
+    }
+
+    /// Get a mutable reference to the wrapped object. No borrow is needed,
+    /// since `&mut self` already guarantees exclusive access.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.cell.get_mut()
+    }
+
+    /// Unwrap the object.
+    pub fn into_inner(self) -> T {
+        self.cell.into_inner()
+    }
+}
+
+/// What a [`MutexNode`] should do when its lock has been poisoned by a panic
+/// in a previous call.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PoisonPolicy {
+    /// Panic, since the object may have been left in an inconsistent state.
+    #[default]
+    Panic,
+    /// Carry on with the object as the panicking call left it.
+    Recover,
+}
+
+/// A node wrapper which lets an object mutate itself from `&self` execution
+/// paths, while keeping the composite holding it `Sync`.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct MutexNode<T> {
+    mutex: Mutex<T>,
+    policy: PoisonPolicy,
+}
+
+#[cfg(feature = "std")]
+impl<T> MutexNode<T> {
+    /// Wrap an object, panicking on use if the lock is ever poisoned.
+    pub fn new(value: T) -> Self {
+        Self::with_policy(value, PoisonPolicy::Panic)
+    }
+
+    /// Wrap an object with a specific policy for poisoned locks.
+    pub fn with_policy(value: T, policy: PoisonPolicy) -> Self {
+        Self {
+            mutex: Mutex::new(value),
+            policy,
+        }
+    }
+
+    /// The policy this node follows when its lock has been poisoned.
+    pub fn policy(&self) -> PoisonPolicy {
+        self.policy
+    }
+
+    /// Run `f` with exclusive access to the wrapped object, blocking until
+    /// the lock is available.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the lock has been poisoned and the policy is
+    /// [`PoisonPolicy::Panic`].
+    #[inline]
+    #[track_caller]
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut value = match (self.mutex.lock(), self.policy) {
+            (Ok(value), _) => value,
+            (Err(poisoned), PoisonPolicy::Recover) => poisoned.into_inner(),
+            (Err(_), PoisonPolicy::Panic) => panic!(
+                "MutexNode<{}> was poisoned by a panic in a previous call",
+                type_name::<T>()
+            ),
+        };
+        f(&mut value)
+    }
+
+    /// Get a mutable reference to the wrapped object. No lock is needed,
+    /// since `&mut self` already guarantees exclusive access, but the
+    /// poisoning policy still applies.
+    #[track_caller]
+    pub fn get_mut(&mut self) -> &mut T {
+        let policy = self.policy;
+        self.mutex
+            .get_mut()
+            .unwrap_or_else(|poisoned| Self::on_poison(policy, poisoned))
+    }
+
+    /// Unwrap the object, following the poisoning policy.
+    #[track_caller]
+    pub fn into_inner(self) -> T {
+        let policy = self.policy;
+        self.mutex
+            .into_inner()
+            .unwrap_or_else(|poisoned| Self::on_poison(policy, poisoned))
+    }
+
+    #[track_caller]
+    fn on_poison<V>(policy: PoisonPolicy, poisoned: PoisonError<V>) -> V {
+        match policy {
+            PoisonPolicy::Recover => poisoned.into_inner(),
+            PoisonPolicy::Panic => panic!(
+                "MutexNode<{}> was poisoned by a panic in a previous call",
+                type_name::<T>()
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RefCellNode;
+
+    #[test]
+    fn can_mutate_through_a_refcell_node() {
+        let node = RefCellNode::new(0);
+        node.with(|count| *count += 1);
+        node.with(|count| *count += 1);
+        assert_eq!(node.into_inner(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "RefCellNode<i32> was re-entered")]
+    fn refcell_node_panics_on_reentry() {
+        let node = RefCellNode::new(0);
+        node.with(|_| node.with(|count| *count += 1));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn mutex_node_follows_poison_policy() {
+        use super::{MutexNode, PoisonPolicy};
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let poison = |node: &MutexNode<i32>| {
+            let _ = catch_unwind(AssertUnwindSafe(|| {
+                node.with(|count| {
+                    *count += 1;
+                    panic!("poison the lock");
+                })
+            }));
+        };
+
+        let recovering = MutexNode::with_policy(0, PoisonPolicy::Recover);
+        poison(&recovering);
+        assert_eq!(recovering.with(|count| *count), 1);
+
+        let panicking = MutexNode::new(0);
+        poison(&panicking);
+        assert!(catch_unwind(AssertUnwindSafe(|| panicking.with(|count| *count))).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::any::{type_name, TypeId};
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+Signatures identify a composite by the types of its objects, in order, so
+results can be tagged with the pipeline build that produced them. They're
+built from std::any::type_name, which isn't available in const contexts on
+stable Rust, so the signature is assembled at runtime. Hashing it is a const
+fn, though, so known signatures can be hashed at compile time to compare
+against.
+
+Note that type names aren't guaranteed to be the same across compiler
+versions, so signatures are only stable for a given build toolchain.
+
+TypeIds are available too, for exact comparisons at runtime, though only for
+composites of 'static types.
+
+The same type names give a readable summary of a composite's structure for
+debugging, without the nesting of its derived Debug output.
+*/
+
+/// Chains of nodes whose object types can be listed in order.
+pub trait NodeTypeNames {
+    /// Call `f` with the type name of each object in the chain, in order.
+    fn for_each_type_name<F: FnMut(&'static str)>(f: &mut F);
+}
+
+impl NodeTypeNames for () {
+    fn for_each_type_name<F: FnMut(&'static str)>(_f: &mut F) {}
+}
+
+impl<A, B: NextNode + NodeTypeNames> NodeTypeNames for Node<A, B> {
+    fn for_each_type_name<F: FnMut(&'static str)>(f: &mut F) {
+        f(type_name::<A>());
+        B::for_each_type_name(f);
+    }
+}
+
+/// Chains of nodes whose object types can be identified by `TypeId`, in
+/// order.
+pub trait NodeTypeIds {
+    /// Call `f` with the `TypeId` of each object in the chain, in order.
+    fn for_each_type_id<F: FnMut(TypeId)>(f: &mut F);
+}
+
+impl NodeTypeIds for () {
+    fn for_each_type_id<F: FnMut(TypeId)>(_f: &mut F) {}
+}
+
+impl<A: 'static, B: NextNode + NodeTypeIds> NodeTypeIds for Node<A, B> {
+    fn for_each_type_id<F: FnMut(TypeId)>(f: &mut F) {
+        f(TypeId::of::<A>());
+        B::for_each_type_id(f);
+    }
+}
+
+/// Hash a signature with 64 bit FNV-1a. This is what
+/// [`Composite::signature_hash`] uses, so it can be applied to a known
+/// signature in a const context.
+pub const fn hash_signature(signature: &str) -> u64 {
+    let bytes = signature.as_bytes();
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut index = 0;
+    while index < bytes.len() {
+        hash ^= bytes[index] as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+        index += 1;
+    }
+    hash
+}
+
+impl<A: NextNode + NodeTypeNames> Composite<A> {
+    /// The type names of the composite's objects, in order.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let plugins = compose!(1u8, "two");
+    /// assert_eq!(plugins.type_names(), vec!["u8", "&str"]);
+    /// ```
+    pub fn type_names(&self) -> Vec<&'static str> {
+        let mut names = Vec::with_capacity(A::LEN);
+        A::for_each_type_name(&mut |name| names.push(name));
+        names
+    }
+
+    /// A string listing the type names of the composite's objects in order,
+    /// separated by commas.
+    pub fn signature(&self) -> String {
+        self.type_names().join(", ")
+    }
+
+    /// A hash of [`signature`](Self::signature), for use as a compact cache
+    /// key or telemetry dimension.
+    pub fn signature_hash(&self) -> u64 {
+        hash_signature(&self.signature())
+    }
+
+    /// A view of the composite whose `Debug` output lists the type names of
+    /// its objects in order, like `[u8, &str]`.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let composite = compose!(1u8, "two");
+    /// assert_eq!(format!("{:?}", composite.debug_structure()), "[u8, &str]");
+    /// ```
+    pub fn debug_structure(&self) -> DebugStructure<A> {
+        DebugStructure(PhantomData)
+    }
+}
+
+impl<A: NextNode + NodeTypeIds> Composite<A> {
+    /// The `TypeId`s of the composite's objects, in order.
+    ///
+    /// # Example usage
+    /// ```
+    /// use std::any::TypeId;
+    /// use zero_v::compose;
+    ///
+    /// let plugins = compose!(1u8, "two", 3u8);
+    /// let ids = plugins.type_ids();
+    /// assert!(ids.contains(&TypeId::of::<&str>()));
+    /// assert_eq!(ids.iter().filter(|id| **id == TypeId::of::<u8>()).count(), 2);
+    /// ```
+    pub fn type_ids(&self) -> Vec<TypeId> {
+        let mut ids = Vec::with_capacity(A::LEN);
+        A::for_each_type_id(&mut |id| ids.push(id));
+        ids
+    }
+}
+
+/// Lists the type names of the objects in a composite when formatted with
+/// `Debug`. Built by [`Composite::debug_structure`].
+pub struct DebugStructure<A>(PhantomData<A>);
+
+impl<A: NodeTypeNames> fmt::Debug for DebugStructure<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        A::for_each_type_name(&mut |name| {
+            list.entry(&format_args!("{}", name));
+        });
+        list.finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::hash_signature;
+    use crate::compose;
+
+    #[test]
+    fn can_build_signatures() {
+        let composite = compose!(1u8, "two", 3.0f64);
+        assert_eq!(composite.signature(), "u8, &str, f64");
+
+        const HASH: u64 = hash_signature("u8, &str, f64");
+        assert_eq!(composite.signature_hash(), HASH);
+        assert_ne!(compose!(1u8, 3.0f64).signature_hash(), HASH);
+    }
+
+    #[test]
+    fn can_list_type_names() {
+        let composite = compose!(1u8, "two", compose!(3.0f64));
+        assert_eq!(
+            composite.type_names(),
+            vec!["u8", "&str", std::any::type_name::<crate::Composite<crate::Node<f64, ()>>>()]
+        );
+        assert!(compose!().type_names().is_empty());
+    }
+
+    #[test]
+    fn can_list_type_ids() {
+        use std::any::TypeId;
+
+        let composite = compose!(1u8, "two");
+        assert_eq!(
+            composite.type_ids(),
+            vec![TypeId::of::<u8>(), TypeId::of::<&'static str>()]
+        );
+    }
+
+    #[test]
+    fn can_debug_structures() {
+        let composite = compose!(1u8, "two", 3.0f64);
+        assert_eq!(format!("{:?}", composite.debug_structure()), "[u8, &str, f64]");
+        assert_eq!(format!("{:?}", compose!().debug_structure()), "[]");
+    }
+}
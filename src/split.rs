@@ -0,0 +1,103 @@
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+SplitAt divides a chain of nodes in two at a given level. The front of the
+chain has to be rebuilt with a unit type in place of the back, which means
+both halves are new types computed from the level.
+
+As with At, stable Rust can't do arithmetic on const generic parameters in
+trait bounds, so each level's impl is written out by the split_at macro,
+deferring to the previous level on the next node. Splitting at level 0 works
+for any chain, leaving the front empty.
+*/
+
+/// Division of a chain of nodes into the nodes above `LEVEL` and the nodes
+/// from `LEVEL` down.
+pub trait SplitAt<const LEVEL: usize> {
+    /// The first `LEVEL` nodes of the chain.
+    type Front: NextNode;
+    /// The rest of the chain.
+    type Back: NextNode;
+
+    /// Consume the chain and return its two halves.
+    fn split_at(self) -> (Self::Front, Self::Back);
+}
+
+impl<A: NextNode> SplitAt<0> for A {
+    type Front = ();
+    type Back = A;
+
+    #[inline]
+    fn split_at(self) -> (Self::Front, Self::Back) {
+        ((), self)
+    }
+}
+
+macro_rules! impl_split_at {
+    ($($level: literal => $previous: literal),+) => {
+        $(
+            impl<A, B: NextNode + SplitAt<$previous>> SplitAt<$level> for Node<A, B> {
+                type Front = Node<A, <B as SplitAt<$previous>>::Front>;
+                type Back = <B as SplitAt<$previous>>::Back;
+
+                #[inline]
+                fn split_at(self) -> (Self::Front, Self::Back) {
+                    let (data, next) = self.into_inner();
+                    let (front, back) = next.split_at();
+                    (Node::new(data, front), back)
+                }
+            }
+        )+
+    };
+}
+
+impl_split_at!(
+    1 => 0, 2 => 1, 3 => 2, 4 => 3, 5 => 4, 6 => 5, 7 => 6, 8 => 7,
+    9 => 8, 10 => 9, 11 => 10, 12 => 11, 13 => 12, 14 => 13, 15 => 14,
+    16 => 15, 17 => 16, 18 => 17, 19 => 18, 20 => 19, 21 => 20, 22 => 21,
+    23 => 22, 24 => 23, 25 => 24, 26 => 25, 27 => 26, 28 => 27, 29 => 28,
+    30 => 29, 31 => 30
+);
+
+impl<A: NextNode> Composite<A> {
+    /// Consume the composite and return two composites, the first holding
+    /// the objects above `LEVEL` and the second holding the rest. `LEVEL` can
+    /// be at most [`MAX_AT_LEVEL`](crate::MAX_AT_LEVEL).
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let (front, back) = compose!(1u8, "two", 3.0f64).split_at::<1>();
+    /// assert_eq!(front, compose!(1u8));
+    /// assert_eq!(back, compose!("two", 3.0f64));
+    /// ```
+    pub fn split_at<const LEVEL: usize>(
+        self,
+    ) -> (
+        Composite<<A as SplitAt<LEVEL>>::Front>,
+        Composite<<A as SplitAt<LEVEL>>::Back>,
+    )
+    where
+        A: SplitAt<LEVEL>,
+    {
+        let (front, back) = self.into_head().split_at();
+        (Composite::new(front), Composite::new(back))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{compose, Composite};
+
+    #[test]
+    fn can_split_composites() {
+        let (front, back) = compose!(1u8, "two", 3.0f64).split_at::<2>();
+        assert_eq!(front, compose!(1u8, "two"));
+        assert_eq!(back, compose!(3.0f64));
+
+        let (front, back) = compose!(1u8).split_at::<0>();
+        assert_eq!(front, Composite::new(()));
+        assert_eq!(back, compose!(1u8));
+    }
+}
@@ -0,0 +1,156 @@
+/*!
+Single pass summary statistics over numeric outputs.
+
+With the `stats` feature enabled, the zero_v macro generates a
+`stats_{method_name}` method for every trait method returning a primitive
+numeric type, which executes every node once and summarizes the outputs
+with [`Stats`]. `Stats` can also be collected from any iterator of numbers.
+*/
+
+use core::iter::FromIterator;
+
+/// Primitive numeric types which can be summarized by [`Stats`].
+pub trait Numeric: Copy + PartialOrd {
+    /// Convert the value to an f64 for computing the mean and variance.
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_numeric {
+    ($($ty: ty),+) => {
+        $(
+            impl Numeric for $ty {
+                #[inline]
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )+
+    };
+}
+
+impl_numeric!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Count, min, max, mean and variance of a set of values, accumulated in a
+/// single pass.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats<T> {
+    count: usize,
+    min: Option<T>,
+    max: Option<T>,
+    // The running mean and sum of squared differences from it, as in
+    // Welford's algorithm. This keeps the variance numerically stable
+    // without needing a second pass.
+    mean: f64,
+    squared_diffs: f64,
+}
+
+impl<T: Numeric> Stats<T> {
+    /// Build an empty set of statistics.
+    pub fn new() -> Self {
+        Self {
+            count: 0,
+            min: None,
+            max: None,
+            mean: 0.0,
+            squared_diffs: 0.0,
+        }
+    }
+
+    /// Add a value to the statistics.
+    pub fn push(&mut self, value: T) {
+        self.count += 1;
+        match self.min {
+            Some(min) if min <= value => {}
+            _ => self.min = Some(value),
+        }
+        match self.max {
+            Some(max) if max >= value => {}
+            _ => self.max = Some(value),
+        }
+
+        let value = value.to_f64();
+        let diff = value - self.mean;
+        self.mean += diff / self.count as f64;
+        self.squared_diffs += diff * (value - self.mean);
+    }
+
+    /// The number of values seen.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The smallest value seen, if any.
+    pub fn min(&self) -> Option<T> {
+        self.min
+    }
+
+    /// The largest value seen, if any.
+    pub fn max(&self) -> Option<T> {
+        self.max
+    }
+
+    /// The mean of the values seen, if any.
+    pub fn mean(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.mean)
+        }
+    }
+
+    /// The population variance of the values seen, if any.
+    pub fn variance(&self) -> Option<f64> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.squared_diffs / self.count as f64)
+        }
+    }
+}
+
+impl<T: Numeric> Default for Stats<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Numeric> Extend<T> for Stats<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T: Numeric> FromIterator<T> for Stats<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stats = Self::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Stats;
+
+    #[test]
+    fn can_summarize_values() {
+        let stats: Stats<u32> = vec![2, 4, 4, 4, 5, 5, 7, 9].into_iter().collect();
+        assert_eq!(stats.count(), 8);
+        assert_eq!(stats.min(), Some(2));
+        assert_eq!(stats.max(), Some(9));
+        assert_eq!(stats.mean(), Some(5.0));
+        assert_eq!(stats.variance(), Some(4.0));
+    }
+
+    #[test]
+    fn empty_stats_have_no_summary() {
+        let stats: Stats<f64> = Stats::new();
+        assert_eq!(stats.count(), 0);
+        assert_eq!(stats.min(), None);
+        assert_eq!(stats.max(), None);
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.variance(), None);
+    }
+}
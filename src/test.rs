@@ -33,6 +33,17 @@ fn can_iter_manually_by_level() {
     let composite = compose!(Adder::<11>::new(), Adder::<12>::new(), Adder::<13>::new());
     let mut outputs = Vec::new();
     for i in 0..composite.len() {
-        outputs.push(composite.head.execute_at_level(0, i));
+        outputs.push(composite.head().execute_at_level(0, i));
     }
 }
+
+#[cfg(feature = "stats")]
+#[test]
+fn can_summarize_outputs() {
+    let composite = compose!(Adder::<1>::new(), Adder::<2>::new(), Adder::<6>::new());
+    let stats = composite.stats_execute(1);
+    assert_eq!(stats.count(), 3);
+    assert_eq!(stats.min(), Some(2));
+    assert_eq!(stats.max(), Some(7));
+    assert_eq!(stats.mean(), Some(4.0));
+}
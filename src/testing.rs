@@ -0,0 +1,181 @@
+/*!
+Helpers for checking that a composite behaves the same as the equivalent
+collection of trait objects.
+
+Static dispatch through a composite should be an optimization and nothing
+more, so a library adopting Zero_V can run both versions of a collection over
+the same inputs and check their outputs match, typically with the pair built
+by [`compose_both!`](crate::compose_both).
+
+Intermediate libraries can also test their code against mock objects
+generated with the zero_v macro's `mock` option, which are built with
+[`mock_node!`](crate::mock_node).
+*/
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+/// Asserts that a composite and a list of boxed trait objects produce the
+/// same outputs for every input.
+///
+/// For each input, `composite_outputs` should call one of the composite's
+/// generated `iter_` methods, and `object_output` the same trait method on a
+/// single object. On failure, the panic message gives the input, the index
+/// of the first mismatched output and the two outputs.
+///
+/// # Example usage
+/// ```
+/// use zero_v::{compose_both, zero_v};
+/// use zero_v::testing::assert_conforms;
+///
+/// #[zero_v(trait_types)]
+/// trait IntOp {
+///     fn execute(&self, input: usize) -> usize;
+/// }
+///
+/// struct Adder(usize);
+///
+/// impl IntOp for Adder {
+///     fn execute(&self, input: usize) -> usize {
+///         input + self.0
+///     }
+/// }
+///
+/// let (ops, objects) = compose_both!(IntOp; Adder(1), Adder(2));
+/// assert_conforms(
+///     &objects,
+///     0..10,
+///     |input| ops.iter_execute(input),
+///     |object, input| object.execute(input),
+/// );
+/// ```
+#[track_caller]
+pub fn assert_conforms<T, In, Out, Outputs, C, D>(
+    objects: &[Box<T>],
+    inputs: impl IntoIterator<Item = In>,
+    mut composite_outputs: C,
+    mut object_output: D,
+) where
+    T: ?Sized,
+    In: Clone + Debug,
+    Out: PartialEq + Debug,
+    Outputs: IntoIterator<Item = Out>,
+    C: FnMut(In) -> Outputs,
+    D: FnMut(&T, In) -> Out,
+{
+    for input in inputs {
+        let expected: Vec<Out> = objects
+            .iter()
+            .map(|object| object_output(object, input.clone()))
+            .collect();
+        let mut expected = expected.into_iter();
+        let mut observed = composite_outputs(input.clone()).into_iter();
+        let mut index = 0;
+        loop {
+            match (observed.next(), expected.next()) {
+                (None, None) => break,
+                (Some(left), Some(right)) => {
+                    if left != right {
+                        panic!(
+                            "static and dynamic outputs differ for input {:?} at index {}:\n \
+                             static: {:?}\ndynamic: {:?}",
+                            input, index, left, right
+                        );
+                    }
+                }
+                (Some(left), None) => panic!(
+                    "unexpected static output for input {:?} at index {}: {:?}",
+                    input, index, left
+                ),
+                (None, Some(right)) => panic!(
+                    "missing static output for input {:?} at index {}: expected {:?}",
+                    input, index, right
+                ),
+            }
+            index += 1;
+        }
+    }
+}
+
+/// Builds a mock object generated by `#[zero_v(trait_types, mock)]`, with
+/// the given return value for each listed method. Methods which aren't
+/// listed panic if called (unless they don't have an output).
+///
+/// # Example usage
+/// ```
+/// use zero_v::{compose, mock_node, zero_v};
+///
+/// #[zero_v(trait_types, mock)]
+/// trait IntOp {
+///     fn execute(&self, input: usize) -> usize;
+/// }
+///
+/// let ops = compose!(
+///     mock_node!(IntOpMock { execute: 3 }),
+///     mock_node!(IntOpMock { execute: 4 })
+/// );
+/// assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![3, 4]);
+/// assert_eq!(ops.head().data().calls.execute.get(), 1);
+/// ```
+#[macro_export]
+macro_rules! mock_node {
+    ($($mock: ident)::+ { $($method: ident: $value: expr),* $(,)? }) => {
+        $($mock)::+ {
+            $($method: ::core::option::Option::Some($value),)*
+            ..::core::default::Default::default()
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::assert_conforms;
+    use crate as zero_v;
+    use crate::{compose_both, zero_v};
+
+    #[zero_v(trait_types)]
+    trait IntOp {
+        fn execute(&self, input: usize) -> usize;
+    }
+
+    struct Adder(usize);
+
+    impl IntOp for Adder {
+        fn execute(&self, input: usize) -> usize {
+            input + self.0
+        }
+    }
+
+    struct Multiplier(usize);
+
+    impl IntOp for Multiplier {
+        fn execute(&self, input: usize) -> usize {
+            input * self.0
+        }
+    }
+
+    #[test]
+    fn matching_collections_conform() {
+        let (ops, objects) = compose_both!(IntOp; Adder(1), Multiplier(2));
+        assert_conforms(
+            &objects,
+            0..5,
+            |input| ops.iter_execute(input),
+            |object, input| object.execute(input),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "differ for input 3 at index 1")]
+    fn diverging_collections_fail() {
+        let (ops, _) = compose_both!(IntOp; Adder(1), Multiplier(2));
+        let (_, objects) = compose_both!(IntOp; Adder(1), Adder(2));
+        assert_conforms(
+            &objects,
+            3..5,
+            |input| ops.iter_execute(input),
+            |object, input| object.execute(input),
+        );
+    }
+}
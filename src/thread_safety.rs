@@ -0,0 +1,99 @@
+use crate::composite::{Composite, NextNode, Node};
+use crate::dyn_tail::DynTail;
+
+/*
+Thread safety checks for composites. A composite is Send (or Sync) exactly
+when every object in it is, but when that doesn't hold the compiler reports
+the failure against the whole nested Node type, which is hard to read for a
+composite of more than a few objects. These marker traits require Send (or
+Sync) one node at a time instead, so the error names the object type which
+isn't thread safe and the node chain holding it.
+*/
+
+/// Composites and node chains whose objects can all be sent between
+/// threads.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be sent between threads",
+    note = "every object in a composite must be `Send` for the composite to be"
+)]
+pub trait SendComposite: Send {}
+
+impl SendComposite for () {}
+
+impl<A: Send, B: NextNode + SendComposite> SendComposite for Node<A, B> {}
+
+impl<A: NextNode + SendComposite> SendComposite for Composite<A> {}
+
+impl<T: ?Sized + Send> SendComposite for DynTail<T> {}
+
+/// Composites and node chains whose objects can all be shared between
+/// threads.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be shared between threads",
+    note = "every object in a composite must be `Sync` for the composite to be"
+)]
+pub trait SyncComposite: Sync {}
+
+impl SyncComposite for () {}
+
+impl<A: Sync, B: NextNode + SyncComposite> SyncComposite for Node<A, B> {}
+
+impl<A: NextNode + SyncComposite> SyncComposite for Composite<A> {}
+
+impl<T: ?Sized + Sync> SyncComposite for DynTail<T> {}
+
+/// Asserts at compile time that composite types are `Send` and `Sync`. When
+/// one isn't, the error names the type of the object responsible rather than
+/// just the whole composite.
+///
+/// ```
+/// use zero_v::{assert_send_sync, Composite, Node};
+///
+/// assert_send_sync!(Composite<Node<u8, Node<String, ()>>>);
+/// ```
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+/// use zero_v::{assert_send_sync, Composite, Node};
+///
+/// // error: `Rc<u8>` cannot be sent between threads safely
+/// assert_send_sync!(Composite<Node<u8, Node<Rc<u8>, ()>>>);
+/// ```
+#[macro_export]
+macro_rules! assert_send_sync {
+    ($($composite: ty),+ $(,)?) => {
+        const _: fn() = || {
+            fn assert_send_sync<T: $crate::SendComposite + $crate::SyncComposite>() {}
+            $(assert_send_sync::<$composite>();)+
+        };
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use core::cell::Cell;
+
+    use super::{SendComposite, SyncComposite};
+    use crate::{compose, Composite, DynTail, Node};
+
+    assert_send_sync!(
+        Composite<()>,
+        Composite<Node<u8, Node<Composite<Node<&'static str, ()>>, ()>>>,
+        Composite<Node<u8, DynTail<dyn Fn() + Send + Sync>>>,
+    );
+
+    fn is_send<T: SendComposite>(_composite: &T) -> bool {
+        true
+    }
+
+    fn is_sync<T: SyncComposite>(_composite: &T) -> bool {
+        true
+    }
+
+    #[test]
+    fn can_require_thread_safe_composites() {
+        let composite = compose!(1u8, Cell::new(2u8));
+        assert!(is_send(&composite));
+        assert!(is_sync(&compose!(1u8, compose!("two"))));
+    }
+}
@@ -0,0 +1,105 @@
+/*
+A toggle holds an object which can be switched on and off at runtime, from a
+settings screen for instance, without changing the type of the composite
+holding it. The generated code (with the `optional` option) treats a disabled
+toggle like an empty Option, so it keeps its level but is skipped by the
+iterators, and the object is kept around (with whatever state it had) for
+when it's switched back on.
+*/
+
+/// A node wrapper which can be enabled and disabled at runtime. Objects start
+/// out enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Toggle<T> {
+    inner: T,
+    enabled: bool,
+}
+
+impl<T> Toggle<T> {
+    /// Wrap an object, enabled.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            enabled: true,
+        }
+    }
+
+    /// Wrap an object, disabled.
+    pub fn disabled(inner: T) -> Self {
+        Self {
+            inner,
+            enabled: false,
+        }
+    }
+
+    /// Whether the object is currently enabled.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable the object.
+    #[inline]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Get a reference to the object if it's enabled.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.enabled.then_some(&self.inner)
+    }
+
+    /// Get a mutable reference to the object if it's enabled.
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.enabled.then_some(&mut self.inner)
+    }
+
+    /// Get a reference to the object, whether or not it's enabled.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the object, whether or not it's enabled.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwrap the object.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Default> Default for Toggle<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> From<T> for Toggle<T> {
+    fn from(inner: T) -> Self {
+        Self::new(inner)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Toggle;
+
+    #[test]
+    fn can_switch_toggles() {
+        let mut toggle = Toggle::new(1);
+        assert_eq!(toggle.get(), Some(&1));
+
+        toggle.set_enabled(false);
+        assert_eq!(toggle.get(), None);
+        assert_eq!(toggle.get_mut(), None);
+        *toggle.inner_mut() += 1;
+
+        toggle.set_enabled(true);
+        assert_eq!(toggle.get(), Some(&2));
+        assert!(!Toggle::disabled(3).is_enabled());
+    }
+}
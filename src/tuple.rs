@@ -0,0 +1,177 @@
+use crate::composite::{Composite, HasLength, NextNode, Node};
+
+/*
+Composites are built from nested nodes, which makes getting the original
+values back out awkward (composite.head().next().next().data() and so on). The
+IntoParts trait flattens a node chain back into a tuple with one element per
+node, in composition order, and the From impls on Composite go the other way
+so tuples can stand in for compose! at API boundaries.
+
+Tuples are also NextNode types in their own right, with one level per
+element, so the zero_v macro can implement a trait's level and iterator traits
+for them directly. A composite over a tuple (a TupleComposite) then works like
+one over nodes, with a much flatter type.
+
+There's no way to build a tuple one element at a time in a generic impl, so
+the impls below are written out for each arity with the help of a couple of
+small macros which spell out the nested node type and the matching
+destructuring pattern.
+*/
+
+/// Conversion of a chain of nodes back into the objects it holds.
+pub trait IntoParts {
+    /// A tuple with one element per node, in composition order.
+    type Parts;
+
+    /// Consume the nodes and return the objects they contain.
+    fn into_parts(self) -> Self::Parts;
+}
+
+/// A composite holding its objects in a flat tuple rather than a chain of
+/// nodes, for more readable types and compiler diagnostics. The zero_v macro
+/// generates the same iteration methods for tuples of up to 12 objects (or up
+/// to 32 with its `tuples` option) as for node chains, though tuples can't
+/// hold nested composites, and the derived comparison and formatting traits
+/// (which come from the standard library's tuple impls) only go up to 12
+/// objects.
+///
+/// # Example usage
+/// ```
+/// use zero_v::{zero_v, TupleComposite};
+///
+/// #[zero_v(trait_types)]
+/// trait IntOp {
+///     fn execute(&self, input: usize) -> usize;
+/// }
+///
+/// impl IntOp for usize {
+///     fn execute(&self, input: usize) -> usize {
+///         input + self
+///     }
+/// }
+///
+/// let ops: TupleComposite<(usize, usize)> = TupleComposite::new((1, 2));
+/// assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 3]);
+/// ```
+pub type TupleComposite<T> = Composite<T>;
+
+impl IntoParts for () {
+    type Parts = ();
+
+    fn into_parts(self) -> Self::Parts {}
+}
+
+impl From<()> for Composite<()> {
+    fn from(_parts: ()) -> Self {
+        Composite::new(())
+    }
+}
+
+macro_rules! nodes_type {
+    () => { () };
+    ($head: ident $(, $tail: ident)*) => { Node<$head, nodes_type!($($tail),*)> };
+}
+
+macro_rules! nodes_pattern {
+    () => { () };
+    ($head: ident $(, $tail: ident)*) => {
+        Node { data: $head, next: nodes_pattern!($($tail),*) }
+    };
+}
+
+macro_rules! impl_into_parts {
+    ($len: literal => $($name: ident),+) => {
+        impl<$($name),+> HasLength for ($($name,)+) {
+            fn get_len(&self) -> usize {
+                $len
+            }
+        }
+
+        impl<$($name),+> NextNode for ($($name,)+) {
+            const LEN: usize = $len;
+        }
+
+        impl<$($name),+> IntoParts for nodes_type!($($name),+) {
+            type Parts = ($($name,)+);
+
+            #[allow(deprecated, non_snake_case)]
+            fn into_parts(self) -> Self::Parts {
+                let nodes_pattern!($($name),+) = self;
+                ($($name,)+)
+            }
+        }
+
+        impl<$($name),+> From<($($name,)+)> for Composite<nodes_type!($($name),+)> {
+            #[allow(deprecated, non_snake_case)]
+            fn from(($($name,)+): ($($name,)+)) -> Self {
+                Composite::new(nodes_pattern!($($name),+))
+            }
+        }
+    };
+}
+
+impl_into_parts!(1 => A);
+impl_into_parts!(2 => A, B);
+impl_into_parts!(3 => A, B, C);
+impl_into_parts!(4 => A, B, C, D);
+impl_into_parts!(5 => A, B, C, D, E);
+impl_into_parts!(6 => A, B, C, D, E, F);
+impl_into_parts!(7 => A, B, C, D, E, F, G);
+impl_into_parts!(8 => A, B, C, D, E, F, G, H);
+impl_into_parts!(9 => A, B, C, D, E, F, G, H, I);
+impl_into_parts!(10 => A, B, C, D, E, F, G, H, I, J);
+impl_into_parts!(11 => A, B, C, D, E, F, G, H, I, J, K);
+impl_into_parts!(12 => A, B, C, D, E, F, G, H, I, J, K, L);
+impl_into_parts!(13 => A, B, C, D, E, F, G, H, I, J, K, L, M);
+impl_into_parts!(14 => A, B, C, D, E, F, G, H, I, J, K, L, M, N);
+impl_into_parts!(15 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O);
+impl_into_parts!(16 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P);
+impl_into_parts!(17 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q);
+impl_into_parts!(18 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R);
+impl_into_parts!(19 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S);
+impl_into_parts!(20 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T);
+impl_into_parts!(21 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U);
+impl_into_parts!(22 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V);
+impl_into_parts!(23 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W);
+impl_into_parts!(24 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X);
+impl_into_parts!(25 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y);
+impl_into_parts!(26 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z);
+impl_into_parts!(27 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA);
+impl_into_parts!(28 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB);
+impl_into_parts!(29 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC);
+impl_into_parts!(30 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD);
+impl_into_parts!(31 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE);
+impl_into_parts!(32 => A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z, AA, AB, AC, AD, AE, AF);
+
+#[cfg(test)]
+mod test {
+    use crate::{compose, Composite, NextNode};
+
+    #[test]
+    fn can_recover_parts_from_composites() {
+        compose!().into_parts();
+        assert_eq!(compose!(1).into_parts(), (1,));
+        assert_eq!(compose!(1, "b", 3.0).into_parts(), (1, "b", 3.0));
+        assert_eq!(
+            compose!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12).into_parts(),
+            (1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12)
+        );
+    }
+
+    #[test]
+    fn can_convert_between_tuples_and_composites() {
+        assert_eq!(Composite::from((1, "b", 3.0)), compose!(1, "b", 3.0));
+        assert_eq!(Composite::from(()), compose!());
+
+        let composite: Composite<_> = (1, 2).into();
+        assert_eq!(composite.into_tuple(), (1, 2));
+    }
+
+    #[test]
+    fn tuples_are_node_chains() {
+        fn len_of<T: NextNode>(nodes: &T) -> (usize, usize) {
+            (T::LEN, nodes.get_len())
+        }
+        assert_eq!(len_of(&(1, "b", 3.0)), (3, 3));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+Views of a composite hold references to its objects in place of the objects
+themselves, so one owned composite can be handed to code expecting a
+composite of references (such as one built with compose_refs!) without
+cloning. As with Append, the view is a new chain of nodes, built by
+recursion over the original. The lifetime of the references is a parameter of
+the chain types, which needs generic associated types.
+*/
+
+/// Chains of nodes which can be viewed as chains of references to their
+/// objects.
+pub trait NodeViews {
+    /// The chain holding a shared reference to each object.
+    type Refs<'a>: NextNode
+    where
+        Self: 'a;
+
+    /// The chain holding a mutable reference to each object.
+    type Muts<'a>: NextNode
+    where
+        Self: 'a;
+
+    /// Borrow each object in the chain.
+    fn as_refs(&self) -> Self::Refs<'_>;
+
+    /// Mutably borrow each object in the chain.
+    fn as_muts(&mut self) -> Self::Muts<'_>;
+}
+
+impl NodeViews for () {
+    type Refs<'a> = ();
+    type Muts<'a> = ();
+
+    #[inline]
+    fn as_refs(&self) -> Self::Refs<'_> {}
+
+    #[inline]
+    fn as_muts(&mut self) -> Self::Muts<'_> {}
+}
+
+impl<A, B: NextNode + NodeViews> NodeViews for Node<A, B> {
+    type Refs<'a>
+        = Node<&'a A, B::Refs<'a>>
+    where
+        Self: 'a;
+    type Muts<'a>
+        = Node<&'a mut A, B::Muts<'a>>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn as_refs(&self) -> Self::Refs<'_> {
+        Node::new(self.data(), self.next().as_refs())
+    }
+
+    #[inline]
+    fn as_muts(&mut self) -> Self::Muts<'_> {
+        let (data, next) = self.parts_mut();
+        Node::new(data, next.as_muts())
+    }
+}
+
+impl<A: NextNode + NodeViews> Composite<A> {
+    /// A composite holding a shared reference to each of this composite's
+    /// objects, in order.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let composite = compose!(1, String::from("two"));
+    /// assert_eq!(composite.as_ref(), compose!(&1, &String::from("two")));
+    /// ```
+    #[inline]
+    pub fn as_ref(&self) -> Composite<A::Refs<'_>> {
+        Composite::new(self.head().as_refs())
+    }
+
+    /// A composite holding a mutable reference to each of this composite's
+    /// objects, in order.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::compose;
+    ///
+    /// let mut composite = compose!(1, String::from("two"));
+    /// let (first, second) = composite.as_mut().into_parts();
+    /// *first += 1;
+    /// second.push('!');
+    /// assert_eq!(composite, compose!(2, String::from("two!")));
+    /// ```
+    #[inline]
+    pub fn as_mut(&mut self) -> Composite<A::Muts<'_>> {
+        Composite::new(self.head_mut().as_muts())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::compose;
+
+    #[test]
+    fn can_view_composites_by_reference() {
+        let mut composite = compose!(1u8, "two", compose!(3.0f64));
+        assert_eq!(composite.as_ref(), compose!(&1u8, &"two", &compose!(3.0f64)));
+
+        *composite.as_mut().into_head().into_inner().0 = 4;
+        assert_eq!(*composite.get::<0>(), 4);
+        assert_eq!(compose!().as_ref(), compose!());
+    }
+}
@@ -0,0 +1,107 @@
+use crate::composite::{Composite, NextNode, Node};
+
+/*
+Visiting walks every object in a composite with the same generic hook, for
+work which doesn't need the objects' trait (printing, validating, collecting
+metadata). It mirrors Mapper: closures can't be generic over their input
+type, so the hook is a method on a Visitor, and the walk is a recursion over
+the node chain. Nested composites are visited as single objects, since the
+hook can't tell them apart from any other type. Visiting objects through a
+trait of your own is what the visitors generated by the zero_v macro are for.
+*/
+
+/// A hook which can be run on objects of any type.
+pub trait Visitor {
+    /// Visit one object.
+    fn visit<T>(&mut self, item: &T);
+}
+
+/// Chains of nodes whose objects can be walked by a [`Visitor`].
+pub trait Visit<V: Visitor> {
+    /// Run the visitor on each object in the chain, in order.
+    fn visit_nodes(&self, visitor: &mut V);
+}
+
+impl<V: Visitor> Visit<V> for () {
+    #[inline]
+    fn visit_nodes(&self, _visitor: &mut V) {}
+}
+
+impl<V: Visitor, A, B: NextNode + Visit<V>> Visit<V> for Node<A, B> {
+    #[inline]
+    fn visit_nodes(&self, visitor: &mut V) {
+        visitor.visit(self.data());
+        self.next().visit_nodes(visitor);
+    }
+}
+
+impl<A: NextNode> Composite<A> {
+    /// Run `visitor` on each of the composite's objects, in order.
+    ///
+    /// # Example usage
+    /// ```
+    /// use zero_v::{compose, Visitor};
+    ///
+    /// struct Sizes(Vec<usize>);
+    ///
+    /// impl Visitor for Sizes {
+    ///     fn visit<T>(&mut self, _item: &T) {
+    ///         self.0.push(std::mem::size_of::<T>());
+    ///     }
+    /// }
+    ///
+    /// let mut sizes = Sizes(Vec::new());
+    /// compose!(1u8, 2u32, "three").accept(&mut sizes);
+    /// assert_eq!(sizes.0, vec![1, 4, std::mem::size_of::<&str>()]);
+    /// ```
+    #[inline]
+    pub fn accept<V: Visitor>(&self, visitor: &mut V)
+    where
+        A: Visit<V>,
+    {
+        self.head().visit_nodes(visitor)
+    }
+}
+
+impl<A, B: NextNode> Node<A, B> {
+    /// Run `visitor` on the object in this node and each one after it, in
+    /// order.
+    #[inline]
+    pub fn accept<V: Visitor>(&self, visitor: &mut V)
+    where
+        Self: Visit<V>,
+    {
+        self.visit_nodes(visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::any::type_name;
+
+    use super::Visitor;
+    use crate::{compose, compose_nodes};
+
+    struct TypeNames(Vec<&'static str>);
+
+    impl Visitor for TypeNames {
+        fn visit<T>(&mut self, _item: &T) {
+            self.0.push(type_name::<T>());
+        }
+    }
+
+    #[test]
+    fn can_visit_objects() {
+        let mut names = TypeNames(Vec::new());
+        compose!(1u8, "two", compose!(3.0f64)).accept(&mut names);
+        assert_eq!(
+            names.0,
+            vec!["u8", "&str", type_name::<crate::Composite<crate::Node<f64, ()>>>()]
+        );
+
+        let mut names = TypeNames(Vec::new());
+        compose_nodes!(1u8).accept(&mut names);
+        compose!().accept(&mut names);
+        assert_eq!(names.0, vec!["u8"]);
+    }
+}
@@ -0,0 +1,42 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::Expr;
+
+pub(crate) struct ComposeBalanced {
+    items: Vec<Expr>,
+}
+
+impl ComposeBalanced {
+    pub(crate) fn generate(&self) -> TokenStream {
+        TokenStream::from(balanced(&self.items))
+    }
+}
+
+impl Parse for ComposeBalanced {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let items = Punctuated::<Expr, Comma>::parse_terminated(input)?;
+        Ok(Self {
+            items: items.into_iter().collect(),
+        })
+    }
+}
+
+// Split the items in half until each half is a single item, nesting a
+// composite for each split. The nesting depth is then logarithmic in the
+// number of items, rather than linear.
+fn balanced(items: &[Expr]) -> TokenStream2 {
+    if items.len() <= 2 {
+        return quote! { zero_v::compose!(#(#items),*) };
+    }
+
+    let (left, right) = items.split_at(items.len().div_ceil(2));
+    let halves = [left, right].map(|half| match half {
+        [item] => quote! { #item },
+        _ => balanced(half),
+    });
+    quote! { zero_v::compose!(#(#halves),*) }
+}
@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::{braced, bracketed, Expr, Ident, LitStr, Token};
+
+struct Entry {
+    key: LitStr,
+    _arrow: Token![=>],
+    item: Expr,
+}
+
+impl Parse for Entry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            key: input.parse()?,
+            _arrow: input.parse()?,
+            item: input.parse()?,
+        })
+    }
+}
+
+// The spec is either given inline as a list of keys, or as the path of a file
+// (relative to the crate root) holding the list.
+enum Spec {
+    Inline(Vec<LitStr>),
+    File(LitStr),
+}
+
+pub(crate) struct ComposeFromSpec {
+    registry: Vec<Entry>,
+    spec: Spec,
+}
+
+impl ComposeFromSpec {
+    pub(crate) fn generate(&self) -> TokenStream {
+        match self.expand() {
+            Ok(tokens) => TokenStream::from(tokens),
+            Err(err) => TokenStream::from(err.to_compile_error()),
+        }
+    }
+
+    fn expand(&self) -> syn::Result<proc_macro2::TokenStream> {
+        for (i, entry) in self.registry.iter().enumerate() {
+            if self.registry[..i].iter().any(|e| e.key.value() == entry.key.value()) {
+                return Err(syn::Error::new(
+                    entry.key.span(),
+                    format!("key `{}` is registered more than once", entry.key.value()),
+                ));
+            }
+        }
+
+        let (keys, tracked_file) = match &self.spec {
+            Spec::Inline(keys) => (keys.clone(), None),
+            Spec::File(path) => {
+                let (keys, full_path) = read_spec_file(path)?;
+                (keys, Some(full_path))
+            }
+        };
+
+        let items = keys
+            .iter()
+            .map(|key| {
+                self.registry
+                    .iter()
+                    .find(|entry| entry.key.value() == key.value())
+                    .map(|entry| &entry.item)
+                    .ok_or_else(|| {
+                        let known = self
+                            .registry
+                            .iter()
+                            .map(|entry| format!("`{}`", entry.key.value()))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        syn::Error::new(
+                            key.span(),
+                            format!(
+                                "unknown key `{}` in pipeline spec, expected one of {}",
+                                key.value(),
+                                known
+                            ),
+                        )
+                    })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        // Including the spec file makes cargo rebuild the crate when the
+        // file changes.
+        let tracking = tracked_file.map(|path| {
+            quote! { const _: &str = include_str!(#path); }
+        });
+
+        Ok(quote! {{
+            #tracking
+            zero_v::compose!(#(#items),*)
+        }})
+    }
+}
+
+fn read_spec_file(path: &LitStr) -> syn::Result<(Vec<LitStr>, String)> {
+    let root = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = PathBuf::from(root).join(path.value());
+    let contents = std::fs::read_to_string(&full_path).map_err(|err| {
+        syn::Error::new(
+            path.span(),
+            format!("couldn't read pipeline spec {}: {}", full_path.display(), err),
+        )
+    })?;
+
+    // Errors inside the file can't point at it, so report them on the path.
+    let keys = Punctuated::<LitStr, Comma>::parse_terminated
+        .parse_str(&contents)
+        .map_err(|err| {
+            syn::Error::new(
+                path.span(),
+                format!("invalid pipeline spec {}: {}", full_path.display(), err),
+            )
+        })?;
+    let keys = keys
+        .into_iter()
+        .map(|key| LitStr::new(&key.value(), path.span()))
+        .collect();
+    Ok((keys, full_path.display().to_string()))
+}
+
+impl Parse for ComposeFromSpec {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let registry_label: Ident = input.parse()?;
+        if registry_label != "registry" {
+            return Err(syn::Error::new(registry_label.span(), "expected `registry`"));
+        }
+        let content;
+        braced!(content in input);
+        let registry = Punctuated::<Entry, Comma>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+        let _comma: Option<Token![,]> = input.parse()?;
+
+        let spec_label: Ident = input.parse()?;
+        if spec_label != "spec" {
+            return Err(syn::Error::new(spec_label.span(), "expected `spec`"));
+        }
+        let _eq: Token![=] = input.parse()?;
+        let spec = if input.peek(LitStr) {
+            Spec::File(input.parse()?)
+        } else if input.peek(syn::token::Bracket) {
+            let content;
+            bracketed!(content in input);
+            let keys = Punctuated::<LitStr, Comma>::parse_terminated(&content)?;
+            Spec::Inline(keys.into_iter().collect())
+        } else {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "expected a list of keys or the path of a spec file",
+            ));
+        };
+        let _comma: Option<Token![,]> = input.parse()?;
+
+        Ok(Self { registry, spec })
+    }
+}
@@ -0,0 +1,119 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Group, Ident, Literal, TokenStream as TokenStream2, TokenTree};
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, LitInt, RangeLimits, Token};
+
+pub(crate) enum ComposeRepeat {
+    // `item; count`: the same item, count times.
+    Copies { item: Expr, count: usize },
+    // `item for i in start..end`: the item once for each value of i, with
+    // every `#i` in the item replaced by the value.
+    Sequence { item: TokenStream2, var: Ident, values: Vec<usize> },
+}
+
+impl ComposeRepeat {
+    pub(crate) fn generate(&self) -> TokenStream {
+        let items = match self {
+            Self::Copies { item, count } => (0..*count).map(|_| quote! { #item }).collect(),
+            Self::Sequence { item, var, values } => values
+                .iter()
+                .map(|value| substitute(item.clone(), var, *value))
+                .collect::<Vec<_>>(),
+        };
+
+        TokenStream::from(quote! { zero_v::compose!(#(#items),*) })
+    }
+}
+
+impl Parse for ComposeRepeat {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let tokens: Vec<TokenTree> = input.parse::<TokenStream2>()?.into_iter().collect();
+
+        // The item in a sequence can't be parsed as an expression until the
+        // variable has been substituted, so look for the `for` which ends it.
+        let for_index = tokens.windows(3).position(|window| match window {
+            [TokenTree::Ident(for_), TokenTree::Ident(_), TokenTree::Ident(in_)] => {
+                for_ == "for" && in_ == "in"
+            }
+            _ => false,
+        });
+
+        match for_index {
+            Some(index) if index > 0 => {
+                let var = match &tokens[index + 1] {
+                    TokenTree::Ident(var) => var.clone(),
+                    _ => unreachable!(),
+                };
+                let range: syn::ExprRange =
+                    syn::parse2(tokens[index + 3..].iter().cloned().collect())?;
+                let bound = |bound: &Option<Box<Expr>>| -> syn::Result<usize> {
+                    // Bounds passed on by compose! arrive wrapped in invisible
+                    // groups.
+                    let mut bound = bound.as_deref();
+                    while let Some(Expr::Group(group)) = bound {
+                        bound = Some(&group.expr);
+                    }
+                    match bound {
+                        Some(Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Int(lit),
+                            ..
+                        })) => lit.base10_parse(),
+                        _ => Err(syn::Error::new_spanned(
+                            &range,
+                            "expected a range between two integer literals",
+                        )),
+                    }
+                };
+                let (start, end) = (bound(&range.from)?, bound(&range.to)?);
+                let values = match range.limits {
+                    RangeLimits::HalfOpen(_) => (start..end).collect(),
+                    RangeLimits::Closed(_) => (start..=end).collect(),
+                };
+                Ok(Self::Sequence {
+                    item: tokens[..index].iter().cloned().collect(),
+                    var,
+                    values,
+                })
+            }
+            _ => {
+                let input = tokens.into_iter().collect::<TokenStream2>();
+                syn::parse::Parser::parse2(
+                    |input: ParseStream| {
+                        let item = input.parse()?;
+                        input.parse::<Token![;]>()?;
+                        let count = input.parse::<LitInt>()?.base10_parse()?;
+                        Ok(Self::Copies { item, count })
+                    },
+                    input,
+                )
+            }
+        }
+    }
+}
+
+/// Replace every `#var` in the tokens with the value, as an unsuffixed
+/// literal so it can stand in for any integer type (or const parameter).
+fn substitute(tokens: TokenStream2, var: &Ident, value: usize) -> TokenStream2 {
+    let mut output = Vec::new();
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(ref punct) if punct.as_char() == '#' => match tokens.peek() {
+                Some(TokenTree::Ident(ident)) if ident == var => {
+                    tokens.next();
+                    output.push(TokenTree::Literal(Literal::usize_unsuffixed(value)));
+                }
+                _ => output.push(token),
+            },
+            TokenTree::Group(group) => {
+                let mut replaced =
+                    Group::new(group.delimiter(), substitute(group.stream(), var, value));
+                replaced.set_span(group.span());
+                output.push(TokenTree::Group(replaced));
+            }
+            _ => output.push(token),
+        }
+    }
+    output.into_iter().collect()
+}
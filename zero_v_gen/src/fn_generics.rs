@@ -32,7 +32,7 @@ impl FnGenerics {
             .make_where_clause()
             .predicates
             .extend::<Vec<WherePredicate>>(vec![
-                parse_quote! { NodeType: NextNode + #level_trait #generics },
+                parse_quote! { NodeType: zero_v::NextNode + #level_trait #generics },
                 parse_quote! { #type_name: #iter_trait #iter_generics },
             ]);
 
@@ -4,21 +4,63 @@ use proc_macro2::Ident;
 use quote::format_ident;
 use syn::{ItemTrait, TraitItem};
 
+use crate::trait_types::{
+    is_lending, is_supported, is_supported_consuming, is_supported_mut, is_supported_pinned,
+};
+
 pub(crate) struct Idents {
     main: Ident,
     main_methods: Vec<Ident>,
+    lending_methods: Vec<Ident>,
+    mut_methods: Vec<Ident>,
+    consuming_methods: Vec<Ident>,
+    pinned_methods: Vec<Ident>,
 }
 
 impl Idents {
     pub(crate) fn from_trait(main: ItemTrait) -> Self {
-        let main_methods = main.items.into_iter().filter_map(|i| match i {
-            TraitItem::Method(m) => Some(m.sig.ident),
-            _ => None,
-        });
+        let mut_methods = main
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                TraitItem::Method(m) if is_supported_mut(&m.sig) => Some(m.sig.ident.clone()),
+                _ => None,
+            })
+            .collect();
+        let consuming_methods = main
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                TraitItem::Method(m) if is_supported_consuming(&m.sig) => {
+                    Some(m.sig.ident.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        let pinned_methods = main
+            .items
+            .iter()
+            .filter_map(|i| match i {
+                TraitItem::Method(m) if is_supported_pinned(&m.sig) => Some(m.sig.ident.clone()),
+                _ => None,
+            })
+            .collect();
+        let (lending_methods, main_methods) = main
+            .items
+            .into_iter()
+            .filter_map(|i| match i {
+                TraitItem::Method(m) if is_supported(&m.sig) => Some(m.sig),
+                _ => None,
+            })
+            .partition::<Vec<_>, _>(is_lending);
 
         Self {
             main: main.ident,
-            main_methods: main_methods.collect(),
+            main_methods: main_methods.into_iter().map(|sig| sig.ident).collect(),
+            lending_methods: lending_methods.into_iter().map(|sig| sig.ident).collect(),
+            mut_methods,
+            consuming_methods,
+            pinned_methods,
         }
     }
 
@@ -26,6 +68,10 @@ impl Idents {
         Self {
             main,
             main_methods: vec![],
+            lending_methods: vec![],
+            mut_methods: vec![],
+            consuming_methods: vec![],
+            pinned_methods: vec![],
         }
     }
 
@@ -33,12 +79,72 @@ impl Idents {
         format_ident!("{}AtLevel", self.main)
     }
 
+    pub(crate) fn level_of_trait(&self) -> Ident {
+        format_ident!("{}LevelOf", self.main)
+    }
+
+    pub(crate) fn level_next_trait(&self) -> Ident {
+        format_ident!("{}LevelNext", self.main)
+    }
+
+    pub(crate) fn mutator_trait(&self) -> Ident {
+        format_ident!("{}Mutator", self.main)
+    }
+
+    pub(crate) fn mut_trait(&self) -> Ident {
+        format_ident!("{}Mut", self.main)
+    }
+
+    pub(crate) fn consume_trait(&self) -> Ident {
+        format_ident!("{}Consume", self.main)
+    }
+
+    pub(crate) fn pinned_trait(&self) -> Ident {
+        format_ident!("{}Pinned", self.main)
+    }
+
+    pub(crate) fn mock_struct(&self) -> Ident {
+        format_ident!("{}Mock", self.main)
+    }
+
+    pub(crate) fn mock_calls_struct(&self) -> Ident {
+        format_ident!("{}MockCalls", self.main)
+    }
+
     pub(crate) fn level_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
         self.main_methods
             .iter()
             .map(|m| format_ident!("{}_at_level", m))
     }
 
+    pub(crate) fn try_fold_level_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("{}_try_fold_at_level", m))
+    }
+
+    pub(crate) fn extend_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("extend_{}", m))
+    }
+
+    pub(crate) fn try_fold_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("try_fold_{}", m))
+    }
+
+    pub(crate) fn const_level_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("{}_at_const", m))
+    }
+
+    pub(crate) fn typed_level_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods.iter().map(|m| format_ident!("{}_at", m))
+    }
+
     pub(crate) fn iter_trait(&self) -> Ident {
         format_ident!("Iter{}", self.main)
     }
@@ -49,6 +155,54 @@ impl Idents {
             .map(|m| format_ident!("iter_{}", m))
     }
 
+    pub(crate) fn range_iter_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("iter_{}_range", m))
+    }
+
+    pub(crate) fn ref_iter_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("iter_{}_ref", m))
+    }
+
+    pub(crate) fn bind_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("bind_{}", m))
+    }
+
+    pub(crate) fn step_iter_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("iter_{}_step", m))
+    }
+
+    pub(crate) fn flatten_iter_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("iter_{}_flatten", m))
+    }
+
+    pub(crate) fn priority_iter_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("iter_{}_by_priority", m))
+    }
+
+    pub(crate) fn chain_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("chain_{}", m))
+    }
+
+    pub(crate) fn stats_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("stats_{}", m))
+    }
+
     pub(crate) fn composite_iters<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
         self.main_methods.iter().map(|m| {
             format_ident!(
@@ -57,4 +211,123 @@ impl Idents {
             )
         })
     }
+
+    pub(crate) fn composite_ref_iters<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods.iter().map(|m| {
+            format_ident!(
+                "CompositeRefIterator{}",
+                m.to_string().to_case(Case::UpperCamel)
+            )
+        })
+    }
+
+    pub(crate) fn bound_composites<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods.iter().map(|m| {
+            format_ident!(
+                "BoundComposite{}",
+                m.to_string().to_case(Case::UpperCamel)
+            )
+        })
+    }
+
+    pub(crate) fn composite_priority_iters<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods.iter().map(|m| {
+            format_ident!(
+                "CompositePriorityIterator{}",
+                m.to_string().to_case(Case::UpperCamel)
+            )
+        })
+    }
+
+    pub(crate) fn args_structs<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("{}Args", m.to_string().to_case(Case::UpperCamel)))
+    }
+
+    pub(crate) fn with_iter_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.main_methods
+            .iter()
+            .map(|m| format_ident!("iter_{}_with", m))
+    }
+
+    pub(crate) fn visitor_traits<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.lending_methods.iter().map(move |m| {
+            format_ident!(
+                "{}{}Visitor",
+                self.main,
+                m.to_string().to_case(Case::UpperCamel)
+            )
+        })
+    }
+
+    pub(crate) fn for_each_level_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.lending_methods
+            .iter()
+            .map(|m| format_ident!("{}_for_each_level", m))
+    }
+
+    pub(crate) fn for_each_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.lending_methods
+            .iter()
+            .map(|m| format_ident!("for_each_{}", m))
+    }
+
+    pub(crate) fn iter_mut_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.mut_methods
+            .iter()
+            .map(|m| format_ident!("iter_{}_mut", m))
+    }
+
+    pub(crate) fn for_each_mut_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.mut_methods
+            .iter()
+            .map(|m| format_ident!("for_each_{}_mut", m))
+    }
+
+    pub(crate) fn mut_callers<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.mut_methods.iter().map(move |m| {
+            format_ident!(
+                "{}{}Caller",
+                self.main,
+                m.to_string().to_case(Case::UpperCamel)
+            )
+        })
+    }
+
+    pub(crate) fn each_into_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.consuming_methods
+            .iter()
+            .map(|m| format_ident!("{}_each_into", m))
+    }
+
+    pub(crate) fn for_each_into_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.consuming_methods
+            .iter()
+            .map(|m| format_ident!("for_each_{}_into", m))
+    }
+
+    pub(crate) fn consuming_iter_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.consuming_methods
+            .iter()
+            .map(|m| format_ident!("into_iter_{}", m))
+    }
+
+    pub(crate) fn each_pinned_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.pinned_methods
+            .iter()
+            .map(|m| format_ident!("{}_each_pinned", m))
+    }
+
+    pub(crate) fn for_each_pinned_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.pinned_methods
+            .iter()
+            .map(|m| format_ident!("for_each_{}_pinned", m))
+    }
+
+    pub(crate) fn pinned_iter_methods<'a>(&'a self) -> impl Iterator<Item = Ident> + 'a {
+        self.pinned_methods
+            .iter()
+            .map(|m| format_ident!("iter_{}_pinned", m))
+    }
 }
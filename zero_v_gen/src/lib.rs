@@ -3,6 +3,9 @@ use proc_macro2::Ident;
 use syn::parse::{Parse, ParseStream};
 use syn::{parse_macro_input, Token};
 
+mod compose_balanced;
+mod compose_from_spec;
+mod compose_repeat;
 mod fn_generics;
 mod idents;
 mod trait_types;
@@ -32,9 +35,13 @@ impl Parse for ZeroVGen {
 
 /// This macro generates iterations for traits implementing zero_v iteration
 /// and generic bounds for functions taking a collection of objects with those
-/// traits. It has some limitations at the moment: if your trait's method 
-/// arguments involve lifetimes or generics, the generation is likely to fail.
-/// If it doesn't then this macro should generate all your boilerplate for you.
+/// traits. It has some limitations at the moment: your trait's method
+/// arguments need to be `Copy` (since they're passed to every object in the
+/// collection), and any references in them need a lifetime declared on the
+/// trait rather than an elided one. Arguments and outputs can otherwise use
+/// the trait's generics however they like (e.g. `&'a HashMap<K, Vec<V>>`).
+/// If your trait fits, this macro should generate all your boilerplate for
+/// you.
 ///
 /// The macro will generate one iteration method on the collection for each of
 /// your methods. If your method has signature:
@@ -49,6 +56,325 @@ impl Parse for ZeroVGen {
 /// fn iter_{method_name}(&self, input_1: Type1, input_2: Type2, ...) -> impl Iterator<Item=OutType>
 /// ```
 ///
+/// Alongside it, the following variations are generated:
+///
+/// * `iter_{method_name}_range(&self, range, input_1, ...)` only executes the
+///   nodes whose level falls in `range` (e.g. `2..` to resume from the third
+///   node), panicking if the range extends past the end of the composite.
+/// * `chain_{method_name}(&self, other, input_1, ...)` yields this
+///   composite's outputs followed by those of `other`, a composite of
+///   objects implementing the same trait, without merging the two.
+/// * `iter_{method_name}_flatten(&self, input_1, ...)` is only generated for
+///   methods returning an `Option<T>`. It still executes every node, but
+///   yields the `T`s directly and skips any `None`s.
+/// * `stats_{method_name}(&self, input_1, ...)` is only generated with the
+///   `stats` feature, for methods returning a primitive number. It executes
+///   every node and returns a `zero_v::stats::Stats` summary of the outputs
+///   (count, min, max, mean and variance) computed in a single pass.
+/// * `iter_{method_name}_step(&self, start, step, input_1, ...)` executes
+///   every `step`th node starting at level `start`. Unlike calling
+///   `.step_by()` on the iterator, the skipped nodes are never executed. A
+///   `start` past the last level yields nothing. Panics if `step` is zero.
+/// * `iter_{method_name}_ref(&self, &input_1, ...)` borrows its arguments
+///   instead of taking them by value.
+/// * `bind_{method_name}(&self, input_1, ...)` captures the arguments once
+///   and returns a value which can be iterated over (by reference or by
+///   value) any number of times.
+/// * `try_fold_{method_name}(&self, init, input_1, ..., fold)` folds the
+///   outputs with `fold(acc, output) -> Result<acc, E>` in a single pass
+///   over the nodes, returning the first error straight away without
+///   executing any of the remaining nodes.
+/// * `extend_{method_name}(&self, out, input_1, ...)` pushes every output
+///   into `out`, any collection implementing `Extend`, in a single pass over
+///   the nodes.
+/// * `{method_name}_at_const::<LEVEL>(&self, input_1, ...)` executes the
+///   node at a level known at compile time, returning its output directly.
+///   The node is resolved through the type system (see `zero_v::At`), so
+///   there's no runtime dispatch at all. `zero_v::At` counts nodes rather
+///   than objects, so using this on a collection holding a nested composite
+///   or a block fails to compile instead of disagreeing with
+///   `{method_name}_at_level`.
+/// * `{method_name}_at(&self, input_1, ..., level)` executes the node at a
+///   `zero_v::Level` of the collection, as returned by `iter_levels()` or
+///   `level_of()`. It behaves like `{method_name}_at_level`, but the level
+///   can't be swapped with a `usize` argument or taken from another
+///   collection by mistake.
+/// * `iter_levels(&self)` yields every `zero_v::Level` of the collection in
+///   order, counting each object in a nested composite or dyn tail as a
+///   level of its own, so it lines up with the outputs of the iterators.
+/// * `level_of::<T, _>()` returns the `zero_v::Level` of the collection's
+///   object of type `T`, which must appear exactly once among the objects in
+///   the collection's own nodes. The second type parameter is the object's
+///   position and should be left as `_`. Every object in a nested composite
+///   above it counts as a level of its own, so the level targets the same
+///   object in the level based methods. In const contexts,
+///   `<Collection as {TraitName}LevelOf<T, _>>::LEVEL` gives the same level
+///   as a `usize`.
+/// * Levels of a composite get a `next()` method, from the generated
+///   `{TraitName}LevelNext` trait, returning the level after them, or `None`
+///   for the last level. Like `level_of`, every object in a nested composite
+///   counts as a level of its own. A dyn tail's objects aren't counted, since
+///   they're only known at runtime.
+/// * `iter_{method_name}_by_priority(&self, input_1, ...)` is only available
+///   when every object in the collection implements `zero_v::Priority`. It
+///   yields the outputs in ascending order of each object's `PRIORITY`
+///   (ties keep composition order), so objects can declare their own
+///   ordering. The order is a const table worked out once for each
+///   collection type, so iterating it costs no more than `iter_{method_name}`.
+///
+/// The iterators returned by the `iter_` methods (other than
+/// `iter_{method_name}_flatten`) have a `reset()` method which rewinds them to
+/// their first output, so one iterator can be reused across many passes
+/// rather than being rebuilt for each one. The `iter_{method_name}`,
+/// `iter_{method_name}_range`, `iter_{method_name}_step` and
+/// `iter_{method_name}_ref` iterators also implement `zero_v::NamedOutputs`,
+/// so `zero_v::assert_outputs_eq!` can name the object behind a mismatched
+/// output.
+///
+/// Composites can also be nested, by passing one composite as an element of
+/// another. The objects of the inner composite are treated as levels of the
+/// outer one, so grouping related objects into a reusable sub-composite
+/// doesn't change the outputs. Note that `len()` still counts the inner
+/// composite as a single node, and that nesting is only supported for traits
+/// without type parameters.
+///
+/// An object which may or may not be configured can be composed as an
+/// `Option`. A `None` still takes up a level, but is skipped by the
+/// iterators, folds and visitors rather than producing an output, so
+/// `compose!(Adder::new(1), logger_from_config)` works whether or not a
+/// logger was set up. Objects wrapped in a `zero_v::Toggle` are skipped in
+/// the same way while disabled, and can be switched back on with
+/// `set_enabled(true)` without changing the type of the collection. Like
+/// nesting, these are only supported for traits without type parameters.
+/// They also need the `optional` option (`#[zero_v(trait_types, optional)]`),
+/// since the impls for them would conflict with an impl of your trait for
+/// `Option<T>` or `Toggle<T>` itself.
+///
+/// Objects tagged with a key by `zero_v::compose_named!` are executed as if
+/// they weren't wrapped, without any option, for traits without type
+/// parameters.
+///
+/// A block of objects of the same type can be composed as an array, with
+/// each element counting as a level of its own, so
+/// `compose!(Reader::new(), [Smoother::new(); 32])` runs 33 objects without
+/// 33 nested node types. Similarly, `zero_v::Repeat::<T, N>::new(object)`
+/// runs one object `N` times in a row, as `N` levels. Arrays and repeats are
+/// also only supported for traits without type parameters, and need the
+/// `blocks` option (`#[zero_v(trait_types, blocks)]`) so they don't conflict
+/// with an impl of your trait for an array or `Repeat` itself.
+///
+/// Plain tuples of up to 12 objects implementing the trait get the same
+/// methods, so simple collections can skip `compose!` and be passed as
+/// `&(Adder::new(1), Multiplier::new(2))`, or wrapped in a
+/// `zero_v::TupleComposite` for flatter types than a chain of nodes. Tuples
+/// can't hold nested composites, though. Passing `tuples = N`
+/// (`#[zero_v(trait_types, tuples = 32)]`) changes the largest tuple to `N`
+/// (at most 32, or 0 for none), as every size adds impls to compile.
+///
+/// Methods whose output mentions `Self` (for example, ones returning a
+/// generic associated type like `Self::View<'a>`) can produce a different
+/// output type for every object in the collection, so they can't be iterated
+/// over. For those, the macro generates a `{TraitName}{MethodName}Visitor`
+/// trait with a single method:
+///
+/// ```ignore
+/// fn visit<'a, TraitType: {TraitName} + 'a>(&mut self, level: usize, output: <TraitType as {TraitName}>::View<'a>)
+/// ```
+///
+/// and a `for_each_{method_name}(&self, input_1, ..., visitor)` method on the
+/// collection, which hands each object's output to the visitor in turn.
+///
+/// To change the objects in place, implement the generated
+/// `{TraitName}Mutator` trait, whose `visit<TraitType: {TraitName}>(&mut self,
+/// node: &mut TraitType)` method will be called on every object in the
+/// collection by `for_each_node_mut(&mut self, mutator)`. This pairs well
+/// with `&mut self` methods on your trait.
+///
+/// `&mut self` methods without generic parameters are also called on every
+/// object for you, through a generated mutator, by
+/// `for_each_{method_name}_mut(&mut self, input_1, ..., f)`, which hands each
+/// output to the closure `f` in turn, and
+/// `iter_{method_name}_mut(&mut self, input_1, ...)`, which runs every object
+/// straight away and returns an iterator over the collected outputs. This
+/// lets stateful objects such as counters and caches be composed too. Like
+/// the mutator, these skip `None`s and disabled objects.
+///
+/// Methods taking `self` by value (again without generic parameters), such as
+/// `fn finalize(self) -> Report`, can only be called once per object, so
+/// they're run as the collection is taken apart. The generated
+/// `{TraitName}Consume` trait gives collections
+/// `for_each_{method_name}_into(self, input_1, ..., f)` and
+/// `into_iter_{method_name}(self, input_1, ...)`, which consume every object
+/// in order. Collections holding `Repeat`s or a `DynTail` can't be taken apart
+/// into their objects, so they don't get these. Methods taking
+/// `self: Rc<Self>` or `self: Arc<Self>` are called the same way, with each
+/// object moved into a fresh `Rc` or `Arc` first.
+///
+/// Methods taking `self: Pin<&mut Self>`, such as hand-written futures, are
+/// called on every object of a pinned collection through the generated
+/// `{TraitName}Pinned` trait, by `for_each_{method_name}_pinned(self:
+/// Pin<&mut Self>, input_1, ..., f)` and
+/// `iter_{method_name}_pinned(self: Pin<&mut Self>, input_1, ...)`. These
+/// walk the collection with zero_v's pin projections, so they support plain
+/// objects, nested collections and (with `optional`) `Option`s, but not the
+/// other wrappers.
+///
+/// Collections also get an `audit()` method returning a `zero_v::Audit`,
+/// which lists the type name, size, alignment and offset of every object in
+/// the collection (along with how deeply it's nested in inner composites),
+/// to track down which object is responsible for a large collection.
+///
+/// Methods with bounds on `Self` other than `Self: Sized` (such as
+/// `where Self: Clone`) are skipped, since the generated code only knows that
+/// each object in the collection implements your trait. They can still be
+/// called on the objects themselves.
+///
+/// Passing `shadow` as well (`#[zero_v(trait_types, shadow)]`) turns on
+/// shadow execution when the `shadow` feature is enabled. In debug builds,
+/// every output of the `iter_` methods is then checked against the same
+/// method called through `dyn {TraitName}`, panicking with the level and
+/// type of the offending object if the two disagree. This requires your
+/// trait to be object safe and your outputs to be `PartialEq + Debug`.
+/// Release builds and builds without the feature are unchanged.
+///
+/// Passing `shared` (`#[zero_v(trait_types, shared)]`) generates a
+/// `{TraitName}Mut` trait with the same methods taking `&mut self`, and
+/// implements your trait for `zero_v::RefCellNode<T>` and
+/// `zero_v::MutexNode<T>` wherever `T` implements it. Stateful objects can
+/// then implement `{TraitName}Mut` and be composed inside one of the
+/// wrappers, which borrow or lock them for each call. This requires every
+/// item in your trait to be a method the macro can iterate over.
+///
+/// Passing `refs` (`#[zero_v(trait_types, refs)]`) implements your trait for
+/// `&T`, `Box<T>`, `Rc<T>` and `Arc<T>` wherever `T` implements it, so a
+/// composite can hold references to long-lived objects owned elsewhere (see
+/// `zero_v::compose_refs!`) or shared, heap allocated ones. Like
+/// `shared`, this requires every item in your trait to be a method the macro
+/// can iterate over.
+///
+/// Passing `either` (`#[zero_v(trait_types, either)]`) implements your trait
+/// for `zero_v::Either<L, R>` wherever both `L` and `R` implement it, calling
+/// whichever object it holds. This lets one position in a composite hold one
+/// of two implementations, picked at runtime, with the same requirements on
+/// your trait as `shared`.
+///
+/// Passing `dyn_tail` (`#[zero_v(trait_types, dyn_tail)]`) lets a collection
+/// end in a `zero_v::DynTail<dyn {TraitName}>` of boxed trait objects, such
+/// as plugins loaded at runtime, with `compose!(Builtin::new(), ..tail)`. The
+/// iterators run the statically dispatched objects first and then the tail's
+/// objects through their vtables. This requires your trait to be object
+/// safe, and the tail's objects aren't passed to mutators or visitors.
+///
+/// Passing `dyn_in_debug` (`#[zero_v(trait_types, dyn_in_debug)]`) makes
+/// the level based methods (and the `iter_` methods built on them) call each
+/// object through `dyn {TraitName}` in debug builds. The statically
+/// dispatched version of each level method is only generated for release
+/// builds, so debug builds compile one copy of it per collection type rather
+/// than one per node. This requires your trait to be object safe.
+///
+/// Passing `to_dyn_vec` (`#[zero_v(trait_types, to_dyn_vec)]`) adds a
+/// `to_dyn_vec(&self)` method to collections, returning a `Vec<&dyn
+/// {TraitName}>` of their objects in level order, for handing them to an API
+/// which needs trait objects. Objects skipped by the iterators (such as
+/// `None`s) are left out. This requires your trait to be object safe.
+///
+/// Passing `mock` (`#[zero_v(trait_types, mock)]`) generates a
+/// `{TraitName}Mock` struct implementing your trait, for testing code which
+/// takes collections of your objects. It has an `Option` field for each
+/// method with an output, holding the value the method returns (it panics
+/// if there isn't one), and a `calls` field counting the calls to each
+/// method. `zero_v::mock_node!({TraitName}Mock { method_name: value })`
+/// builds one. This requires your trait to have no generic parameters, the
+/// outputs to be `Clone` and every item in your trait to be a method the
+/// macro can iterate over.
+///
+/// Passing `args` (`#[zero_v(trait_types, args)]`) generates a
+/// `{MethodName}Args` struct for each method, with one field per argument,
+/// and an `iter_{method_name}_with(&self, args)` method taking it. The struct
+/// implements `Default` when all of its fields do, so long argument lists
+/// can be given partially:
+///
+/// ```ignore
+/// collection.iter_execute_with(ExecuteArgs { input: 1, ..Default::default() })
+/// ```
+///
+/// Passing `max_nodes = N` (`#[zero_v(trait_types, max_nodes = 32)]`) puts a
+/// budget on the size of the collections used with your trait. Building code
+/// which executes a collection holding more than `N` objects (counting the
+/// objects of nested composites individually) then fails with a compile time
+/// error naming the budget:
+///
+/// ```compile_fail
+/// use zero_v::{compose, zero_v};
+///
+/// #[zero_v(trait_types, max_nodes = 2)]
+/// trait IntOp {
+///     fn execute(&self, input: usize) -> usize;
+/// }
+///
+/// impl IntOp for usize {
+///     fn execute(&self, input: usize) -> usize {
+///         input + self
+///     }
+/// }
+///
+/// fn main() {
+///     let ops = compose!(1usize, 2usize, 3usize);
+///     println!("{}", ops.iter_execute(1).sum::<usize>());
+/// }
+/// ```
+///
+/// The `{method_name}_at_const` methods index the nodes of a collection
+/// (see `zero_v::At`), so they fail to compile on collections holding nested
+/// composites or blocks, where a node can hold several objects:
+///
+/// ```compile_fail
+/// use zero_v::{compose, zero_v};
+///
+/// #[zero_v(trait_types)]
+/// trait IntOp {
+///     fn execute(&self, input: usize) -> usize;
+/// }
+///
+/// impl IntOp for usize {
+///     fn execute(&self, input: usize) -> usize {
+///         input + self
+///     }
+/// }
+///
+/// fn main() {
+///     let ops = compose!(compose!(1usize, 2usize), 3usize);
+///     println!("{}", ops.execute_at_const::<1>(1));
+/// }
+/// ```
+///
+/// `level_of::<T, _>()` finds the object by its type, so it fails to compile
+/// (with a "type annotations needed" error) on a collection holding more than
+/// one object of type `T`, since there's no single level to give:
+///
+/// ```compile_fail,E0283
+/// use zero_v::{compose, zero_v, Composite, Node};
+///
+/// #[zero_v(trait_types)]
+/// trait IntOp {
+///     fn execute(&self, input: usize) -> usize;
+/// }
+///
+/// struct Adder(usize);
+///
+/// impl IntOp for Adder {
+///     fn execute(&self, input: usize) -> usize {
+///         input + self.0
+///     }
+/// }
+///
+/// fn main() {
+///     type Ops = Composite<Node<Adder, Node<Adder, ()>>>;
+///     let ops: Ops = compose!(Adder(1), Adder(2));
+///     println!("{:?}", ops.execute_at(1, Ops::level_of::<Adder, _>()));
+/// }
+/// ```
+///
 /// # Interface
 /// For traits, the interface is very simple.
 ///
@@ -99,6 +425,39 @@ impl Parse for ZeroVGen {
 ///     println!("{}", result);
 /// }
 /// ```
+///
+/// Passing a collection holding an object which doesn't implement your trait
+/// fails to compile, with a note (unless your trait has a
+/// `#[diagnostic::on_unimplemented]` of its own) that every object in the
+/// collection needs to implement it:
+///
+/// ```compile_fail,E0277
+/// # use zero_v::{compose, zero_v};
+/// #
+/// # #[zero_v(trait_types)]
+/// # trait IntOp {
+/// #     fn execute(&self, input:usize) -> usize;
+/// # }
+/// #
+/// # struct Adder {}
+/// #
+/// # impl IntOp for Adder {
+/// #     fn execute(&self, input: usize) -> usize {
+/// #         input + 1
+/// #     }
+/// # }
+/// #
+/// # #[zero_v(fn_generics, IntOp as IntOps)]
+/// # fn get_intops_sum(input: usize, collection: IntOps) -> usize {
+/// #     collection.iter_execute(input).sum()
+/// # }
+/// #
+/// fn main() {
+///     let collection = compose!(Adder {}, "not an IntOp");
+///     let result = get_intops_sum(1, collection);
+///     println!("{}", result);
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn zero_v(args: TokenStream, input: TokenStream) -> TokenStream {
     match parse_macro_input!(args as ZeroVGen) {
@@ -106,3 +465,102 @@ pub fn zero_v(args: TokenStream, input: TokenStream) -> TokenStream {
         ZeroVGen::FnGenerics(g) => g.generate(input),
     }
 }
+
+/// Builds a composite holding `N` copies of the same stage. The item
+/// expression is evaluated once per node, so `compose_repeat!(expr; 3)` is
+/// equivalent to `compose!(expr, expr, expr)`.
+///
+/// # Usage Example
+///
+/// ```
+/// use zero_v::{compose, compose_repeat};
+///
+/// let repeated = compose_repeat!(String::from("pass"); 3);
+/// assert_eq!(repeated, compose!(
+///     String::from("pass"),
+///     String::from("pass"),
+///     String::from("pass")
+/// ));
+/// ```
+///
+/// Writing `compose_repeat!(expr for i in start..end)` instead builds one
+/// node for each value of `i`, with every `#i` in the expression replaced by
+/// the value, which is handy for sequences of const generic objects. The
+/// bounds of the range must be integer literals, and `..=` works too.
+///
+/// ```
+/// use zero_v::{compose, compose_repeat};
+///
+/// struct Adder<const N: usize>;
+///
+/// impl<const N: usize> Adder<N> {
+///     fn amount(&self) -> usize {
+///         N
+///     }
+/// }
+///
+/// let (first, second, third) = compose_repeat!(Adder::<#i> for i in 0..3).into_parts();
+/// assert_eq!((first.amount(), second.amount(), third.amount()), (0, 1, 2));
+/// assert_eq!(compose_repeat!(#n * 10 for n in 1..=3), compose!(10, 20, 30));
+/// ```
+#[proc_macro]
+pub fn compose_repeat(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as compose_repeat::ComposeRepeat).generate()
+}
+
+/// Builds a composite holding the given objects as a balanced binary tree of
+/// nested composites, rather than as one long chain of nodes. A chain of `N`
+/// nodes nests `N` types deep, which makes for huge types (and slow builds)
+/// once `N` gets large, while the tree only nests about `2 * log2(N)` deep.
+///
+/// The iteration methods generated by the zero_v macro treat the objects of
+/// nested composites as levels of the outer one, so they run the objects in
+/// the order given, exactly as if they'd been passed to `compose!`. Like any
+/// other nested composites, this needs a trait without type parameters, and
+/// `len()` and the level based accessors like `get` only see the top of the
+/// tree.
+///
+/// # Usage Example
+///
+/// ```
+/// use zero_v::{compose, compose_balanced};
+///
+/// let tree = compose_balanced!(1, 2, 3, 4, 5);
+/// assert_eq!(tree, compose!(compose!(compose!(1, 2), 3), compose!(4, 5)));
+/// ```
+#[proc_macro]
+pub fn compose_balanced(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as compose_balanced::ComposeBalanced).generate()
+}
+
+/// Builds a composite from a declarative spec: a list of string keys, each of
+/// which is looked up in a registry mapping keys to constructor expressions.
+/// The spec can be given inline, or as the path of a file (relative to the
+/// crate root) holding a comma separated list of quoted keys, so a pipeline's
+/// composition can live in a simple checked-in file while keeping fully
+/// static dispatch. Unknown keys fail compilation.
+///
+/// Each key's expression is evaluated once for every time the key appears in
+/// the spec.
+///
+/// # Usage Example
+///
+/// ```
+/// use zero_v::{compose, compose_from_spec};
+///
+/// let pipeline = compose_from_spec! {
+///     registry {
+///         "greeting" => String::from("hello"),
+///         "answer" => 42,
+///     },
+///     spec = ["answer", "greeting", "answer"],
+/// };
+/// assert_eq!(pipeline, compose!(42, String::from("hello"), 42));
+/// ```
+///
+/// With a spec file, `spec = "pipelines/default.spec"` reads the keys from
+/// that file instead, and the crate is rebuilt whenever the file changes.
+#[proc_macro]
+pub fn compose_from_spec(input: TokenStream) -> TokenStream {
+    parse_macro_input!(input as compose_from_spec::ComposeFromSpec).generate()
+}
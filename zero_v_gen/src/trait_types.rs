@@ -1,17 +1,54 @@
 use proc_macro::TokenStream;
-use proc_macro2::Ident;
+use proc_macro2::{Group, Ident, Spacing, TokenStream as TokenStream2, TokenTree};
 use quote::{format_ident, quote};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{
-    parse_macro_input, parse_quote, FnArg, GenericParam, ItemTrait, Pat, PatType, ReturnType,
-    TraitItem, Type, WherePredicate,
+    parse_macro_input, parse_quote, FnArg, GenericArgument, GenericParam, Generics, ItemTrait, Lit,
+    Meta, MetaNameValue, NestedMeta, Pat, PatType, PathArguments, ReturnType, Signature,
+    TraitBound, TraitBoundModifier, TraitItem, Type, TypeParamBound, TypePath, WherePredicate,
 };
 
 use crate::Idents;
 
-pub(crate) struct TraitTypes;
+/// The largest tuple the level and iterator traits are implemented for by
+/// default. Every arity adds a set of impls to compile, so larger tuples are
+/// opt in through the `tuples` option.
+const DEFAULT_TUPLE_LEN: usize = 12;
+
+/// The largest tuple the `tuples` option accepts, matching the tuple impls of
+/// NextNode in zero_v.
+const MAX_TUPLE_LEN: usize = 32;
+
+pub(crate) struct TraitTypes {
+    // Cross-check every execution against dynamic dispatch in debug builds.
+    shadow: bool,
+    // Generate interior mutability wrappers for stateful objects.
+    shared: bool,
+    // Reject composites with more objects than this at compile time.
+    max_nodes: Option<usize>,
+    // Generate a struct holding the arguments of each method.
+    args: bool,
+    // Implement the trait for references to objects implementing it.
+    refs: bool,
+    // Implement the trait for zero_v::Either.
+    either: bool,
+    // Let composites end in a zero_v::DynTail of trait objects.
+    dyn_tail: bool,
+    // Iterate through trait objects in debug builds.
+    dyn_in_debug: bool,
+    // Generate a mock implementation of the trait for tests.
+    mock: bool,
+    // Generate a method listing a composite's objects as trait objects.
+    to_dyn_vec: bool,
+    // Let nodes hold Options and zero_v::Toggles.
+    optional: bool,
+    // Let nodes hold arrays and zero_v::Repeats of objects.
+    blocks: bool,
+    // Implement the level and iterator traits for tuples up to this length.
+    tuples: usize,
+}
 
 impl TraitTypes {
     pub(crate) fn generate(&self, input: TokenStream) -> TokenStream {
@@ -22,7 +59,13 @@ impl TraitTypes {
         let trait_ident = &trait_type.ident;
         let trait_methods = || {
             trait_type.items.iter().filter_map(|i| match i {
-                TraitItem::Method(m) => Some(m),
+                TraitItem::Method(m) if is_supported(&m.sig) && !is_lending(&m.sig) => Some(m),
+                _ => None,
+            })
+        };
+        let lending_methods = || {
+            trait_type.items.iter().filter_map(|i| match i {
+                TraitItem::Method(m) if is_supported(&m.sig) && is_lending(&m.sig) => Some(m),
                 _ => None,
             })
         };
@@ -40,6 +83,7 @@ impl TraitTypes {
                     })
                     .collect::<Punctuated<FnArg, Comma>>()
             })
+            .map(with_trailing_comma)
             .collect::<Vec<_>>();
         let trait_method_args = trait_methods()
             .map(|m| {
@@ -55,13 +99,33 @@ impl TraitTypes {
                     })
                     .collect::<Punctuated<Ident, Comma>>()
             })
+            .map(with_trailing_comma)
+            .collect::<Vec<_>>();
+
+        // The ref iterators hold a reference to each argument instead, and
+        // copy it out for every node.
+        let ref_iter_inputs = trait_methods()
+            .map(|m| {
+                let inputs = m.sig.inputs.iter().filter_map(|arg| match arg {
+                    FnArg::Typed(PatType { pat, ty, .. }) => Some(quote! { #pat: &'zero_v #ty }),
+                    _ => None,
+                });
+                quote! { #(#inputs,)* }
+            })
+            .collect::<Vec<_>>();
+        let ref_iter_self_args = trait_method_args
+            .iter()
+            .map(|args| {
+                let iter = args.iter();
+                quote! { #(*self.#iter,)* }
+            })
             .collect::<Vec<_>>();
 
         let trait_method_self_args = trait_method_args
             .iter()
             .map(|args| {
                 let iter = args.iter();
-                quote! { #(self.#iter),* }
+                quote! { #(self.#iter,)* }
             })
             .collect::<Vec<_>>();
 
@@ -72,14 +136,47 @@ impl TraitTypes {
             })
             .collect();
 
+        // Generated items can trip lints through no fault of the user (e.g.
+        // the level methods take one more argument than the trait's, and not
+        // every generated method will be used), so allow those which user
+        // crates might otherwise deny.
+        let allow_lints = quote! {
+            #[allow(
+                dead_code,
+                non_camel_case_types,
+                clippy::too_many_arguments,
+                clippy::type_complexity,
+            )]
+        };
+
         let level_trait = idents.level_trait();
 
+        // A composite holding an object which doesn't implement the trait
+        // fails on the bound on that object, which the compiler reports in
+        // place of the generated traits requiring it (and method calls on
+        // the composite don't use these hints at all). So the hint goes on
+        // the trait itself, unless it already has one of its own.
+        let has_on_unimplemented = trait_type.attrs.iter().any(|attr| {
+            let segments = attr.path.segments.iter().map(|s| s.ident.to_string());
+            segments.eq(["diagnostic", "on_unimplemented"])
+        });
+        let on_unimplemented = if has_on_unimplemented {
+            quote! {}
+        } else {
+            let note = format!(
+                "every object in a zero_v collection of `{}` objects needs to implement it",
+                trait_ident
+            );
+            quote! { #[diagnostic::on_unimplemented(note = #note)] }
+        };
+        let mutator_trait = idents.mutator_trait();
+
         let zv_trait_type: GenericParam = parse_quote! { TraitType };
         let zv_trait_type_pred: WherePredicate =
             parse_quote! { TraitType: #trait_ident #ty_generics };
         let zv_node_type: GenericParam = parse_quote! { NodeType };
         let zv_node_type_pred: WherePredicate =
-            parse_quote! { NodeType: NextNode + #level_trait #ty_generics };
+            parse_quote! { NodeType: zero_v::NextNode + #level_trait #ty_generics };
 
         let zv_generics = vec![zv_trait_type.clone(), zv_node_type.clone()];
         let zv_where = vec![zv_trait_type_pred.clone(), zv_node_type_pred.clone()];
@@ -95,7 +192,7 @@ impl TraitTypes {
         let (level_impl_generics, _, level_where_clause) = level_generics.split_for_impl();
         let level_methods: Vec<Ident> = idents.level_methods().collect();
         let level_method_inputs = trait_methods()
-            .map(|m| m.sig.inputs.clone())
+            .map(|m| with_trailing_comma(m.sig.inputs.clone()))
             .collect::<Vec<_>>();
 
         let composite_zv_generics = vec![zv_node_type.clone()];
@@ -116,182 +213,3800 @@ impl TraitTypes {
             })
             .collect();
 
-        let iter_trait = idents.iter_trait();
-        let mut iter_generics = trait_generics.clone();
-        iter_generics.params.push(zv_node_type.clone());
-        iter_generics
-            .make_where_clause()
-            .predicates
-            .push(zv_node_type_pred.clone());
+        // Methods whose outputs borrow from (or are otherwise defined by) the
+        // object they're called on, such as those returning a generic
+        // associated type, don't have a single output type across the
+        // composite, so they can't be yielded from an iterator. Instead,
+        // each output is handed to a visitor which is generic over the type
+        // of the object that produced it.
+        let visitor_traits: Vec<Ident> = idents.visitor_traits().collect();
+        let for_each_level_methods: Vec<Ident> = idents.for_each_level_methods().collect();
 
-        let (iter_impl_generics, iter_ty_generics, iter_where_clause) =
-            iter_generics.split_for_impl();
-        let iter_methods: Vec<Ident> = idents.iter_methods().collect();
+        // The try_fold methods run the fold as a single recursive pass over
+        // the nodes, returning as soon as the fold returns an error.
+        let try_fold_level_methods: Vec<Ident> = idents.try_fold_level_methods().collect();
+        let try_fold_methods: Vec<Ident> = idents.try_fold_methods().collect();
+        let extend_methods: Vec<Ident> = idents.extend_methods().collect();
+        let try_fold_generics = trait_method_outputs
+            .iter()
+            .map(|output| {
+                quote! {
+                    <
+                        ZeroVAcc,
+                        ZeroVError,
+                        ZeroVFold: FnMut(ZeroVAcc, #output) -> Result<ZeroVAcc, ZeroVError>
+                    >
+                }
+            })
+            .collect::<Vec<_>>();
+        let for_each_methods: Vec<Ident> = idents.for_each_methods().collect();
+        let lending_idents: Vec<Ident> = lending_methods().map(|m| m.sig.ident.clone()).collect();
+        let lending_inputs = lending_methods()
+            .map(|m| with_trailing_comma(m.sig.inputs.clone()))
+            .collect::<Vec<_>>();
+        let lending_args = lending_methods()
+            .map(|m| {
+                m.sig
+                    .inputs
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        FnArg::Typed(PatType { pat, .. }) => match **pat {
+                            Pat::Ident(ref i) => Some(i.ident.clone()),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .collect::<Punctuated<Ident, Comma>>()
+            })
+            .map(with_trailing_comma)
+            .collect::<Vec<_>>();
 
-        let composite_iters: Vec<Ident> = idents.composite_iters().collect();
-        let mut composite_generics = trait_generics.clone();
-        let mut composite_lifetime_generics = composite_generics.clone();
-        composite_generics
-            .params
-            .extend(vec![parse_quote! { '_ }, zv_node_type.clone()]);
+        let (lending_generics, lending_where): (Vec<TokenStream2>, Vec<TokenStream2>) =
+            lending_methods()
+                .zip(&visitor_traits)
+                .map(|(m, visitor_trait)| {
+                    let mut generics = without_self_bounds(&m.sig.generics);
+                    generics
+                        .params
+                        .push(parse_quote! { ZeroVVisitor: #visitor_trait #ty_generics });
+                    let (_, _, where_clause) = generics.split_for_impl();
+                    let params = &generics.params;
+                    (quote! { <#params> }, quote! { #where_clause })
+                })
+                .unzip();
 
-        composite_lifetime_generics
-            .params
-            .extend(vec![parse_quote! { 'zero_v }, zv_node_type.clone()]);
+        let visitor_methods = lending_methods()
+            .map(|m| {
+                let mut generics = without_self_bounds(&m.sig.generics);
+                let lifetimes = generics
+                    .lifetimes()
+                    .map(|l| l.lifetime.clone())
+                    .collect::<Vec<_>>();
+                generics
+                    .params
+                    .push(parse_quote! { TraitType: #trait_ident #ty_generics });
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .extend(lifetimes.iter().map(|l| -> WherePredicate {
+                        parse_quote! { TraitType: #l }
+                    }));
+                let (_, _, where_clause) = generics.split_for_impl();
+                let params = &generics.params;
 
-        composite_lifetime_generics
-            .make_where_clause()
-            .predicates
-            .push(zv_node_type_pred.clone());
-        let (_, composite_ty_generics, _) = composite_generics.split_for_impl();
+                let output = match &m.sig.output {
+                    ReturnType::Default => quote! { () },
+                    ReturnType::Type(_, ty) => replace_self(
+                        quote! { #ty },
+                        &quote! { TraitType },
+                        &quote! { <TraitType as #trait_ident #ty_generics> },
+                    ),
+                };
 
-        let (composite_impl_generics, composite_lifetime_ty_generics, composite_where_clause) =
-            composite_lifetime_generics.split_for_impl();
+                quote! {
+                    fn visit<#params>(&mut self, level: usize, output: #output) #where_clause;
+                }
+            })
+            .collect::<Vec<_>>();
 
-        let composite_phantom_types = trait_generics
-            .params
-            .iter()
-            .filter_map(|p| match p {
-                GenericParam::Type(t) => Some(t.ident.clone()),
+        // &mut self methods can't be dispatched to one level at a time
+        // through a shared reference, so they're called on every object by a
+        // generated implementation of the mutator trait instead.
+        let mut_methods = || {
+            trait_type.items.iter().filter_map(|i| match i {
+                TraitItem::Method(m) if is_supported_mut(&m.sig) => Some(m),
                 _ => None,
             })
+        };
+        let iter_mut_methods: Vec<Ident> = idents.iter_mut_methods().collect();
+        let for_each_mut_methods: Vec<Ident> = idents.for_each_mut_methods().collect();
+        let mut_callers: Vec<Ident> = idents.mut_callers().collect();
+        let mut_method_inputs = mut_methods()
+            .map(|m| {
+                m.sig
+                    .inputs
+                    .iter()
+                    .filter(|arg| matches!(arg, FnArg::Typed(_)))
+                    .cloned()
+                    .collect::<Punctuated<FnArg, Comma>>()
+            })
+            .map(with_trailing_comma)
             .collect::<Vec<_>>();
-
-        let composite_phantom_names = composite_phantom_types
-            .iter()
-            .enumerate()
-            .map(|(i, _)| format_ident!("_phantom_{}", i))
+        let mut_method_args = mut_methods()
+            .map(|m| {
+                m.sig
+                    .inputs
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        FnArg::Typed(PatType { pat, .. }) => match **pat {
+                            Pat::Ident(ref i) => Some(i.ident.clone()),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .collect::<Punctuated<Ident, Comma>>()
+            })
+            .map(with_trailing_comma)
             .collect::<Vec<_>>();
+        let mut_method_outputs: Vec<Type> = mut_methods()
+            .map(|m| match &m.sig.output {
+                ReturnType::Default => parse_quote! { () },
+                ReturnType::Type(_, ty) => *ty.clone(),
+            })
+            .collect();
 
-        let composite_phantom_fields = quote! {
-            #(
-                #composite_phantom_names: PhantomData<#composite_phantom_types>,
-            )*
+        // Methods taking self by value can only run once per object, so
+        // they're called as the composite is taken apart, through a
+        // separate consume trait. Methods taking an Rc<Self> or Arc<Self>
+        // go the same way, with each object moved into a fresh Rc or Arc.
+        // Repeats and dyn tails can't be taken apart into their objects, so
+        // composites holding them don't get it.
+        let consuming_methods = || {
+            trait_type.items.iter().filter_map(|i| match i {
+                TraitItem::Method(m) if is_supported_consuming(&m.sig) => Some(m),
+                _ => None,
+            })
         };
-        let composite_phantom_vals = quote! {
-            #(
-                #composite_phantom_names: PhantomData,
-            )*
+        let consume_trait = idents.consume_trait();
+        let each_into_methods: Vec<Ident> = idents.each_into_methods().collect();
+        let for_each_into_methods: Vec<Ident> = idents.for_each_into_methods().collect();
+        let into_iter_methods: Vec<Ident> = idents.consuming_iter_methods().collect();
+        // The receiver is skipped by position, since Rc and Arc receivers
+        // are typed arguments as far as syn is concerned.
+        let consuming_method_inputs = consuming_methods()
+            .map(|m| {
+                m.sig
+                    .inputs
+                    .iter()
+                    .skip(1)
+                    .cloned()
+                    .collect::<Punctuated<FnArg, Comma>>()
+            })
+            .map(with_trailing_comma)
+            .collect::<Vec<_>>();
+        let consuming_method_args = consuming_methods()
+            .map(|m| {
+                m.sig
+                    .inputs
+                    .iter()
+                    .skip(1)
+                    .filter_map(|arg| match arg {
+                        FnArg::Typed(PatType { pat, .. }) => match **pat {
+                            Pat::Ident(ref i) => Some(i.ident.clone()),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .collect::<Punctuated<Ident, Comma>>()
+            })
+            .map(with_trailing_comma)
+            .collect::<Vec<_>>();
+        let consuming_method_outputs: Vec<Type> = consuming_methods()
+            .map(|m| match &m.sig.output {
+                ReturnType::Default => parse_quote! { () },
+                ReturnType::Type(_, ty) => *ty.clone(),
+            })
+            .collect();
+        // Run each method on the object in zero_v_inner.
+        let consuming_method_calls = consuming_methods()
+            .zip(&consuming_method_args)
+            .map(|(m, args)| {
+                let method = &m.sig.ident;
+                let receiver = match self_kind(&m.sig) {
+                    Some(SelfKind::Rc) => quote! { zero_v::__private::Rc::new(zero_v_inner) },
+                    Some(SelfKind::Arc) => quote! { zero_v::__private::Arc::new(zero_v_inner) },
+                    _ => quote! { zero_v_inner },
+                };
+                quote! { f(#receiver.#method(#args)); }
+            })
+            .collect::<Vec<_>>();
+
+        // Methods taking a Pin<&mut Self> are called on each object of a
+        // pinned composite through a separate pinned trait, which walks the
+        // nodes with zero_v's pin projections. Only wrappers with safe
+        // projections (nested composites and Options) are supported.
+        let pinned_methods = || {
+            trait_type.items.iter().filter_map(|i| match i {
+                TraitItem::Method(m) if is_supported_pinned(&m.sig) => Some(m),
+                _ => None,
+            })
         };
-        let tokens = quote! {
-            use zero_v::{Composite, NextNode, Node};
-            use std::marker::PhantomData;
-            #trait_type
+        let pinned_trait = idents.pinned_trait();
+        let each_pinned_methods: Vec<Ident> = idents.each_pinned_methods().collect();
+        let for_each_pinned_methods: Vec<Ident> = idents.for_each_pinned_methods().collect();
+        let pinned_iter_methods: Vec<Ident> = idents.pinned_iter_methods().collect();
+        let pinned_method_inputs = pinned_methods()
+            .map(|m| m.sig.inputs.iter().skip(1).cloned().collect::<Punctuated<FnArg, Comma>>())
+            .map(with_trailing_comma)
+            .collect::<Vec<_>>();
+        let pinned_method_args = pinned_methods()
+            .map(|m| {
+                m.sig
+                    .inputs
+                    .iter()
+                    .skip(1)
+                    .filter_map(|arg| match arg {
+                        FnArg::Typed(PatType { pat, .. }) => match **pat {
+                            Pat::Ident(ref i) => Some(i.ident.clone()),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .collect::<Punctuated<Ident, Comma>>()
+            })
+            .map(with_trailing_comma)
+            .collect::<Vec<_>>();
+        let pinned_method_outputs: Vec<Type> = pinned_methods()
+            .map(|m| match &m.sig.output {
+                ReturnType::Default => parse_quote! { () },
+                ReturnType::Type(_, ty) => *ty.clone(),
+            })
+            .collect();
+        // Run each method on the pinned object in zero_v_inner.
+        let pinned_method_calls = pinned_methods()
+            .zip(&pinned_method_args)
+            .map(|(m, args)| {
+                let method = &m.sig.ident;
+                quote! { f(zero_v_inner.#method(#args)); }
+            })
+            .collect::<Vec<_>>();
 
-            trait #level_trait #trait_generics #where_clause {
-                #(
-                    fn #level_methods(#level_method_inputs, level: usize) -> #level_method_outputs;
-                )*
+        let zv_inner_type: GenericParam = parse_quote! { InnerType };
+        let zv_inner_type_pred: WherePredicate =
+            parse_quote! { InnerType: zero_v::NextNode + #level_trait #ty_generics };
+        let mut nested_level_generics = trait_generics.clone();
+        nested_level_generics
+            .params
+            .extend(vec![zv_inner_type.clone(), zv_node_type.clone()]);
+        nested_level_generics
+            .make_where_clause()
+            .predicates
+            .extend(vec![zv_inner_type_pred, zv_node_type_pred.clone()]);
+        let (nested_level_generics, _, nested_level_where) = nested_level_generics.split_for_impl();
+
+        // In shadow mode, each node can also be looked up as a trait object,
+        // so the iterators can check that dynamic dispatch on the same node
+        // gives the same output as the generated static dispatch. The same
+        // lookup lets dyn_in_debug iterate through trait objects instead, and
+        // to_dyn_vec list them.
+        // Every level trait impl counts its levels in a const, which is how
+        // levels are found by type at compile time. A dyn tail's objects
+        // aren't known until runtime, so they don't count. With a max_nodes
+        // budget, the node impls also assert on that count. The assertion is
+        // a const, so it's checked when the composite's type is known at
+        // compile time (when the code using it is built).
+        let count = |ty: TokenStream2| {
+            quote! { <#ty as #level_trait #ty_generics>::LEVEL_COUNT }
+        };
+        let node_count = count(quote! { #zv_node_type });
+        let inner_count = count(quote! { zero_v::Composite<#zv_inner_type> });
+        let level_count_unit = quote! { const LEVEL_COUNT: usize = 0; };
+        let level_count_node = quote! { const LEVEL_COUNT: usize = #node_count + 1; };
+        let level_count_nested = quote! { const LEVEL_COUNT: usize = #inner_count + #node_count; };
+        let level_count_composite = quote! { const LEVEL_COUNT: usize = #node_count; };
+        let max_nodes_decls = match self.max_nodes {
+            Some(max_nodes) => {
+                let message = format!(
+                    "composite has more objects than the max_nodes budget of {} set on `{}`",
+                    max_nodes, trait_ident
+                );
+                quote! {
+                    const WITHIN_MAX_NODES: () =
+                        assert!(Self::LEVEL_COUNT <= #max_nodes, #message);
+                }
             }
+            None => quote! {},
+        };
+        let max_nodes_check = if self.max_nodes.is_some() {
+            quote! { let () = Self::WITHIN_MAX_NODES; }
+        } else {
+            quote! {}
+        };
 
-            impl #impl_generics #level_trait #ty_generics for () #where_clause {
-                #(
-                    #[allow(unused)]
-                    fn #level_methods(#level_method_inputs, level: usize) -> #level_method_outputs {
-                        None
+        let shadow = self.shadow && cfg!(feature = "shadow");
+        let dyn_lookup = shadow || self.dyn_in_debug || self.to_dyn_vec;
+        let dyn_trait = quote! { dyn #trait_ident #ty_generics + '_ };
+        let to_dyn_vec_decl = if self.to_dyn_vec {
+            quote! {
+                fn to_dyn_vec(&self) -> zero_v::__private::Vec<&(#dyn_trait)>;
+            }
+        } else {
+            quote! {}
+        };
+        let shadow_level_decls = if dyn_lookup {
+            quote! {
+                fn dyn_at_level(&self, level: usize) -> Option<&(#dyn_trait)>;
+            }
+        } else {
+            quote! {}
+        };
+        // to_dyn_vec pushes every object below each point in a single walk,
+        // rather than looking each level up from the top.
+        let dyn_nodes_vec = quote! {
+            zero_v::__private::Vec<&'zero_v (dyn #trait_ident #ty_generics + 'zero_v)>
+        };
+        let dyn_nodes_decl = if self.to_dyn_vec {
+            quote! {
+                fn dyn_nodes<'zero_v>(&'zero_v self, nodes: &mut #dyn_nodes_vec);
+            }
+        } else {
+            quote! {}
+        };
+        let dyn_nodes = |body: TokenStream2| {
+            if self.to_dyn_vec {
+                quote! {
+                    fn dyn_nodes<'zero_v>(&'zero_v self, nodes: &mut #dyn_nodes_vec) {
+                        #body
                     }
-                )*
+                }
+            } else {
+                quote! {}
             }
-
-            impl #level_impl_generics #level_trait #ty_generics
-                for Node<#zv_trait_type, #zv_node_type>
-            #level_where_clause
-            {
-                #(
-                    fn #level_methods(#level_method_inputs, level: usize)
-                        -> #level_method_outputs
-                    {
-                        if level != 0 {
-                            self.next.#level_methods(#trait_method_args, level - 1)
-                        } else {
-                            Some(self.data.#trait_method_idents(#trait_method_args))
-                        }
+        };
+        let shadow_unit_impls = if dyn_lookup {
+            quote! {
+                fn dyn_at_level(&self, _level: usize) -> Option<&(#dyn_trait)> {
+                    None
+                }
+            }
+        } else {
+            quote! {}
+        };
+        let dyn_nodes_unit = dyn_nodes(quote! { let _ = nodes; });
+        let shadow_node_impls = if dyn_lookup {
+            quote! {
+                fn dyn_at_level(&self, level: usize) -> Option<&(#dyn_trait)> {
+                    if level != 0 {
+                        self.next().dyn_at_level(level - 1)
+                    } else {
+                        Some(self.data())
                     }
-                )*
+                }
             }
-
-            impl #composite_level_generics #level_trait #ty_generics
-                for Composite<#zv_node_type>
-            #composite_level_where
-            {
-                #(
-                    fn #level_methods(#level_method_inputs, level: usize)
-                        -> #level_method_outputs
-                    {
-                            self.head.#level_methods(#trait_method_args, level)
+        } else {
+            quote! {}
+        };
+        let dyn_nodes_node = dyn_nodes(quote! {
+            nodes.push(self.data());
+            self.next().dyn_nodes(nodes);
+        });
+        let shadow_nested_impls = if dyn_lookup {
+            quote! {
+                fn dyn_at_level(&self, level: usize) -> Option<&(#dyn_trait)> {
+                    let inner_count = self.data().level_count();
+                    if level < inner_count {
+                        self.data().dyn_at_level(level)
+                    } else {
+                        self.next().dyn_at_level(level - inner_count)
                     }
-                )*
+                }
             }
-
-
-            trait #iter_trait #iter_generics #iter_where_clause {
-                #(
-                    fn #iter_methods(#level_method_inputs)
-                        -> #composite_iters #composite_ty_generics;
-                )*
+        } else {
+            quote! {}
+        };
+        let dyn_nodes_nested = dyn_nodes(quote! {
+            self.data().dyn_nodes(nodes);
+            self.next().dyn_nodes(nodes);
+        });
+        let shadow_composite_impls = if dyn_lookup {
+            quote! {
+                fn dyn_at_level(&self, level: usize) -> Option<&(#dyn_trait)> {
+                    self.head().dyn_at_level(level)
+                }
             }
-
-            impl #iter_impl_generics #iter_trait #iter_ty_generics for Composite<#zv_node_type>
-            #iter_where_clause
-            {
-                #(
-                    fn #iter_methods(#level_method_inputs)
-                        -> #composite_iters #composite_ty_generics
-                    {
-                        #composite_iters::new(&self.head, #trait_method_args)
+        } else {
+            quote! {}
+        };
+        let dyn_nodes_composite = dyn_nodes(quote! { self.head().dyn_nodes(nodes); });
+        let shadow_checks = |args: &[TokenStream2], level: TokenStream2| -> Vec<TokenStream2> {
+            trait_method_idents
+                .iter()
+                .zip(args)
+                .map(|(method, args)| {
+                    if !shadow {
+                        return quote! {};
                     }
-                )*
-            }
+                    quote! {
+                        #[cfg(debug_assertions)]
+                        {
+                            let level = #level;
+                            let mirror = self
+                                .parent
+                                .dyn_at_level(level)
+                                .map(|node| node.#method(#args));
+                            assert!(
+                                result == mirror,
+                                "static and dynamic dispatch diverged at level {} ({}): {:?} != {:?}",
+                                level,
+                                self.parent.type_name_at_level(level).unwrap_or("<none>"),
+                                result,
+                                mirror,
+                            );
+                        }
+                    }
+                })
+                .collect()
+        };
+        let dispatch = |args: &[TokenStream2], level: TokenStream2| -> Vec<TokenStream2> {
+            level_methods
+                .iter()
+                .zip(args)
+                .map(|(level_method, args)| quote! { self.parent.#level_method(#args #level) })
+                .collect()
+        };
+        // With dyn_in_debug, the level methods of every level impl are only
+        // compiled into release builds. Debug builds get a single provided
+        // method on the level trait instead, which looks the object up as a
+        // trait object and calls it through its vtable, so the per node
+        // copies of each level method (and the static dispatch inside them)
+        // are never generated.
+        let static_level_method = if self.dyn_in_debug {
+            quote! { #[cfg(not(debug_assertions))] }
+        } else {
+            quote! {}
+        };
+        let level_method_decls: Vec<TokenStream2> = level_methods
+            .iter()
+            .zip(&level_method_inputs)
+            .zip(&level_method_outputs)
+            .zip(trait_method_idents.iter().zip(&trait_method_args))
+            .map(|(((level_method, inputs), outputs), (method, args))| {
+                let decl = quote! {
+                    #static_level_method
+                    fn #level_method(#inputs level: usize) -> #outputs;
+                };
+                if !self.dyn_in_debug {
+                    return decl;
+                }
+                quote! {
+                    #decl
 
-            #(
-                struct #composite_iters #composite_lifetime_generics
-                #composite_where_clause
-                {
-                    level: usize,
-                    #trait_method_inputs,
-                    parent: &'zero_v #zv_node_type,
-                    #composite_phantom_fields
+                    #[cfg(debug_assertions)]
+                    fn #level_method(#inputs level: usize) -> #outputs {
+                        #max_nodes_check
+                        self.dyn_at_level(level).map(|zero_v_inner| zero_v_inner.#method(#args))
+                    }
                 }
+            })
+            .collect();
+        let iter_dispatch = dispatch(&trait_method_self_args, quote! { self.level });
+        let ref_iter_dispatch = dispatch(&ref_iter_self_args, quote! { self.level });
+        let priority_dispatch = dispatch(&trait_method_self_args, quote! { level });
+        let iter_shadow_checks = shadow_checks(&trait_method_self_args, quote! { self.level });
+        let ref_iter_shadow_checks = shadow_checks(&ref_iter_self_args, quote! { self.level });
+        let priority_shadow_checks = shadow_checks(&trait_method_self_args, quote! { level });
 
-                impl #composite_impl_generics
-                     #composite_iters #composite_lifetime_ty_generics
-                #composite_where_clause
+        // A composite can itself be held in a node, in which case its objects
+        // are spliced into the levels of the outer composite. This impl sits
+        // alongside the blanket impl for nodes holding objects implementing
+        // the trait, which is only allowed if no other crate could implement
+        // the trait for Composite. That rules out traits with type
+        // parameters, since a downstream crate could implement those for
+        // Composite with one of its own types as a parameter.
+        let nested_level_impl = if type_params(trait_generics).is_empty() {
+            quote! {
+                #[automatically_derived]
+                #allow_lints
+                impl #nested_level_generics #level_trait #ty_generics
+                    for zero_v::Node<zero_v::Composite<#zv_inner_type>, #zv_node_type>
+                #nested_level_where
                 {
-                    fn new(parent: &'zero_v #zv_node_type, #trait_method_inputs) -> Self {
+                    #level_count_nested
+
+                    #[inline]
+                    fn level_count(&self) -> usize {
+                        #max_nodes_check
+                        self.data().level_count() + self.next().level_count()
+                    }
+
+                    fn type_name_at_level(&self, level: usize) -> Option<&'static str> {
+                        let inner_count = self.data().level_count();
+                        if level < inner_count {
+                            self.data().type_name_at_level(level)
+                        } else {
+                            self.next().type_name_at_level(level - inner_count)
+                        }
+                    }
+
+                    #shadow_nested_impls
+                    #dyn_nodes_nested
+
+                    fn audit_levels(
+                        &self,
+                        origin: *const u8,
+                        level: usize,
+                        depth: usize,
+                        nodes: &mut zero_v::__private::Vec<zero_v::NodeAudit>
+                    ) {
+                        self.data().audit_levels(origin, level, depth + 1, nodes);
+                        let level = level + self.data().level_count();
+                        self.next().audit_levels(origin, level, depth, nodes);
+                    }
+
+                    #[inline]
+                    fn visit_nodes_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                        &mut self,
+                        mutator: &mut ZeroVMutator
+                    ) {
+                        #max_nodes_check
+                        self.data_mut().visit_nodes_mut(mutator);
+                        self.next_mut().visit_nodes_mut(mutator);
+                    }
+
+                    #(
+                        #static_level_method
+                        fn #level_methods(#level_method_inputs level: usize)
+                            -> #level_method_outputs
+                        {
+                            #max_nodes_check
+                            let inner_count = self.data().level_count();
+                            if level < inner_count {
+                                self.data().#level_methods(#trait_method_args level)
+                            } else {
+                                self.next().#level_methods(#trait_method_args level - inner_count)
+                            }
+                        }
+                    )*
+
+                    #(
+                        #[inline]
+                        fn #try_fold_level_methods #try_fold_generics(
+                            #level_method_inputs
+                            acc: ZeroVAcc,
+                            fold: &mut ZeroVFold
+                        ) -> Result<ZeroVAcc, ZeroVError> {
+                            #max_nodes_check
+                            let acc = self.data().#try_fold_level_methods(#trait_method_args acc, fold)?;
+                            self.next().#try_fold_level_methods(#trait_method_args acc, fold)
+                        }
+                    )*
+
+                    #(
+                        fn #for_each_level_methods #lending_generics(
+                            #lending_inputs
+                            level: usize,
+                            visitor: &mut ZeroVVisitor
+                        ) #lending_where
+                        {
+                            #max_nodes_check
+                            self.data().#for_each_level_methods(#lending_args level, visitor);
+                            let level = level + self.data().level_count();
+                            self.next().#for_each_level_methods(#lending_args level, visitor);
+                        }
+                    )*
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        // A node can hold an object which might not be there, in which case
+        // the node still takes up a level, but is skipped over by the
+        // iterators (and everything else which runs the objects) while it's
+        // empty. Like the nested impl, this relies on no other crate being
+        // able to implement the trait for the wrapper type.
+        let optional_level_impl = |impl_generics: TokenStream2,
+                                   wrapper: TokenStream2,
+                                   get: TokenStream2,
+                                   get_mut: TokenStream2| {
+            let shadow_optional_impls = if dyn_lookup {
+                quote! {
+                    fn dyn_at_level(&self, level: usize) -> Option<&(#dyn_trait)> {
+                        if level != 0 {
+                            self.next().dyn_at_level(level - 1)
+                        } else {
+                            match #get {
+                                Some(zero_v_inner) => Some(zero_v_inner),
+                                None => None,
+                            }
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            let dyn_nodes_optional = dyn_nodes(quote! {
+                if let Some(zero_v_inner) = #get {
+                    nodes.push(zero_v_inner);
+                }
+                self.next().dyn_nodes(nodes);
+            });
+
+            quote! {
+                #[automatically_derived]
+                #allow_lints
+                impl #impl_generics #level_trait #ty_generics
+                    for zero_v::Node<#wrapper, #zv_node_type>
+                #level_where_clause
+                {
+                    #level_count_node
+
+                    #[inline]
+                    fn level_count(&self) -> usize {
+                        #max_nodes_check
+                        self.next().level_count() + 1
+                    }
+
+                    fn type_name_at_level(&self, level: usize) -> Option<&'static str> {
+                        if level != 0 {
+                            self.next().type_name_at_level(level - 1)
+                        } else {
+                            #get.map(|_| ::core::any::type_name::<#zv_trait_type>())
+                        }
+                    }
+
+                    #shadow_optional_impls
+                    #dyn_nodes_optional
+
+                    fn audit_levels(
+                        &self,
+                        origin: *const u8,
+                        level: usize,
+                        depth: usize,
+                        nodes: &mut zero_v::__private::Vec<zero_v::NodeAudit>
+                    ) {
+                        nodes.push(zero_v::NodeAudit::of(self.data(), origin, level, depth));
+                        self.next().audit_levels(origin, level + 1, depth, nodes);
+                    }
+
+                    #[inline]
+                    fn visit_nodes_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                        &mut self,
+                        mutator: &mut ZeroVMutator
+                    ) {
+                        #max_nodes_check
+                        if let Some(zero_v_inner) = #get_mut {
+                            mutator.visit(zero_v_inner);
+                        }
+                        self.next_mut().visit_nodes_mut(mutator);
+                    }
+
+                    #(
+                        #static_level_method
+                        fn #level_methods(#level_method_inputs level: usize)
+                            -> #level_method_outputs
+                        {
+                            #max_nodes_check
+                            if level != 0 {
+                                self.next().#level_methods(#trait_method_args level - 1)
+                            } else {
+                                #get.map(|zero_v_inner| {
+                                    zero_v_inner.#trait_method_idents(#trait_method_args)
+                                })
+                            }
+                        }
+                    )*
+
+                    #(
+                        #[inline]
+                        fn #try_fold_level_methods #try_fold_generics(
+                            #level_method_inputs
+                            acc: ZeroVAcc,
+                            fold: &mut ZeroVFold
+                        ) -> Result<ZeroVAcc, ZeroVError> {
+                            #max_nodes_check
+                            let acc = match #get {
+                                Some(zero_v_inner) => {
+                                    fold(acc, zero_v_inner.#trait_method_idents(#trait_method_args))?
+                                }
+                                None => acc,
+                            };
+                            self.next().#try_fold_level_methods(#trait_method_args acc, fold)
+                        }
+                    )*
+
+                    #(
+                        fn #for_each_level_methods #lending_generics(
+                            #lending_inputs
+                            level: usize,
+                            visitor: &mut ZeroVVisitor
+                        ) #lending_where
+                        {
+                            #max_nodes_check
+                            if let Some(zero_v_inner) = #get {
+                                visitor.visit::<#zv_trait_type>(
+                                    level,
+                                    zero_v_inner.#lending_idents(#lending_args)
+                                );
+                            }
+                            self.next().#for_each_level_methods(#lending_args level + 1, visitor);
+                        }
+                    )*
+                }
+            }
+        };
+        // These overlap with any impl of the trait for an Option or Toggle
+        // itself, so they're opt in.
+        let optional_level_impls = if self.optional && type_params(trait_generics).is_empty() {
+            let option_impl = optional_level_impl(
+                quote! { #level_impl_generics },
+                quote! { Option<#zv_trait_type> },
+                quote! { self.data().as_ref() },
+                quote! { self.data_mut().as_mut() },
+            );
+            let toggle_impl = optional_level_impl(
+                quote! { #level_impl_generics },
+                quote! { zero_v::Toggle<#zv_trait_type> },
+                quote! { self.data().get() },
+                quote! { self.data_mut().get_mut() },
+            );
+            quote! {
+                #option_impl
+                #toggle_impl
+            }
+        } else {
+            quote! {}
+        };
+        // Named objects are always there, but share the optional impl so
+        // they don't need one of their own. Named only exists to be composed,
+        // so there's no impl of the trait for it to overlap with and the
+        // objects are forwarded to without any option. Like nesting, this
+        // needs a trait without type parameters.
+        let named_level_impl = if type_params(trait_generics).is_empty() {
+            let mut named_generics = level_generics.clone();
+            named_generics.params.push(parse_quote! { ZeroVKey });
+            let (named_impl_generics, _, _) = named_generics.split_for_impl();
+            optional_level_impl(
+                quote! { #named_impl_generics },
+                quote! { zero_v::Named<ZeroVKey, #zv_trait_type> },
+                quote! { Some(self.data().inner()) },
+                quote! { Some(self.data_mut().inner_mut()) },
+            )
+        } else {
+            quote! {}
+        };
+
+        // An array of objects in a node counts as one level per object, so a
+        // block of identical objects doesn't need a node (and a nested type)
+        // for each one. A Repeat works the same way, with one object standing
+        // in for every level of the block. `get` looks up the object at
+        // zero_v_index within the block. As with the optional impls, these
+        // overlap with any impl of the trait for the wrapper type itself, so
+        // they're opt in.
+        let mut block_generics = level_generics.clone();
+        block_generics
+            .params
+            .push(parse_quote! { const ZERO_V_LEN: usize });
+        let (block_impl_generics, _, block_where) = block_generics.split_for_impl();
+        let block_level_impl = |wrapper: TokenStream2,
+                                get: TokenStream2,
+                                audit: TokenStream2,
+                                visit: TokenStream2| {
+            let level_count_block = quote! { const LEVEL_COUNT: usize = #node_count + ZERO_V_LEN; };
+            let shadow_block_impls = if dyn_lookup {
+                quote! {
+                    fn dyn_at_level(&self, level: usize) -> Option<&(#dyn_trait)> {
+                        if level < ZERO_V_LEN {
+                            let zero_v_index = level;
+                            Some(#get)
+                        } else {
+                            self.next().dyn_at_level(level - ZERO_V_LEN)
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            let dyn_nodes_block = dyn_nodes(quote! {
+                nodes.extend((0..ZERO_V_LEN).filter_map(|level| self.dyn_at_level(level)));
+                self.next().dyn_nodes(nodes);
+            });
+
+            quote! {
+                #[automatically_derived]
+                #allow_lints
+                impl #block_impl_generics #level_trait #ty_generics
+                    for zero_v::Node<#wrapper, #zv_node_type>
+                #block_where
+                {
+                    #level_count_block
+
+                    #[inline]
+                    fn level_count(&self) -> usize {
+                        #max_nodes_check
+                        self.next().level_count() + ZERO_V_LEN
+                    }
+
+                    fn type_name_at_level(&self, level: usize) -> Option<&'static str> {
+                        if level < ZERO_V_LEN {
+                            Some(::core::any::type_name::<#zv_trait_type>())
+                        } else {
+                            self.next().type_name_at_level(level - ZERO_V_LEN)
+                        }
+                    }
+
+                    #shadow_block_impls
+                    #dyn_nodes_block
+
+                    fn audit_levels(
+                        &self,
+                        origin: *const u8,
+                        level: usize,
+                        depth: usize,
+                        nodes: &mut zero_v::__private::Vec<zero_v::NodeAudit>
+                    ) {
+                        #audit
+                        self.next().audit_levels(origin, level + ZERO_V_LEN, depth, nodes);
+                    }
+
+                    #[inline]
+                    fn visit_nodes_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                        &mut self,
+                        mutator: &mut ZeroVMutator
+                    ) {
+                        #max_nodes_check
+                        #visit
+                        self.next_mut().visit_nodes_mut(mutator);
+                    }
+
+                    #(
+                        #[inline]
+                        #static_level_method
+                        fn #level_methods(#level_method_inputs level: usize)
+                            -> #level_method_outputs
+                        {
+                            #max_nodes_check
+                            if level < ZERO_V_LEN {
+                                let zero_v_index = level;
+                                Some((#get).#trait_method_idents(#trait_method_args))
+                            } else {
+                                self.next().#level_methods(#trait_method_args level - ZERO_V_LEN)
+                            }
+                        }
+                    )*
+
+                    #(
+                        #[inline]
+                        fn #try_fold_level_methods #try_fold_generics(
+                            #level_method_inputs
+                            acc: ZeroVAcc,
+                            fold: &mut ZeroVFold
+                        ) -> Result<ZeroVAcc, ZeroVError> {
+                            #max_nodes_check
+                            let mut acc = acc;
+                            for zero_v_index in 0..ZERO_V_LEN {
+                                acc = fold(acc, (#get).#trait_method_idents(#trait_method_args))?;
+                            }
+                            self.next().#try_fold_level_methods(#trait_method_args acc, fold)
+                        }
+                    )*
+
+                    #(
+                        fn #for_each_level_methods #lending_generics(
+                            #lending_inputs
+                            level: usize,
+                            visitor: &mut ZeroVVisitor
+                        ) #lending_where
+                        {
+                            #max_nodes_check
+                            for zero_v_index in 0..ZERO_V_LEN {
+                                visitor.visit::<#zv_trait_type>(
+                                    level + zero_v_index,
+                                    (#get).#lending_idents(#lending_args)
+                                );
+                            }
+                            self.next().#for_each_level_methods(
+                                #lending_args
+                                level + ZERO_V_LEN,
+                                visitor
+                            );
+                        }
+                    )*
+                }
+            }
+        };
+        let block_level_impls = if self.blocks && type_params(trait_generics).is_empty() {
+            let array_impl = block_level_impl(
+                quote! { [#zv_trait_type; ZERO_V_LEN] },
+                quote! { &self.data()[zero_v_index] },
+                quote! {
+                    for (index, zero_v_inner) in self.data().iter().enumerate() {
+                        nodes.push(zero_v::NodeAudit::of(zero_v_inner, origin, level + index, depth));
+                    }
+                },
+                quote! {
+                    for zero_v_inner in self.data_mut().iter_mut() {
+                        mutator.visit(zero_v_inner);
+                    }
+                },
+            );
+            let repeat_impl = block_level_impl(
+                quote! { zero_v::Repeat<#zv_trait_type, ZERO_V_LEN> },
+                quote! { self.data().inner() },
+                quote! {
+                    nodes.push(zero_v::NodeAudit::of(self.data().inner(), origin, level, depth));
+                },
+                quote! {
+                    mutator.visit(self.data_mut().inner_mut());
+                },
+            );
+            quote! {
+                #array_impl
+                #repeat_impl
+            }
+        } else {
+            quote! {}
+        };
+
+        // With the dyn_tail option, a composite can end in a DynTail of trait
+        // objects in place of the unit type, whose objects take the levels
+        // after the static ones. The tail's objects can't be handed to the
+        // mutators or visitors, which are generic over sized object types,
+        // so those skip them.
+        let dyn_tail_level_impl = if self.dyn_tail {
+            let mut dyn_tail_generics = trait_generics.clone();
+            dyn_tail_generics
+                .params
+                .insert(0, parse_quote! { 'zero_v_dyn });
+            let (dyn_tail_impl_generics, _, dyn_tail_where) = dyn_tail_generics.split_for_impl();
+            let dyn_tail_trait = quote! { dyn #trait_ident #ty_generics + 'zero_v_dyn };
+            let shadow_dyn_tail_impls = if dyn_lookup {
+                quote! {
+                    fn dyn_at_level(&self, level: usize) -> Option<&(#dyn_trait)> {
+                        self.get(level)
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            let dyn_nodes_dyn_tail = dyn_nodes(quote! {
+                for zero_v_inner in self.iter() {
+                    nodes.push(zero_v_inner);
+                }
+            });
+
+            quote! {
+                #[automatically_derived]
+                #allow_lints
+                impl #dyn_tail_impl_generics #level_trait #ty_generics
+                    for zero_v::DynTail<#dyn_tail_trait>
+                #dyn_tail_where
+                {
+                    const LEVEL_COUNT: usize = 0;
+
+                    #[inline]
+                    fn level_count(&self) -> usize {
+                        self.len()
+                    }
+
+                    fn type_name_at_level(&self, level: usize) -> Option<&'static str> {
+                        self.get(level).map(|_| ::core::any::type_name::<#dyn_tail_trait>())
+                    }
+
+                    #shadow_dyn_tail_impls
+                    #dyn_nodes_dyn_tail
+
+                    fn audit_levels(
+                        &self,
+                        origin: *const u8,
+                        level: usize,
+                        depth: usize,
+                        nodes: &mut zero_v::__private::Vec<zero_v::NodeAudit>
+                    ) {
+                        nodes.push(zero_v::NodeAudit::of(self, origin, level, depth));
+                    }
+
+                    fn visit_nodes_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                        &mut self,
+                        _mutator: &mut ZeroVMutator
+                    ) {
+                    }
+
+                    #(
+                        #[inline]
+                        #static_level_method
+                        fn #level_methods(#level_method_inputs level: usize)
+                            -> #level_method_outputs
+                        {
+                            self.get(level).map(|zero_v_inner| {
+                                zero_v_inner.#trait_method_idents(#trait_method_args)
+                            })
+                        }
+                    )*
+
+                    #(
+                        #[inline]
+                        fn #try_fold_level_methods #try_fold_generics(
+                            #level_method_inputs
+                            acc: ZeroVAcc,
+                            fold: &mut ZeroVFold
+                        ) -> Result<ZeroVAcc, ZeroVError> {
+                            let mut acc = acc;
+                            for zero_v_inner in self.iter() {
+                                acc = fold(acc, zero_v_inner.#trait_method_idents(#trait_method_args))?;
+                            }
+                            Ok(acc)
+                        }
+                    )*
+
+                    #(
+                        #[allow(unused)]
+                        fn #for_each_level_methods #lending_generics(
+                            #lending_inputs
+                            level: usize,
+                            visitor: &mut ZeroVVisitor
+                        ) #lending_where
+                        {
+                        }
+                    )*
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let iter_trait = idents.iter_trait();
+        let mut iter_generics = trait_generics.clone();
+        iter_generics.params.push(zv_node_type.clone());
+        iter_generics
+            .make_where_clause()
+            .predicates
+            .push(zv_node_type_pred.clone());
+
+        let (iter_impl_generics, iter_ty_generics, iter_where_clause) =
+            iter_generics.split_for_impl();
+
+        // The level of an object found by type, counting every object in a
+        // nested composite (or block) above it as a level of its own. The
+        // index type picks out the node holding the object, like
+        // zero_v::Select, and each node above it adds the difference between
+        // its level count and the level count of the rest of the chain.
+        let level_of_trait = idents.level_of_trait();
+        let trait_args = trait_generics
+            .params
+            .iter()
+            .map(|param| match param {
+                GenericParam::Type(t) => {
+                    let ident = &t.ident;
+                    quote! { #ident }
+                }
+                GenericParam::Lifetime(l) => {
+                    let lifetime = &l.lifetime;
+                    quote! { #lifetime }
+                }
+                GenericParam::Const(c) => {
+                    let ident = &c.ident;
+                    quote! { #ident }
+                }
+            })
+            .collect::<Vec<_>>();
+        let level_of_bound = |index: TokenStream2| {
+            quote! { #level_of_trait<#(#trait_args,)* ZeroVTarget, #index> }
+        };
+        let level_of_here = level_of_bound(quote! { zero_v::Here });
+        let level_of_there = level_of_bound(quote! { zero_v::There<ZeroVIndex> });
+        let level_of_index = level_of_bound(quote! { ZeroVIndex });
+        let with_level_of_params = |params: Vec<GenericParam>, predicates: Vec<WherePredicate>| {
+            let mut generics = trait_generics.clone();
+            generics.params.extend(params);
+            generics.make_where_clause().predicates.extend(predicates);
+            generics
+        };
+        let level_of_decl_generics = with_level_of_params(
+            vec![parse_quote! { ZeroVTarget }, parse_quote! { ZeroVIndex }],
+            vec![],
+        );
+        let level_of_here_generics = with_level_of_params(
+            vec![parse_quote! { ZeroVTarget }, zv_node_type.clone()],
+            vec![parse_quote! { NodeType: zero_v::NextNode }],
+        );
+        let (level_of_here_generics, _, level_of_here_where) =
+            level_of_here_generics.split_for_impl();
+        let level_of_there_generics = with_level_of_params(
+            vec![
+                parse_quote! { ZeroVTarget },
+                parse_quote! { ZeroVIndex },
+                zv_trait_type.clone(),
+                zv_node_type.clone(),
+            ],
+            vec![
+                parse_quote! {
+                    zero_v::Node<#zv_trait_type, #zv_node_type>: #level_trait #ty_generics
+                },
+                parse_quote! {
+                    NodeType: zero_v::NextNode + #level_trait #ty_generics + #level_of_index
+                },
+            ],
+        );
+        let (level_of_there_generics, _, level_of_there_where) =
+            level_of_there_generics.split_for_impl();
+        let level_of_composite_generics = with_level_of_params(
+            vec![
+                parse_quote! { ZeroVTarget },
+                parse_quote! { ZeroVIndex },
+                zv_node_type.clone(),
+            ],
+            vec![parse_quote! { NodeType: zero_v::NextNode + #level_of_index }],
+        );
+        let (level_of_composite_generics, _, level_of_composite_where) =
+            level_of_composite_generics.split_for_impl();
+        // Stepping to the next level is checked against the same count, so
+        // it can't step past the composite's last object (or into a dyn
+        // tail, whose objects aren't counted).
+        let level_next_trait = idents.level_next_trait();
+        let mut level_next_generics = trait_generics.clone();
+        level_next_generics.params.push(zv_node_type.clone());
+        level_next_generics
+            .make_where_clause()
+            .predicates
+            .push(zv_node_type_pred.clone());
+        let (level_next_generics, _, level_next_where) = level_next_generics.split_for_impl();
+        let level_of_items = quote! {
+            #allow_lints
+            trait #level_next_trait #trait_generics #where_clause {
+                // The level after this one, or None if this is the last one.
+                fn next(&self) -> Option<Self> where Self: Sized;
+            }
+
+            #[automatically_derived]
+            #allow_lints
+            impl #level_next_generics #level_next_trait #ty_generics
+                for zero_v::Level<zero_v::Composite<#zv_node_type>>
+            #level_next_where
+            {
+                fn next(&self) -> Option<Self> {
+                    let value = self.value() + 1;
+                    if value < <#zv_node_type as #level_trait #ty_generics>::LEVEL_COUNT {
+                        Some(zero_v::__private::level(value))
+                    } else {
+                        None
+                    }
+                }
+            }
+
+
+            #allow_lints
+            trait #level_of_trait #level_of_decl_generics #where_clause {
+                // The level of the object of type ZeroVTarget below this point.
+                const LEVEL: usize;
+            }
+
+            #[automatically_derived]
+            #allow_lints
+            impl #level_of_here_generics #level_of_here
+                for zero_v::Node<ZeroVTarget, #zv_node_type>
+            #level_of_here_where
+            {
+                const LEVEL: usize = 0;
+            }
+
+            #[automatically_derived]
+            #allow_lints
+            impl #level_of_there_generics #level_of_there
+                for zero_v::Node<#zv_trait_type, #zv_node_type>
+            #level_of_there_where
+            {
+                const LEVEL: usize =
+                    <zero_v::Node<#zv_trait_type, #zv_node_type> as #level_trait #ty_generics>::LEVEL_COUNT
+                        - <#zv_node_type as #level_trait #ty_generics>::LEVEL_COUNT
+                        + <#zv_node_type as #level_of_index>::LEVEL;
+            }
+
+            #[automatically_derived]
+            #allow_lints
+            impl #level_of_composite_generics #level_of_index
+                for zero_v::Composite<#zv_node_type>
+            #level_of_composite_where
+            {
+                const LEVEL: usize = <#zv_node_type as #level_of_index>::LEVEL;
+            }
+        };
+        let iter_methods: Vec<Ident> = idents.iter_methods().collect();
+        let range_iter_methods: Vec<Ident> = idents.range_iter_methods().collect();
+        let step_iter_methods: Vec<Ident> = idents.step_iter_methods().collect();
+        let ref_iter_methods: Vec<Ident> = idents.ref_iter_methods().collect();
+        let composite_ref_iters: Vec<Ident> = idents.composite_ref_iters().collect();
+        let bind_methods: Vec<Ident> = idents.bind_methods().collect();
+        let bound_composites: Vec<Ident> = idents.bound_composites().collect();
+        let const_level_methods: Vec<Ident> = idents.const_level_methods().collect();
+        let typed_level_methods: Vec<Ident> = idents.typed_level_methods().collect();
+
+        let composite_iters: Vec<Ident> = idents.composite_iters().collect();
+        let mut composite_generics = trait_generics.clone();
+        let mut composite_lifetime_generics = composite_generics.clone();
+        composite_generics
+            .params
+            .extend(vec![parse_quote! { '_ }, zv_node_type.clone()]);
+
+        composite_lifetime_generics
+            .params
+            .extend(vec![parse_quote! { 'zero_v }, zv_node_type.clone()]);
+
+        composite_lifetime_generics
+            .make_where_clause()
+            .predicates
+            .push(zv_node_type_pred.clone());
+        let (_, composite_ty_generics, _) = composite_generics.split_for_impl();
+
+        let (composite_impl_generics, composite_lifetime_ty_generics, composite_where_clause) =
+            composite_lifetime_generics.split_for_impl();
+
+        let mut bound_ref_generics = composite_lifetime_generics.clone();
+        bound_ref_generics
+            .params
+            .push(parse_quote! { 'zero_v_bound });
+        let (bound_ref_impl_generics, _, _) = bound_ref_generics.split_for_impl();
+
+        // Methods returning an Option get an extra iteration variant which
+        // skips the Nones.
+        let (flatten_iter_decls, flatten_iter_impls): (Vec<TokenStream2>, Vec<TokenStream2>) =
+            idents
+                .flatten_iter_methods()
+                .zip(&iter_methods)
+                .zip(&composite_iters)
+                .enumerate()
+                .map(|(i, ((flatten_method, iter_method), composite_iter))| {
+                    if !is_option(&trait_method_outputs[i]) {
+                        return (quote! {}, quote! {});
+                    }
+
+                    let inputs = &trait_method_inputs[i];
+                    let args = &trait_method_args[i];
+                    let signature = quote! {
+                        fn #flatten_method(&self, #inputs)
+                            -> ::core::iter::Flatten<#composite_iter #composite_ty_generics>
+                    };
+                    (
+                        quote! { #signature; },
+                        quote! { #signature { self.#iter_method(#args).flatten() } },
+                    )
+                })
+                .unzip();
+
+        // With the stats feature, methods returning a primitive number get a
+        // method summarizing their outputs.
+        let (stats_decls, stats_impls): (Vec<TokenStream2>, Vec<TokenStream2>) = idents
+            .stats_methods()
+            .zip(&iter_methods)
+            .enumerate()
+            .map(|(i, (stats_method, iter_method))| {
+                let output = &trait_method_outputs[i];
+                if !cfg!(feature = "stats") || !is_primitive_number(output) {
+                    return (quote! {}, quote! {});
+                }
+
+                let inputs = &trait_method_inputs[i];
+                let args = &trait_method_args[i];
+                let signature = quote! {
+                    fn #stats_method(&self, #inputs) -> zero_v::stats::Stats<#output>
+                };
+                (
+                    quote! { #signature; },
+                    quote! { #signature { self.#iter_method(#args).collect() } },
+                )
+            })
+            .unzip();
+
+        // With the args option, each method gets a struct holding its
+        // arguments, generic over just those of the trait's parameters its
+        // arguments mention, and an iter_{method}_with method taking it.
+        let (args_struct_items, (with_iter_decls, with_iter_impls)): (
+            Vec<TokenStream2>,
+            (Vec<TokenStream2>, Vec<TokenStream2>),
+        ) = idents
+            .args_structs()
+            .zip(idents.with_iter_methods())
+            .zip(&iter_methods)
+            .zip(&composite_iters)
+            .enumerate()
+            .map(|(i, (((args_struct, with_method), iter_method), composite_iter))| {
+                if !self.args {
+                    return (quote! {}, (quote! {}, quote! {}));
+                }
+
+                let fields = &trait_method_inputs[i];
+                let names = trait_method_args[i].iter().collect::<Vec<_>>();
+                let types = fields
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        FnArg::Typed(PatType { ty, .. }) => Some(ty),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+
+                let struct_generics = Generics {
+                    params: trait_generics
+                        .params
+                        .iter()
+                        .filter(|param| mentions(quote! { #fields }, param))
+                        .cloned()
+                        .map(without_bounds)
+                        .collect(),
+                    ..Default::default()
+                };
+                let (struct_impl_generics, struct_ty_generics, _) =
+                    struct_generics.split_for_impl();
+
+                // The Default impl only applies when every argument type
+                // implements Default. The bounds are wrapped in for<...> so
+                // that a concrete argument type without a Default impl only
+                // makes the impl unusable, rather than being an error.
+                let item = quote! {
+                    #allow_lints
+                    #[derive(Clone, Copy)]
+                    struct #args_struct #struct_generics {
+                        #fields
+                    }
+
+                    #[automatically_derived]
+                    #allow_lints
+                    impl #struct_impl_generics Default for #args_struct #struct_ty_generics
+                    where
+                        #(for<'zero_v_default> #types: Default,)*
+                    {
+                        fn default() -> Self {
+                            Self {
+                                #(#names: Default::default(),)*
+                            }
+                        }
+                    }
+                };
+                let signature = quote! {
+                    fn #with_method(&self, args: #args_struct #struct_ty_generics)
+                        -> #composite_iter #composite_ty_generics
+                };
+                (
+                    item,
+                    (
+                        quote! { #signature; },
+                        quote! { #signature { self.#iter_method(#(args.#names,)*) } },
+                    ),
+                )
+            })
+            .unzip();
+
+        let chain_methods: Vec<Ident> = idents.chain_methods().collect();
+        let chain_params: Vec<GenericParam> =
+            vec![parse_quote! { 'zero_v }, parse_quote! { OtherNodes }];
+        let mut chain_other_generics = trait_generics.clone();
+        chain_other_generics.params.extend(chain_params);
+        let (_, chain_other_ty_generics, _) = chain_other_generics.split_for_impl();
+
+        // The by_priority iterators walk the order of the levels worked out
+        // from each node's const priority, so they're only defined for
+        // composites whose objects all have one.
+        let priority_iter_methods: Vec<Ident> = idents.priority_iter_methods().collect();
+        let composite_priority_iters: Vec<Ident> = idents.composite_priority_iters().collect();
+        let mut priority_lifetime_generics = composite_lifetime_generics.clone();
+        priority_lifetime_generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote! { NodeType: zero_v::Priorities });
+        let (priority_impl_generics, priority_lifetime_ty_generics, priority_where_clause) =
+            priority_lifetime_generics.split_for_impl();
+
+        // The iterators need to mention every parameter of the trait, even
+        // those which only show up in outputs or bounds, so each one gets a
+        // PhantomData field.
+        let composite_phantom_types = trait_generics
+            .params
+            .iter()
+            .filter_map(|p| match p {
+                GenericParam::Type(t) => {
+                    let ident = &t.ident;
+                    Some(quote! { #ident })
+                }
+                GenericParam::Lifetime(l) => {
+                    let lifetime = &l.lifetime;
+                    Some(quote! { &#lifetime () })
+                }
+                GenericParam::Const(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        let composite_phantom_names = composite_phantom_types
+            .iter()
+            .enumerate()
+            .map(|(i, _)| format_ident!("_phantom_{}", i))
+            .collect::<Vec<_>>();
+
+        let composite_phantom_fields = quote! {
+            #(
+                #composite_phantom_names: ::core::marker::PhantomData<#composite_phantom_types>,
+            )*
+        };
+        let composite_phantom_vals = quote! {
+            #(
+                #composite_phantom_names: ::core::marker::PhantomData,
+            )*
+        };
+
+        let mut_caller_impls = mut_methods()
+            .zip(&mut_callers)
+            .zip(&mut_method_inputs)
+            .zip(&mut_method_args)
+            .zip(&mut_method_outputs)
+            .map(|((((m, caller), inputs), args), output)| {
+                let method = &m.sig.ident;
+                let self_args = args.iter();
+                let mut caller_generics = trait_generics.clone();
+                caller_generics
+                    .params
+                    .push(parse_quote! { ZeroVF: FnMut(#output) });
+                let (caller_impl_generics, caller_ty_generics, caller_where) =
+                    caller_generics.split_for_impl();
+
+                quote! {
+                    #allow_lints
+                    struct #caller #caller_impl_generics #caller_where {
+                        #inputs
+                        f: ZeroVF,
+                        #composite_phantom_fields
+                    }
+
+                    #[automatically_derived]
+                    #allow_lints
+                    impl #caller_impl_generics #mutator_trait #ty_generics
+                        for #caller #caller_ty_generics
+                    #caller_where
+                    {
+                        fn visit<TraitType: #trait_ident #ty_generics>(
+                            &mut self,
+                            node: &mut TraitType
+                        ) {
+                            (self.f)(node.#method(#(self.#self_args),*));
+                        }
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let consume_items = if consuming_method_calls.is_empty() {
+            quote! {}
+        } else {
+            // `call` builds the code which runs a method on whatever the node
+            // held (zero_v_data) before the rest of the chain (zero_v_next) is
+            // consumed in turn. It's given the method call on zero_v_inner,
+            // and the call consuming a nested composite the same way.
+            let node_consume_impl = |impl_generics: TokenStream2,
+                                     wrapper: TokenStream2,
+                                     where_clause: TokenStream2,
+                                     call: &dyn Fn(&TokenStream2, &TokenStream2) -> TokenStream2| {
+                let calls = consuming_method_calls
+                    .iter()
+                    .zip(&each_into_methods)
+                    .zip(&consuming_method_args)
+                    .map(|((invoke, each_into), args)| {
+                        call(invoke, &quote! { #each_into(#args f) })
+                    });
+                quote! {
+                    #[automatically_derived]
+                    #allow_lints
+                    impl #impl_generics #consume_trait #ty_generics
+                        for zero_v::Node<#wrapper, #zv_node_type>
+                    #where_clause
+                    {
+                        #(
+                            #[inline]
+                            fn #each_into_methods<ZeroVF: FnMut(#consuming_method_outputs)>(
+                                self,
+                                #consuming_method_inputs
+                                f: &mut ZeroVF
+                            ) {
+                                let (zero_v_data, zero_v_next) = self.into_inner();
+                                #calls
+                                zero_v_next.#each_into_methods(#consuming_method_args f);
+                            }
+                        )*
+                    }
+                }
+            };
+
+            let zv_consume_node_pred: WherePredicate =
+                parse_quote! { NodeType: zero_v::NextNode + #consume_trait #ty_generics };
+            let mut consume_generics = trait_generics.clone();
+            consume_generics
+                .params
+                .extend(vec![zv_trait_type.clone(), zv_node_type.clone()]);
+            consume_generics
+                .make_where_clause()
+                .predicates
+                .extend(vec![zv_trait_type_pred.clone(), zv_consume_node_pred.clone()]);
+            let (consume_impl_generics, _, consume_where) = consume_generics.split_for_impl();
+
+            let node_impl = node_consume_impl(
+                quote! { #consume_impl_generics },
+                quote! { #zv_trait_type },
+                quote! { #consume_where },
+                &|invoke, _| quote! { let zero_v_inner = zero_v_data; #invoke },
+            );
+            // As with the level trait, the wrapper impls rely on no other
+            // crate being able to implement the trait for the wrapper types.
+            let wrapper_impls = if type_params(trait_generics).is_empty() {
+                let mut nested_generics = trait_generics.clone();
+                nested_generics
+                    .params
+                    .extend(vec![zv_inner_type.clone(), zv_node_type.clone()]);
+                nested_generics.make_where_clause().predicates.extend(vec![
+                    parse_quote! { InnerType: zero_v::NextNode + #consume_trait #ty_generics },
+                    zv_consume_node_pred.clone(),
+                ]);
+                let (nested_impl_generics, _, nested_where) = nested_generics.split_for_impl();
+                let nested_impl = node_consume_impl(
+                    quote! { #nested_impl_generics },
+                    quote! { zero_v::Composite<#zv_inner_type> },
+                    quote! { #nested_where },
+                    &|_, each_into| quote! { zero_v_data.into_head().#each_into; },
+                );
+                let option_impl = node_consume_impl(
+                    quote! { #consume_impl_generics },
+                    quote! { Option<#zv_trait_type> },
+                    quote! { #consume_where },
+                    &|invoke, _| quote! { if let Some(zero_v_inner) = zero_v_data { #invoke } },
+                );
+                let toggle_impl = node_consume_impl(
+                    quote! { #consume_impl_generics },
+                    quote! { zero_v::Toggle<#zv_trait_type> },
+                    quote! { #consume_where },
+                    &|invoke, _| {
+                        quote! {
+                            if zero_v_data.is_enabled() {
+                                let zero_v_inner = zero_v_data.into_inner();
+                                #invoke
+                            }
+                        }
+                    },
+                );
+                let mut named_generics = consume_generics.clone();
+                named_generics.params.push(parse_quote! { ZeroVKey });
+                let (named_impl_generics, _, _) = named_generics.split_for_impl();
+                let named_impl = node_consume_impl(
+                    quote! { #named_impl_generics },
+                    quote! { zero_v::Named<ZeroVKey, #zv_trait_type> },
+                    quote! { #consume_where },
+                    &|invoke, _| quote! { let zero_v_inner = zero_v_data.into_inner(); #invoke },
+                );
+                let optional_impls = if self.optional {
+                    quote! {
+                        #option_impl
+                        #toggle_impl
+                    }
+                } else {
+                    quote! {}
+                };
+                let mut array_generics = consume_generics.clone();
+                array_generics
+                    .params
+                    .push(parse_quote! { const ZERO_V_LEN: usize });
+                let (array_impl_generics, _, _) = array_generics.split_for_impl();
+                let array_impl = node_consume_impl(
+                    quote! { #array_impl_generics },
+                    quote! { [#zv_trait_type; ZERO_V_LEN] },
+                    quote! { #consume_where },
+                    &|invoke, _| {
+                        quote! {
+                            for zero_v_inner in ::core::iter::IntoIterator::into_iter(zero_v_data) {
+                                #invoke
+                            }
+                        }
+                    },
+                );
+                let block_impls = if self.blocks {
+                    quote! { #array_impl }
+                } else {
+                    quote! {}
+                };
+                quote! {
+                    #nested_impl
+                    #named_impl
+                    #optional_impls
+                    #block_impls
+                }
+            } else {
+                quote! {}
+            };
+
+            let mut composite_consume_generics = trait_generics.clone();
+            composite_consume_generics.params.push(zv_node_type.clone());
+            composite_consume_generics
+                .make_where_clause()
+                .predicates
+                .push(zv_consume_node_pred.clone());
+            let (composite_consume_generics, _, composite_consume_where) =
+                composite_consume_generics.split_for_impl();
+
+            let tuple_impls = (1..=self.tuples).map(|arity| {
+                let params = (0..arity)
+                    .map(|i| format_ident!("TraitType{}", i))
+                    .collect::<Vec<_>>();
+                let indices = (0..arity).map(syn::Index::from).collect::<Vec<_>>();
+                let mut tuple_generics = trait_generics.clone();
+                tuple_generics
+                    .params
+                    .extend(params.iter().map(|param| -> GenericParam { parse_quote! { #param } }));
+                tuple_generics
+                    .make_where_clause()
+                    .predicates
+                    .extend(params.iter().map(|param| -> WherePredicate {
+                        parse_quote! { #param: #trait_ident #ty_generics }
+                    }));
+                let (tuple_impl_generics, _, tuple_where) = tuple_generics.split_for_impl();
+                let calls = consuming_method_calls.iter().map(|invoke| {
+                    let calls = indices.iter().map(|index| {
+                        quote! {
+                            let zero_v_inner = self.#index;
+                            #invoke
+                        }
+                    });
+                    quote! { #({ #calls })* }
+                });
+
+                quote! {
+                    #[automatically_derived]
+                    #allow_lints
+                    impl #tuple_impl_generics #consume_trait #ty_generics
+                        for (#(#params,)*)
+                    #tuple_where
+                    {
+                        #(
+                            #[inline]
+                            fn #each_into_methods<ZeroVF: FnMut(#consuming_method_outputs)>(
+                                self,
+                                #consuming_method_inputs
+                                f: &mut ZeroVF
+                            ) {
+                                #calls
+                            }
+                        )*
+                    }
+                }
+            });
+
+            quote! {
+                #allow_lints
+                trait #consume_trait #trait_generics #where_clause {
+                    #(
+                        // Call the method on every object below this point,
+                        // handing each output to f.
+                        fn #each_into_methods<ZeroVF: FnMut(#consuming_method_outputs)>(
+                            self,
+                            #consuming_method_inputs
+                            f: &mut ZeroVF
+                        );
+
+                        fn #for_each_into_methods<ZeroVF: FnMut(#consuming_method_outputs)>(
+                            self,
+                            #consuming_method_inputs
+                            mut f: ZeroVF
+                        ) where Self: Sized {
+                            self.#each_into_methods(#consuming_method_args &mut f);
+                        }
+
+                        fn #into_iter_methods(self, #consuming_method_inputs)
+                            -> <zero_v::__private::Vec<#consuming_method_outputs>
+                                as ::core::iter::IntoIterator>::IntoIter
+                        where Self: Sized {
+                            let mut outputs = zero_v::__private::Vec::new();
+                            self.#each_into_methods(
+                                #consuming_method_args
+                                &mut |output| outputs.push(output)
+                            );
+                            outputs.into_iter()
+                        }
+                    )*
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #impl_generics #consume_trait #ty_generics for () #where_clause {
+                    #(
+                        #[allow(unused)]
+                        fn #each_into_methods<ZeroVF: FnMut(#consuming_method_outputs)>(
+                            self,
+                            #consuming_method_inputs
+                            f: &mut ZeroVF
+                        ) {
+                        }
+                    )*
+                }
+
+                #node_impl
+
+                #wrapper_impls
+
+                #[automatically_derived]
+                #allow_lints
+                impl #composite_consume_generics #consume_trait #ty_generics
+                    for zero_v::Composite<#zv_node_type>
+                #composite_consume_where
+                {
+                    #(
+                        #[inline]
+                        fn #each_into_methods<ZeroVF: FnMut(#consuming_method_outputs)>(
+                            self,
+                            #consuming_method_inputs
+                            f: &mut ZeroVF
+                        ) {
+                            self.into_head().#each_into_methods(#consuming_method_args f);
+                        }
+                    )*
+                }
+
+                #(#tuple_impls)*
+            }
+        };
+
+        let pinned_items = if pinned_method_calls.is_empty() {
+            quote! {}
+        } else {
+            // As for the consume trait, `call` builds the code which runs a
+            // method on the pinned projection of whatever the node held
+            // (zero_v_data), given the method call on zero_v_inner and the
+            // call walking a nested composite.
+            let node_pinned_impl = |impl_generics: TokenStream2,
+                                    wrapper: TokenStream2,
+                                    where_clause: TokenStream2,
+                                    call: &dyn Fn(&TokenStream2, &TokenStream2) -> TokenStream2| {
+                let calls = pinned_method_calls
+                    .iter()
+                    .zip(&each_pinned_methods)
+                    .zip(&pinned_method_args)
+                    .map(|((invoke, each_pinned), args)| {
+                        call(invoke, &quote! { #each_pinned(#args f) })
+                    });
+                quote! {
+                    #[automatically_derived]
+                    #allow_lints
+                    impl #impl_generics #pinned_trait #ty_generics
+                        for zero_v::Node<#wrapper, #zv_node_type>
+                    #where_clause
+                    {
+                        #(
+                            #[inline]
+                            fn #each_pinned_methods<ZeroVF: FnMut(#pinned_method_outputs)>(
+                                self: ::core::pin::Pin<&mut Self>,
+                                #pinned_method_inputs
+                                f: &mut ZeroVF
+                            ) {
+                                let zero_v_projection = self.project();
+                                let zero_v_data = zero_v_projection.data;
+                                #calls
+                                zero_v_projection.next.#each_pinned_methods(#pinned_method_args f);
+                            }
+                        )*
+                    }
+                }
+            };
+
+            let zv_pinned_node_pred: WherePredicate =
+                parse_quote! { NodeType: zero_v::NextNode + #pinned_trait #ty_generics };
+            let mut pinned_generics = trait_generics.clone();
+            pinned_generics
+                .params
+                .extend(vec![zv_trait_type.clone(), zv_node_type.clone()]);
+            pinned_generics
+                .make_where_clause()
+                .predicates
+                .extend(vec![zv_trait_type_pred.clone(), zv_pinned_node_pred.clone()]);
+            let (pinned_impl_generics, _, pinned_where) = pinned_generics.split_for_impl();
+
+            let node_impl = node_pinned_impl(
+                quote! { #pinned_impl_generics },
+                quote! { #zv_trait_type },
+                quote! { #pinned_where },
+                &|invoke, _| quote! { let zero_v_inner = zero_v_data; #invoke },
+            );
+            let wrapper_impls = if type_params(trait_generics).is_empty() {
+                let mut nested_generics = trait_generics.clone();
+                nested_generics
+                    .params
+                    .extend(vec![zv_inner_type.clone(), zv_node_type.clone()]);
+                nested_generics.make_where_clause().predicates.extend(vec![
+                    parse_quote! { InnerType: zero_v::NextNode + #pinned_trait #ty_generics },
+                    zv_pinned_node_pred.clone(),
+                ]);
+                let (nested_impl_generics, _, nested_where) = nested_generics.split_for_impl();
+                let nested_impl = node_pinned_impl(
+                    quote! { #nested_impl_generics },
+                    quote! { zero_v::Composite<#zv_inner_type> },
+                    quote! { #nested_where },
+                    &|_, each_pinned| quote! { zero_v_data.project().#each_pinned; },
+                );
+                let option_impl = node_pinned_impl(
+                    quote! { #pinned_impl_generics },
+                    quote! { Option<#zv_trait_type> },
+                    quote! { #pinned_where },
+                    &|invoke, _| {
+                        quote! { if let Some(zero_v_inner) = zero_v_data.as_pin_mut() { #invoke } }
+                    },
+                );
+                let optional_impls = if self.optional {
+                    quote! { #option_impl }
+                } else {
+                    quote! {}
+                };
+                quote! {
+                    #nested_impl
+                    #optional_impls
+                }
+            } else {
+                quote! {}
+            };
+
+            let mut composite_pinned_generics = trait_generics.clone();
+            composite_pinned_generics.params.push(zv_node_type.clone());
+            composite_pinned_generics
+                .make_where_clause()
+                .predicates
+                .push(zv_pinned_node_pred.clone());
+            let (composite_pinned_generics, _, composite_pinned_where) =
+                composite_pinned_generics.split_for_impl();
+
+            quote! {
+                #allow_lints
+                trait #pinned_trait #trait_generics #where_clause {
+                    #(
+                        // Call the method on every object below this point,
+                        // handing each output to f.
+                        fn #each_pinned_methods<ZeroVF: FnMut(#pinned_method_outputs)>(
+                            self: ::core::pin::Pin<&mut Self>,
+                            #pinned_method_inputs
+                            f: &mut ZeroVF
+                        );
+
+                        fn #for_each_pinned_methods<ZeroVF: FnMut(#pinned_method_outputs)>(
+                            self: ::core::pin::Pin<&mut Self>,
+                            #pinned_method_inputs
+                            mut f: ZeroVF
+                        ) {
+                            self.#each_pinned_methods(#pinned_method_args &mut f);
+                        }
+
+                        fn #pinned_iter_methods(
+                            self: ::core::pin::Pin<&mut Self>,
+                            #pinned_method_inputs
+                        ) -> <zero_v::__private::Vec<#pinned_method_outputs>
+                            as ::core::iter::IntoIterator>::IntoIter
+                        {
+                            let mut outputs = zero_v::__private::Vec::new();
+                            self.#each_pinned_methods(
+                                #pinned_method_args
+                                &mut |output| outputs.push(output)
+                            );
+                            outputs.into_iter()
+                        }
+                    )*
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #impl_generics #pinned_trait #ty_generics for () #where_clause {
+                    #(
+                        #[allow(unused)]
+                        fn #each_pinned_methods<ZeroVF: FnMut(#pinned_method_outputs)>(
+                            self: ::core::pin::Pin<&mut Self>,
+                            #pinned_method_inputs
+                            f: &mut ZeroVF
+                        ) {
+                        }
+                    )*
+                }
+
+                #node_impl
+
+                #wrapper_impls
+
+                #[automatically_derived]
+                #allow_lints
+                impl #composite_pinned_generics #pinned_trait #ty_generics
+                    for zero_v::Composite<#zv_node_type>
+                #composite_pinned_where
+                {
+                    #(
+                        #[inline]
+                        fn #each_pinned_methods<ZeroVF: FnMut(#pinned_method_outputs)>(
+                            self: ::core::pin::Pin<&mut Self>,
+                            #pinned_method_inputs
+                            f: &mut ZeroVF
+                        ) {
+                            self.project().#each_pinned_methods(#pinned_method_args f);
+                        }
+                    )*
+                }
+            }
+        };
+
+        // The iterator trait is implemented for composites and for plain
+        // tuples (see the tuple impls below), which hold their objects
+        // directly rather than under a head node.
+        let iter_impl = |impl_generics: TokenStream2,
+                         target: TokenStream2,
+                         where_clause: TokenStream2,
+                         head: TokenStream2,
+                         head_mut: TokenStream2| {
+            let to_dyn_vec_impl = if self.to_dyn_vec {
+                quote! {
+                    fn to_dyn_vec(&self) -> zero_v::__private::Vec<&(#dyn_trait)> {
+                        let mut nodes = zero_v::__private::Vec::new();
+                        #head.dyn_nodes(&mut nodes);
+                        nodes
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            quote! {
+                    #[automatically_derived]
+                    #allow_lints
+                    impl #impl_generics #iter_trait #iter_ty_generics for #target
+                    #where_clause
+                    {
+                        fn iter_levels(&self) -> ::core::iter::Map<
+                            ::core::ops::Range<usize>,
+                            fn(usize) -> zero_v::Level<Self>
+                        > {
+                            (0..#head.level_count())
+                                .map(zero_v::__private::level as fn(usize) -> zero_v::Level<Self>)
+                        }
+
+                        fn for_each_node_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                            &mut self,
+                            mutator: &mut ZeroVMutator
+                        ) {
+                            #head_mut.visit_nodes_mut(mutator);
+                        }
+
+                        fn audit(&self) -> zero_v::Audit {
+                            let mut nodes = zero_v::__private::Vec::new();
+                            let origin = self as *const Self as *const u8;
+                            #head.audit_levels(origin, 0, 0, &mut nodes);
+                            zero_v::Audit::new(self, nodes)
+                        }
+
+                        #to_dyn_vec_impl
+
+                        #(
+                            fn #for_each_mut_methods<ZeroVF: FnMut(#mut_method_outputs)>(
+                                &mut self,
+                                #mut_method_inputs
+                                f: ZeroVF
+                            ) {
+                                let mut caller = #mut_callers {
+                                    #mut_method_args
+                                    f,
+                                    #composite_phantom_vals
+                                };
+                                self.for_each_node_mut(&mut caller);
+                            }
+
+                            fn #iter_mut_methods(&mut self, #mut_method_inputs)
+                                -> <zero_v::__private::Vec<#mut_method_outputs>
+                                    as ::core::iter::IntoIterator>::IntoIter
+                            {
+                                let mut outputs = zero_v::__private::Vec::new();
+                                self.#for_each_mut_methods(
+                                    #mut_method_args
+                                    |output| outputs.push(output)
+                                );
+                                outputs.into_iter()
+                            }
+                        )*
+
+                        #(
+                            fn #iter_methods(#level_method_inputs)
+                                -> #composite_iters #composite_ty_generics
+                            {
+                                let levels = 0..#head.level_count();
+                                #composite_iters::new(#head, levels, 1, #trait_method_args)
+                            }
+
+                            fn #range_iter_methods<LevelRange: ::core::ops::RangeBounds<usize>>(
+                                &self,
+                                range: LevelRange,
+                                #trait_method_inputs
+                            ) -> #composite_iters #composite_ty_generics
+                            {
+                                let levels = zero_v::resolve_level_range(range, #head.level_count());
+                                #composite_iters::new(#head, levels, 1, #trait_method_args)
+                            }
+
+                            fn #chain_methods<
+                                'zero_v,
+                                OtherNodes: zero_v::NextNode + #level_trait #ty_generics
+                            >(
+                                &'zero_v self,
+                                other: &'zero_v zero_v::Composite<OtherNodes>,
+                                #trait_method_inputs
+                            ) -> ::core::iter::Chain<
+                                #composite_iters #composite_lifetime_ty_generics,
+                                #composite_iters #chain_other_ty_generics
+                            >
+                            {
+                                self.#iter_methods(#trait_method_args)
+                                    .chain(other.#iter_methods(#trait_method_args))
+                            }
+
+                            #flatten_iter_impls
+
+                            #with_iter_impls
+
+                            #stats_impls
+
+                            fn #step_iter_methods(
+                                &self,
+                                start: usize,
+                                step: usize,
+                                #trait_method_inputs
+                            ) -> #composite_iters #composite_ty_generics
+                            {
+                                assert!(step != 0, "level step must be greater than zero");
+                                // Like step_by, starting past the end just
+                                // yields nothing.
+                                let end = #head.level_count();
+                                #composite_iters::new(#head, start.min(end)..end, step, #trait_method_args)
+                            }
+
+                            fn #ref_iter_methods<'zero_v>(
+                                &'zero_v self,
+                                #ref_iter_inputs
+                            ) -> #composite_ref_iters #composite_lifetime_ty_generics
+                            {
+                                let end = #head.level_count();
+                                #composite_ref_iters::new(#head, end, #trait_method_args)
+                            }
+
+                            fn #bind_methods(&self, #trait_method_inputs)
+                                -> #bound_composites #composite_ty_generics
+                            {
+                                #bound_composites {
+                                    parent: #head,
+                                    #trait_method_args
+                                    #composite_phantom_vals
+                                }
+                            }
+
+                            #[inline]
+                            fn #try_fold_methods #try_fold_generics(
+                                &self,
+                                init: ZeroVAcc,
+                                #trait_method_inputs
+                                mut fold: ZeroVFold
+                            ) -> Result<ZeroVAcc, ZeroVError> {
+                                #head.#try_fold_level_methods(#trait_method_args init, &mut fold)
+                            }
+
+                            // Pushes each output as soon as its node has run, through
+                            // a fold which can't fail.
+                            #[inline]
+                            fn #extend_methods<ZeroVExtend: Extend<#trait_method_outputs>>(
+                                &self,
+                                out: &mut ZeroVExtend,
+                                #trait_method_inputs
+                            ) {
+                                let pushed = #head.#try_fold_level_methods(
+                                    #trait_method_args
+                                    (),
+                                    &mut |(), output| {
+                                        out.extend(Some(output));
+                                        Ok::<(), ::core::convert::Infallible>(())
+                                    }
+                                );
+                                match pushed {
+                                    Ok(()) => {}
+                                    Err(never) => match never {},
+                                }
+                            }
+
+                            fn #priority_iter_methods(&self, #trait_method_inputs)
+                                -> #composite_priority_iters #composite_ty_generics
+                            where
+                                #zv_node_type: zero_v::Priorities
+                            {
+                                #composite_priority_iters::new(#head, #trait_method_args)
+                            }
+
+                            #[inline]
+                            fn #const_level_methods<const LEVEL: usize>(&self, #trait_method_inputs)
+                                -> #trait_method_outputs
+                            where
+                                #zv_node_type: zero_v::At<LEVEL>,
+                                <#zv_node_type as zero_v::At<LEVEL>>::Output: #trait_ident #ty_generics
+                            {
+                                let () = <Self as #iter_trait #iter_ty_generics>::ONE_LEVEL_PER_NODE;
+                                zero_v::At::<LEVEL>::at(#head).#trait_method_idents(#trait_method_args)
+                            }
+
+                            #[inline]
+                            fn #typed_level_methods(
+                                &self,
+                                #trait_method_inputs
+                                level: zero_v::Level<Self>
+                            ) -> #level_method_outputs {
+                                #head.#level_methods(#trait_method_args level.value())
+                            }
+                        )*
+
+                        #(
+                            fn #for_each_methods #lending_generics(
+                                #lending_inputs
+                                visitor: &mut ZeroVVisitor
+                            ) #lending_where
+                            {
+                                #head.#for_each_level_methods(#lending_args 0, visitor)
+                            }
+                        )*
+                    }
+            }
+        };
+        let composite_iter_impl = iter_impl(
+            quote! { #iter_impl_generics },
+            quote! { zero_v::Composite<#zv_node_type> },
+            quote! { #iter_where_clause },
+            quote! { self.head() },
+            quote! { self.head_mut() },
+        );
+
+        // Plain tuples of objects implementing the trait get the level and
+        // iterator traits too, so simple collections can skip compose!. Each
+        // arity is written out, matching the tuple impls in zero_v (which
+        // make tuples valid NextNode types). Unlike nodes, tuples can't hold
+        // nested composites.
+        let tuple_impls = (1..=self.tuples).map(|arity| {
+            let params = (0..arity)
+                .map(|i| format_ident!("TraitType{}", i))
+                .collect::<Vec<_>>();
+            let indices = (0..arity).map(syn::Index::from).collect::<Vec<_>>();
+            let tuple = quote! { (#(#params,)*) };
+
+            let mut tuple_generics = trait_generics.clone();
+            tuple_generics
+                .params
+                .extend(params.iter().map(|param| -> GenericParam { parse_quote! { #param } }));
+            tuple_generics
+                .make_where_clause()
+                .predicates
+                .extend(params.iter().map(|param| -> WherePredicate {
+                    parse_quote! { #param: #trait_ident #ty_generics }
+                }));
+            let (tuple_impl_generics, _, tuple_where) = tuple_generics.split_for_impl();
+
+            let shadow_tuple_impls = if dyn_lookup {
+                quote! {
+                    fn dyn_at_level(&self, level: usize) -> Option<&(#dyn_trait)> {
+                        match level {
+                            #(#indices => Some(&self.#indices),)*
+                            _ => None,
+                        }
+                    }
+                }
+            } else {
+                quote! {}
+            };
+            let dyn_nodes_tuple = dyn_nodes(quote! { #(nodes.push(&self.#indices);)* });
+
+            let level_method_impls = level_methods
+                .iter()
+                .zip(&level_method_inputs)
+                .zip(&level_method_outputs)
+                .zip(trait_method_idents.iter().zip(&trait_method_args))
+                .map(|(((level_method, inputs), outputs), (method, args))| {
+                    let arms = indices.iter().map(|index| {
+                        quote! { #index => Some(self.#index.#method(#args)), }
+                    });
+                    quote! {
+                        #static_level_method
+                        fn #level_method(#inputs level: usize) -> #outputs {
+                            #max_nodes_check
+                            match level {
+                                #(#arms)*
+                                _ => None,
+                            }
+                        }
+                    }
+                });
+            let try_fold_level_impls = try_fold_level_methods
+                .iter()
+                .zip(&try_fold_generics)
+                .zip(&level_method_inputs)
+                .zip(trait_method_idents.iter().zip(&trait_method_args))
+                .map(|(((try_fold_method, generics), inputs), (method, args))| {
+                    let folds = indices.iter().map(|index| {
+                        quote! { let acc = fold(acc, self.#index.#method(#args))?; }
+                    });
+                    quote! {
+                        #[inline]
+                        fn #try_fold_method #generics(
+                            #inputs
+                            acc: ZeroVAcc,
+                            fold: &mut ZeroVFold
+                        ) -> Result<ZeroVAcc, ZeroVError> {
+                            #max_nodes_check
+                            #(#folds)*
+                            Ok(acc)
+                        }
+                    }
+                });
+            let for_each_level_impls = for_each_level_methods
+                .iter()
+                .zip(lending_generics.iter().zip(&lending_where))
+                .zip(&lending_inputs)
+                .zip(lending_idents.iter().zip(&lending_args))
+                .map(|(((for_each_method, (generics, where_clause)), inputs), (method, args))| {
+                    let visits = params.iter().zip(&indices).map(|(param, index)| {
+                        quote! {
+                            visitor.visit::<#param>(level + #index, self.#index.#method(#args));
+                        }
+                    });
+                    quote! {
+                        fn #for_each_method #generics(
+                            #inputs
+                            level: usize,
+                            visitor: &mut ZeroVVisitor
+                        ) #where_clause
+                        {
+                            #max_nodes_check
+                            #(#visits)*
+                        }
+                    }
+                });
+
+            let tuple_iter_impl = replace_ident(
+                iter_impl(
+                    quote! { #tuple_impl_generics },
+                    tuple.clone(),
+                    quote! { #tuple_where },
+                    quote! { self },
+                    quote! { self },
+                ),
+                "NodeType",
+                &tuple,
+            );
+
+            quote! {
+                #[automatically_derived]
+                #allow_lints
+                impl #tuple_impl_generics #level_trait #ty_generics for #tuple #tuple_where {
+                    const LEVEL_COUNT: usize = #arity;
+
+                    #[inline]
+                    fn level_count(&self) -> usize {
+                        #max_nodes_check
+                        #arity
+                    }
+
+                    fn type_name_at_level(&self, level: usize) -> Option<&'static str> {
+                        match level {
+                            #(#indices => Some(::core::any::type_name::<#params>()),)*
+                            _ => None,
+                        }
+                    }
+
+                    #shadow_tuple_impls
+                    #dyn_nodes_tuple
+
+                    fn audit_levels(
+                        &self,
+                        origin: *const u8,
+                        level: usize,
+                        depth: usize,
+                        nodes: &mut zero_v::__private::Vec<zero_v::NodeAudit>
+                    ) {
+                        #(
+                            nodes.push(
+                                zero_v::NodeAudit::of(&self.#indices, origin, level + #indices, depth)
+                            );
+                        )*
+                    }
+
+                    #[inline]
+                    fn visit_nodes_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                        &mut self,
+                        mutator: &mut ZeroVMutator
+                    ) {
+                        #max_nodes_check
+                        #(mutator.visit(&mut self.#indices);)*
+                    }
+
+                    #(#level_method_impls)*
+
+                    #(#try_fold_level_impls)*
+
+                    #(#for_each_level_impls)*
+                }
+
+                #tuple_iter_impl
+            }
+        });
+
+        let tokens = quote! {
+            #on_unimplemented
+            #trait_type
+
+            #(#args_struct_items)*
+
+            #allow_lints
+            trait #level_trait #trait_generics #where_clause {
+                // The number of levels below this point, counting each object
+                // in a nested composite as a level of its own.
+                fn level_count(&self) -> usize;
+
+                // The same count worked out at compile time, leaving out any
+                // objects in a dyn tail.
+                const LEVEL_COUNT: usize;
+
+                #max_nodes_decls
+
+                // The type name of the object at the given level, for
+                // reporting which object produced an output (None if the
+                // level is an empty optional node).
+                fn type_name_at_level(&self, level: usize) -> Option<&'static str>;
+
+                #shadow_level_decls
+
+                #dyn_nodes_decl
+
+                // Describe the layout of every object below this point, with
+                // offsets relative to the composite at origin.
+                fn audit_levels(
+                    &self,
+                    origin: *const u8,
+                    level: usize,
+                    depth: usize,
+                    nodes: &mut zero_v::__private::Vec<zero_v::NodeAudit>
+                );
+
+                // Apply a mutator to every object below this point.
+                fn visit_nodes_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                    &mut self,
+                    mutator: &mut ZeroVMutator
+                );
+
+                #(#level_method_decls)*
+
+                #(
+                    fn #try_fold_level_methods #try_fold_generics(
+                        #level_method_inputs
+                        acc: ZeroVAcc,
+                        fold: &mut ZeroVFold
+                    ) -> Result<ZeroVAcc, ZeroVError>;
+                )*
+
+                #(
+                    fn #for_each_level_methods #lending_generics(
+                        #lending_inputs
+                        level: usize,
+                        visitor: &mut ZeroVVisitor
+                    ) #lending_where;
+                )*
+            }
+
+            #allow_lints
+            trait #mutator_trait #trait_generics #where_clause {
+                fn visit<TraitType: #trait_ident #ty_generics>(&mut self, node: &mut TraitType);
+            }
+
+            #(#mut_caller_impls)*
+
+            #consume_items
+
+            #pinned_items
+
+            #(
+                #allow_lints
+                trait #visitor_traits #trait_generics #where_clause {
+                    #visitor_methods
+                }
+            )*
+
+            #[automatically_derived]
+            #allow_lints
+            impl #impl_generics #level_trait #ty_generics for () #where_clause {
+                #level_count_unit
+
+                fn level_count(&self) -> usize {
+                    0
+                }
+
+                fn type_name_at_level(&self, _level: usize) -> Option<&'static str> {
+                    None
+                }
+
+                #shadow_unit_impls
+                #dyn_nodes_unit
+
+                fn audit_levels(
+                    &self,
+                    _origin: *const u8,
+                    _level: usize,
+                    _depth: usize,
+                    _nodes: &mut zero_v::__private::Vec<zero_v::NodeAudit>
+                ) {
+                }
+
+                fn visit_nodes_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                    &mut self,
+                    _mutator: &mut ZeroVMutator
+                ) {
+                }
+
+                #(
+                    #[allow(unused)]
+                    #static_level_method
+                    fn #level_methods(#level_method_inputs level: usize) -> #level_method_outputs {
+                        None
+                    }
+                )*
+
+                #(
+                    #[allow(unused)]
+                    fn #try_fold_level_methods #try_fold_generics(
+                        #level_method_inputs
+                        acc: ZeroVAcc,
+                        fold: &mut ZeroVFold
+                    ) -> Result<ZeroVAcc, ZeroVError> {
+                        Ok(acc)
+                    }
+                )*
+
+                #(
+                    #[allow(unused)]
+                    fn #for_each_level_methods #lending_generics(
+                        #lending_inputs
+                        level: usize,
+                        visitor: &mut ZeroVVisitor
+                    ) #lending_where
+                    {
+                    }
+                )*
+            }
+
+            #[automatically_derived]
+            #allow_lints
+            impl #level_impl_generics #level_trait #ty_generics
+                for zero_v::Node<#zv_trait_type, #zv_node_type>
+            #level_where_clause
+            {
+                #level_count_node
+
+                #[inline]
+                fn level_count(&self) -> usize {
+                    #max_nodes_check
+                    self.next().level_count() + 1
+                }
+
+                fn type_name_at_level(&self, level: usize) -> Option<&'static str> {
+                    if level != 0 {
+                        self.next().type_name_at_level(level - 1)
+                    } else {
+                        Some(::core::any::type_name::<#zv_trait_type>())
+                    }
+                }
+
+                #shadow_node_impls
+                #dyn_nodes_node
+
+                fn audit_levels(
+                    &self,
+                    origin: *const u8,
+                    level: usize,
+                    depth: usize,
+                    nodes: &mut zero_v::__private::Vec<zero_v::NodeAudit>
+                ) {
+                    nodes.push(zero_v::NodeAudit::of(self.data(), origin, level, depth));
+                    self.next().audit_levels(origin, level + 1, depth, nodes);
+                }
+
+                #[inline]
+                fn visit_nodes_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                    &mut self,
+                    mutator: &mut ZeroVMutator
+                ) {
+                    #max_nodes_check
+                    mutator.visit(self.data_mut());
+                    self.next_mut().visit_nodes_mut(mutator);
+                }
+
+                #(
+                    #static_level_method
+                    fn #level_methods(#level_method_inputs level: usize)
+                        -> #level_method_outputs
+                    {
+                        #max_nodes_check
+                        if level != 0 {
+                            self.next().#level_methods(#trait_method_args level - 1)
+                        } else {
+                            Some(self.data().#trait_method_idents(#trait_method_args))
+                        }
+                    }
+                )*
+
+                #(
+                    #[inline]
+                    fn #try_fold_level_methods #try_fold_generics(
+                        #level_method_inputs
+                        acc: ZeroVAcc,
+                        fold: &mut ZeroVFold
+                    ) -> Result<ZeroVAcc, ZeroVError> {
+                        #max_nodes_check
+                        let acc = fold(acc, self.data().#trait_method_idents(#trait_method_args))?;
+                        self.next().#try_fold_level_methods(#trait_method_args acc, fold)
+                    }
+                )*
+
+                #(
+                    fn #for_each_level_methods #lending_generics(
+                        #lending_inputs
+                        level: usize,
+                        visitor: &mut ZeroVVisitor
+                    ) #lending_where
+                    {
+                        #max_nodes_check
+                        visitor.visit::<#zv_trait_type>(level, self.data().#lending_idents(#lending_args));
+                        self.next().#for_each_level_methods(#lending_args level + 1, visitor);
+                    }
+                )*
+            }
+
+            #nested_level_impl
+
+            #optional_level_impls
+            #named_level_impl
+
+            #block_level_impls
+
+            #dyn_tail_level_impl
+
+            #[automatically_derived]
+            #allow_lints
+            impl #composite_level_generics #level_trait #ty_generics
+                for zero_v::Composite<#zv_node_type>
+            #composite_level_where
+            {
+                #level_count_composite
+
+                #[inline]
+                fn level_count(&self) -> usize {
+                    self.head().level_count()
+                }
+
+                fn type_name_at_level(&self, level: usize) -> Option<&'static str> {
+                    self.head().type_name_at_level(level)
+                }
+
+                #shadow_composite_impls
+                #dyn_nodes_composite
+
+                fn audit_levels(
+                    &self,
+                    origin: *const u8,
+                    level: usize,
+                    depth: usize,
+                    nodes: &mut zero_v::__private::Vec<zero_v::NodeAudit>
+                ) {
+                    self.head().audit_levels(origin, level, depth, nodes);
+                }
+
+                #[inline]
+                fn visit_nodes_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                    &mut self,
+                    mutator: &mut ZeroVMutator
+                ) {
+                    self.head_mut().visit_nodes_mut(mutator);
+                }
+
+                #(
+                    #static_level_method
+                    fn #level_methods(#level_method_inputs level: usize)
+                        -> #level_method_outputs
+                    {
+                            self.head().#level_methods(#trait_method_args level)
+                    }
+                )*
+
+                #(
+                    #[inline]
+                    fn #try_fold_level_methods #try_fold_generics(
+                        #level_method_inputs
+                        acc: ZeroVAcc,
+                        fold: &mut ZeroVFold
+                    ) -> Result<ZeroVAcc, ZeroVError> {
+                        self.head().#try_fold_level_methods(#trait_method_args acc, fold)
+                    }
+                )*
+
+                #(
+                    fn #for_each_level_methods #lending_generics(
+                        #lending_inputs
+                        level: usize,
+                        visitor: &mut ZeroVVisitor
+                    ) #lending_where
+                    {
+                        self.head().#for_each_level_methods(#lending_args level, visitor)
+                    }
+                )*
+            }
+
+
+            #level_of_items
+
+            #allow_lints
+            trait #iter_trait #iter_generics #iter_where_clause {
+                fn for_each_node_mut<ZeroVMutator: #mutator_trait #ty_generics>(
+                    &mut self,
+                    mutator: &mut ZeroVMutator
+                );
+
+                // Every level of the collection, counting each object in a
+                // nested composite or dyn tail as a level of its own.
+                fn iter_levels(&self) -> ::core::iter::Map<
+                    ::core::ops::Range<usize>,
+                    fn(usize) -> zero_v::Level<Self>
+                >;
+
+                // Find the level of the object of type ZeroVTarget. Like
+                // zero_v::Select, the index is left to be inferred, so this
+                // only compiles if exactly one object has that type. When
+                // there are several, inference has no single level to pick
+                // and fails with a "type annotations needed" error.
+                fn level_of<ZeroVTarget, ZeroVIndex>() -> zero_v::Level<Self>
+                where
+                    Self: Sized + #level_of_index
+                {
+                    zero_v::__private::level(<Self as #level_of_index>::LEVEL)
+                }
+
+                fn audit(&self) -> zero_v::Audit;
+
+                // zero_v::At counts nodes rather than levels, so the const
+                // level methods only agree with the other level methods when
+                // every node holds exactly one object. This only evaluates
+                // (and fails the build) when one of them is used on a
+                // collection where that doesn't hold.
+                const ONE_LEVEL_PER_NODE: () = assert!(
+                    <#zv_node_type as #level_trait #ty_generics>::LEVEL_COUNT
+                        == <#zv_node_type as zero_v::NextNode>::LEN,
+                    "the `_at_const` methods index nodes, so they can't be used on collections holding nested composites or blocks"
+                );
+
+                #to_dyn_vec_decl
+
+                #(
+                    fn #for_each_mut_methods<ZeroVF: FnMut(#mut_method_outputs)>(
+                        &mut self,
+                        #mut_method_inputs
+                        f: ZeroVF
+                    );
+
+                    fn #iter_mut_methods(&mut self, #mut_method_inputs)
+                        -> <zero_v::__private::Vec<#mut_method_outputs>
+                            as ::core::iter::IntoIterator>::IntoIter;
+                )*
+
+                #(
+                    fn #iter_methods(#level_method_inputs)
+                        -> #composite_iters #composite_ty_generics;
+
+                    fn #range_iter_methods<LevelRange: ::core::ops::RangeBounds<usize>>(
+                        &self,
+                        range: LevelRange,
+                        #trait_method_inputs
+                    ) -> #composite_iters #composite_ty_generics;
+
+                    fn #chain_methods<
+                        'zero_v,
+                        OtherNodes: zero_v::NextNode + #level_trait #ty_generics
+                    >(
+                        &'zero_v self,
+                        other: &'zero_v zero_v::Composite<OtherNodes>,
+                        #trait_method_inputs
+                    ) -> ::core::iter::Chain<
+                        #composite_iters #composite_lifetime_ty_generics,
+                        #composite_iters #chain_other_ty_generics
+                    >;
+
+                    #flatten_iter_decls
+
+                    #with_iter_decls
+
+                    #stats_decls
+
+                    fn #step_iter_methods(
+                        &self,
+                        start: usize,
+                        step: usize,
+                        #trait_method_inputs
+                    ) -> #composite_iters #composite_ty_generics;
+
+                    fn #ref_iter_methods<'zero_v>(
+                        &'zero_v self,
+                        #ref_iter_inputs
+                    ) -> #composite_ref_iters #composite_lifetime_ty_generics;
+
+                    fn #bind_methods(&self, #trait_method_inputs)
+                        -> #bound_composites #composite_ty_generics;
+
+                    fn #try_fold_methods #try_fold_generics(
+                        &self,
+                        init: ZeroVAcc,
+                        #trait_method_inputs
+                        fold: ZeroVFold
+                    ) -> Result<ZeroVAcc, ZeroVError>;
+
+                    fn #extend_methods<ZeroVExtend: Extend<#trait_method_outputs>>(
+                        &self,
+                        out: &mut ZeroVExtend,
+                        #trait_method_inputs
+                    );
+
+                    fn #priority_iter_methods(&self, #trait_method_inputs)
+                        -> #composite_priority_iters #composite_ty_generics
+                    where
+                        #zv_node_type: zero_v::Priorities;
+
+                    fn #const_level_methods<const LEVEL: usize>(&self, #trait_method_inputs)
+                        -> #trait_method_outputs
+                    where
+                        #zv_node_type: zero_v::At<LEVEL>,
+                        <#zv_node_type as zero_v::At<LEVEL>>::Output: #trait_ident #ty_generics;
+
+                    fn #typed_level_methods(
+                        &self,
+                        #trait_method_inputs
+                        level: zero_v::Level<Self>
+                    ) -> #level_method_outputs;
+                )*
+
+                #(
+                    fn #for_each_methods #lending_generics(
+                        #lending_inputs
+                        visitor: &mut ZeroVVisitor
+                    ) #lending_where;
+                )*
+            }
+
+            #composite_iter_impl
+
+            #(#tuple_impls)*
+
+            #(
+                #allow_lints
+                struct #composite_iters #composite_lifetime_generics
+                #composite_where_clause
+                {
+                    level: usize,
+                    start: usize,
+                    end: usize,
+                    step: usize,
+                    #trait_method_inputs
+                    parent: &'zero_v #zv_node_type,
+                    #composite_phantom_fields
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #composite_impl_generics
+                     #composite_iters #composite_lifetime_ty_generics
+                #composite_where_clause
+                {
+                    fn new(
+                        parent: &'zero_v #zv_node_type,
+                        levels: ::core::ops::Range<usize>,
+                        step: usize,
+                        #trait_method_inputs
+                    ) -> Self {
+                        Self {
+                            parent,
+                            #trait_method_args
+                            level: levels.start,
+                            start: levels.start,
+                            end: levels.end,
+                            step,
+                            #composite_phantom_vals
+                        }
+                    }
+
+                    /// Rewind the iterator to its first level, so it can be
+                    /// run again without being rebuilt.
+                    #[inline]
+                    fn reset(&mut self) {
+                        self.level = self.start;
+                    }
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #composite_impl_generics zero_v::NamedOutputs for
+                     #composite_iters #composite_lifetime_ty_generics
+                #composite_where_clause
+                {
+                    fn next_type_name(&self) -> Option<&'static str> {
+                        let mut level = self.level;
+                        while level < self.end {
+                            if let Some(name) = self.parent.type_name_at_level(level) {
+                                return Some(name);
+                            }
+                            level = level.saturating_add(self.step);
+                        }
+                        None
+                    }
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #composite_impl_generics Iterator for
+                     #composite_iters #composite_lifetime_ty_generics
+                #composite_where_clause
+                {
+                    type Item = #trait_method_outputs;
+
+                    #[inline]
+                    fn next(&mut self) -> Option<Self::Item> {
+                        while self.level < self.end {
+                            let result = #iter_dispatch;
+                            #iter_shadow_checks
+                            // Skipped levels are never dispatched to, so their
+                            // nodes don't do any work.
+                            self.level = self.level.saturating_add(self.step);
+                            // Empty optional nodes have no output, so move on
+                            // to the next level.
+                            if result.is_some() {
+                                return result;
+                            }
+                        }
+                        None
+                    }
+                }
+
+                // Like the iterator above, but borrowing its arguments, for
+                // tight loops which reuse one iterator with reset() rather
+                // than building a new one (and copying the arguments into it)
+                // for every pass.
+                #allow_lints
+                struct #composite_ref_iters #composite_lifetime_generics
+                #composite_where_clause
+                {
+                    level: usize,
+                    end: usize,
+                    #ref_iter_inputs
+                    parent: &'zero_v #zv_node_type,
+                    #composite_phantom_fields
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #composite_impl_generics
+                     #composite_ref_iters #composite_lifetime_ty_generics
+                #composite_where_clause
+                {
+                    fn new(
+                        parent: &'zero_v #zv_node_type,
+                        end: usize,
+                        #ref_iter_inputs
+                    ) -> Self {
                         Self {
                             parent,
-                            #trait_method_args,
+                            #trait_method_args
                             level: 0,
+                            end,
                             #composite_phantom_vals
                         }
                     }
+
+                    /// Rewind the iterator to the first level, so it can be
+                    /// run again without being rebuilt.
+                    #[inline]
+                    fn reset(&mut self) {
+                        self.level = 0;
+                    }
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #composite_impl_generics zero_v::NamedOutputs for
+                     #composite_ref_iters #composite_lifetime_ty_generics
+                #composite_where_clause
+                {
+                    fn next_type_name(&self) -> Option<&'static str> {
+                        (self.level..self.end)
+                            .find_map(|level| self.parent.type_name_at_level(level))
+                    }
                 }
 
+                #[automatically_derived]
+                #allow_lints
                 impl #composite_impl_generics Iterator for
-                     #composite_iters #composite_lifetime_ty_generics
+                     #composite_ref_iters #composite_lifetime_ty_generics
                 #composite_where_clause
                 {
                     type Item = #trait_method_outputs;
 
                     #[inline]
                     fn next(&mut self) -> Option<Self::Item> {
-                        let result = self.parent.#level_methods(
-                            #trait_method_self_args,
-                            self.level
-                        );
-                        self.level += 1;
-                        result
+                        while self.level < self.end {
+                            let result = #ref_iter_dispatch;
+                            #ref_iter_shadow_checks
+                            self.level += 1;
+                            if result.is_some() {
+                                return result;
+                            }
+                        }
+                        None
+                    }
+                }
+
+                // A composite with its arguments already applied, which can be
+                // iterated over any number of times.
+                #allow_lints
+                struct #bound_composites #composite_lifetime_generics
+                #composite_where_clause
+                {
+                    #trait_method_inputs
+                    parent: &'zero_v #zv_node_type,
+                    #composite_phantom_fields
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #composite_impl_generics
+                     #bound_composites #composite_lifetime_ty_generics
+                #composite_where_clause
+                {
+                    /// Execute every node with the bound arguments.
+                    #[inline]
+                    fn iter(&self) -> #composite_iters #composite_lifetime_ty_generics {
+                        let end = self.parent.level_count();
+                        #composite_iters::new(self.parent, 0..end, 1, #trait_method_self_args)
+                    }
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #composite_impl_generics IntoIterator for
+                     #bound_composites #composite_lifetime_ty_generics
+                #composite_where_clause
+                {
+                    type Item = #trait_method_outputs;
+                    type IntoIter = #composite_iters #composite_lifetime_ty_generics;
+
+                    #[inline]
+                    fn into_iter(self) -> Self::IntoIter {
+                        self.iter()
+                    }
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #bound_ref_impl_generics IntoIterator for
+                     &'zero_v_bound #bound_composites #composite_lifetime_ty_generics
+                #composite_where_clause
+                {
+                    type Item = #trait_method_outputs;
+                    type IntoIter = #composite_iters #composite_lifetime_ty_generics;
+
+                    #[inline]
+                    fn into_iter(self) -> Self::IntoIter {
+                        self.iter()
+                    }
+                }
+
+                // Yields outputs in the order of the levels' priorities. The
+                // order is a const table worked out once per composite type,
+                // so each step is a lookup in that table rather than a search.
+                #allow_lints
+                struct #composite_priority_iters #priority_lifetime_generics
+                #priority_where_clause
+                {
+                    rank: usize,
+                    #trait_method_inputs
+                    parent: &'zero_v #zv_node_type,
+                    #composite_phantom_fields
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #priority_impl_generics
+                     #composite_priority_iters #priority_lifetime_ty_generics
+                #priority_where_clause
+                {
+                    fn new(
+                        parent: &'zero_v #zv_node_type,
+                        #trait_method_inputs
+                    ) -> Self {
+                        Self {
+                            parent,
+                            #trait_method_args
+                            rank: 0,
+                            #composite_phantom_vals
+                        }
+                    }
+
+                    /// Rewind the iterator to the output with the lowest
+                    /// priority, so it can be run again without being rebuilt.
+                    #[inline]
+                    fn reset(&mut self) {
+                        self.rank = 0;
+                    }
+                }
+
+                #[automatically_derived]
+                #allow_lints
+                impl #priority_impl_generics Iterator for
+                     #composite_priority_iters #priority_lifetime_ty_generics
+                #priority_where_clause
+                {
+                    type Item = #trait_method_outputs;
+
+                    fn next(&mut self) -> Option<Self::Item> {
+                        while self.rank < <#zv_node_type as zero_v::NextNode>::LEN {
+                            let level = <#zv_node_type as zero_v::Priorities>::ORDER[self.rank];
+                            self.rank += 1;
+                            let result = #priority_dispatch;
+                            #priority_shadow_checks
+                            if result.is_some() {
+                                return result;
+                            }
+                        }
+                        None
                     }
                 }
             )*
         };
 
-        TokenStream::from(tokens)
+        let shared_tokens = if self.shared {
+            match shared_impls(&trait_type, &idents) {
+                Ok(tokens) => tokens,
+                Err(err) => return TokenStream::from(err.to_compile_error()),
+            }
+        } else {
+            quote! {}
+        };
+
+        let refs_tokens = if self.refs {
+            match refs_impls(&trait_type) {
+                Ok(tokens) => tokens,
+                Err(err) => return TokenStream::from(err.to_compile_error()),
+            }
+        } else {
+            quote! {}
+        };
+
+        let either_tokens = if self.either {
+            match either_impls(&trait_type) {
+                Ok(tokens) => tokens,
+                Err(err) => return TokenStream::from(err.to_compile_error()),
+            }
+        } else {
+            quote! {}
+        };
+
+        let mock_tokens = if self.mock {
+            match mock_impls(&trait_type, &idents) {
+                Ok(tokens) => tokens,
+                Err(err) => return TokenStream::from(err.to_compile_error()),
+            }
+        } else {
+            quote! {}
+        };
+
+        TokenStream::from(quote! {
+            #tokens
+            #shared_tokens
+            #refs_tokens
+            #either_tokens
+            #mock_tokens
+        })
     }
 }
 
 impl Parse for TraitTypes {
-    fn parse(_input: ParseStream) -> syn::Result<Self> {
-        Ok(Self {})
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut trait_types = Self {
+            shadow: false,
+            shared: false,
+            max_nodes: None,
+            args: false,
+            refs: false,
+            either: false,
+            dyn_tail: false,
+            dyn_in_debug: false,
+            mock: false,
+            to_dyn_vec: false,
+            optional: false,
+            blocks: false,
+            tuples: DEFAULT_TUPLE_LEN,
+        };
+        let options = Punctuated::<NestedMeta, Comma>::parse_terminated(input)?;
+        for option in options {
+            match &option {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("shadow") => {
+                    trait_types.shadow = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("shared") => {
+                    trait_types.shared = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("args") => {
+                    trait_types.args = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("refs") => {
+                    trait_types.refs = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("either") => {
+                    trait_types.either = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("dyn_tail") => {
+                    trait_types.dyn_tail = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("dyn_in_debug") => {
+                    trait_types.dyn_in_debug = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("mock") => {
+                    trait_types.mock = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("to_dyn_vec") => {
+                    trait_types.to_dyn_vec = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("optional") => {
+                    trait_types.optional = true
+                }
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("blocks") => {
+                    trait_types.blocks = true
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Int(max_nodes),
+                    ..
+                })) if path.is_ident("max_nodes") => {
+                    trait_types.max_nodes = Some(max_nodes.base10_parse()?)
+                }
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    path,
+                    lit: Lit::Int(tuples),
+                    ..
+                })) if path.is_ident("tuples") => {
+                    trait_types.tuples = tuples.base10_parse()?;
+                    if trait_types.tuples > MAX_TUPLE_LEN {
+                        return Err(syn::Error::new_spanned(
+                            tuples,
+                            format!("`tuples` can be at most {}", MAX_TUPLE_LEN),
+                        ));
+                    }
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        option,
+                        "expected one of `shadow` | `shared` | `args` | `refs` | `either` | \
+                         `dyn_tail` | `dyn_in_debug` | `mock` | `to_dyn_vec` | `optional` | \
+                         `blocks` | `max_nodes = <number>` | `tuples = <number>`",
+                    ))
+                }
+            }
+        }
+        Ok(trait_types)
+    }
+}
+
+/// Add a trailing comma to a non-empty list of arguments, so it can be
+/// followed by more arguments (or fields) whether or not it's empty.
+fn with_trailing_comma<T>(mut list: Punctuated<T, Comma>) -> Punctuated<T, Comma> {
+    if !list.empty_or_trailing() {
+        list.push_punct(Comma::default());
+    }
+    list
+}
+
+/// The signatures, names and argument names of a trait's methods, for
+/// options which generate impls forwarding every method to another object.
+/// Every item in the trait needs to be a supported method for the impls to
+/// be complete.
+#[allow(clippy::type_complexity)]
+fn forwarded_methods(
+    trait_type: &ItemTrait,
+    option: &str,
+) -> syn::Result<(Vec<Signature>, Vec<Ident>, Vec<Punctuated<Ident, Comma>>)> {
+    let mut sigs = vec![];
+    let mut method_idents = vec![];
+    let mut method_args = vec![];
+    for item in &trait_type.items {
+        let sig = match item {
+            TraitItem::Method(m) if is_supported(&m.sig) && !is_lending(&m.sig) => &m.sig,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    item,
+                    format!(
+                        "`{}` requires every item in the trait to be a method taking `&self` \
+                         without bounds on `Self` or outputs mentioning `Self`",
+                        option
+                    ),
+                ))
+            }
+        };
+
+        sigs.push(sig.clone());
+        method_idents.push(sig.ident.clone());
+        method_args.push(with_trailing_comma(
+            sig.inputs
+                .iter()
+                .filter_map(|arg| match arg {
+                    FnArg::Typed(PatType { pat, .. }) => match **pat {
+                        Pat::Ident(ref i) => Some(i.ident.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect::<Punctuated<Ident, Comma>>(),
+        ));
+    }
+    Ok((sigs, method_idents, method_args))
+}
+
+/// Implement the trait for shared references and smart pointers to anything
+/// implementing it, for the `refs` option, so composites can hold borrowed or
+/// shared objects.
+fn refs_impls(trait_type: &ItemTrait) -> syn::Result<TokenStream2> {
+    let trait_ident = &trait_type.ident;
+    let (_, ty_generics, _) = trait_type.generics.split_for_impl();
+    let (sigs, method_idents, method_args) = forwarded_methods(trait_type, "refs")?;
+
+    let mut ref_generics = trait_type.generics.clone();
+    // Lifetimes have to come before the trait's own type parameters.
+    ref_generics.params.insert(0, parse_quote! { 'zero_v_ref });
+    ref_generics
+        .params
+        .push(parse_quote! { ZeroVInner: #trait_ident #ty_generics + ?Sized });
+    let (ref_impl_generics, _, ref_where_clause) = ref_generics.split_for_impl();
+    let mut pointer_generics = trait_type.generics.clone();
+    pointer_generics
+        .params
+        .push(parse_quote! { ZeroVInner: #trait_ident #ty_generics + ?Sized });
+    let (pointer_impl_generics, _, pointer_where_clause) = pointer_generics.split_for_impl();
+
+    let impls = [
+        (
+            quote! { #ref_impl_generics },
+            quote! { &'zero_v_ref ZeroVInner },
+            quote! { #ref_where_clause },
+        ),
+        (
+            quote! { #pointer_impl_generics },
+            quote! { zero_v::__private::Box<ZeroVInner> },
+            quote! { #pointer_where_clause },
+        ),
+        (
+            quote! { #pointer_impl_generics },
+            quote! { zero_v::__private::Rc<ZeroVInner> },
+            quote! { #pointer_where_clause },
+        ),
+        (
+            quote! { #pointer_impl_generics },
+            quote! { zero_v::__private::Arc<ZeroVInner> },
+            quote! { #pointer_where_clause },
+        ),
+    ]
+    .iter()
+    .map(|(impl_generics, pointer, where_clause)| {
+        quote! {
+            #[automatically_derived]
+            #[allow(clippy::too_many_arguments)]
+            impl #impl_generics #trait_ident #ty_generics for #pointer #where_clause {
+                #(
+                    #[inline]
+                    #sigs {
+                        (**self).#method_idents(#method_args)
+                    }
+                )*
+            }
+        }
+    })
+    .collect::<Vec<_>>();
+
+    Ok(quote! { #(#impls)* })
+}
+
+/// Implement the trait for `zero_v::Either` wherever both of its types
+/// implement it, for the `either` option, so the object in one position of a
+/// composite can be picked at runtime.
+fn either_impls(trait_type: &ItemTrait) -> syn::Result<TokenStream2> {
+    let trait_ident = &trait_type.ident;
+    let (_, ty_generics, _) = trait_type.generics.split_for_impl();
+    let (sigs, method_idents, method_args) = forwarded_methods(trait_type, "either")?;
+
+    let either_params: Vec<GenericParam> = vec![
+        parse_quote! { ZeroVLeft: #trait_ident #ty_generics },
+        parse_quote! { ZeroVRight: #trait_ident #ty_generics },
+    ];
+    let mut either_generics = trait_type.generics.clone();
+    either_generics.params.extend(either_params);
+    let (either_impl_generics, _, either_where_clause) = either_generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(clippy::too_many_arguments)]
+        impl #either_impl_generics #trait_ident #ty_generics
+            for zero_v::Either<ZeroVLeft, ZeroVRight>
+        #either_where_clause
+        {
+            #(
+                #[inline]
+                #sigs {
+                    match self {
+                        zero_v::Either::Left(zero_v_inner) => {
+                            zero_v_inner.#method_idents(#method_args)
+                        }
+                        zero_v::Either::Right(zero_v_inner) => {
+                            zero_v_inner.#method_idents(#method_args)
+                        }
+                    }
+                }
+            )*
+        }
+    })
+}
+
+/// Generate the `{TraitName}Mut` trait for the `shared` option, which mirrors
+/// the trait with `&mut self` receivers, and implement the trait for
+/// `RefCellNode` and `MutexNode` around anything implementing it. Every item
+/// in the trait needs to be a supported method, since the wrappers have to
+/// provide all of them.
+fn shared_impls(trait_type: &ItemTrait, idents: &Idents) -> syn::Result<TokenStream2> {
+    let trait_ident = &trait_type.ident;
+    let mut_trait = idents.mut_trait();
+    let (_, ty_generics, where_clause) = trait_type.generics.split_for_impl();
+    let trait_generics = &trait_type.generics;
+
+    let (shared_sigs, method_idents, method_args) = forwarded_methods(trait_type, "shared")?;
+    let mut_sigs = shared_sigs.iter().map(|sig| {
+        let mut mut_sig = sig.clone();
+        if let Some(FnArg::Receiver(receiver)) = mut_sig.inputs.first_mut() {
+            receiver.mutability = Some(Default::default());
+        }
+        mut_sig
+    });
+
+    let mut shared_generics = trait_generics.clone();
+    shared_generics
+        .params
+        .push(parse_quote! { ZeroVInner: #mut_trait #ty_generics });
+    let (shared_impl_generics, _, shared_where_clause) = shared_generics.split_for_impl();
+
+    // MutexNode needs std, so it only exists when zero_v's std feature
+    // (which turns on this crate's) is enabled.
+    let mut wrappers = vec![quote! { zero_v::RefCellNode<ZeroVInner> }];
+    if cfg!(feature = "std") {
+        wrappers.push(quote! { zero_v::MutexNode<ZeroVInner> });
+    }
+
+    let wrapper_impls = wrappers
+        .iter()
+        .map(|wrapper| {
+            quote! {
+                #[automatically_derived]
+                #[allow(clippy::too_many_arguments)]
+                impl #shared_impl_generics #trait_ident #ty_generics for #wrapper
+                #shared_where_clause
+                {
+                    #(
+                        #[inline]
+                        #shared_sigs {
+                            self.with(|zero_v_inner| zero_v_inner.#method_idents(#method_args))
+                        }
+                    )*
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        #[allow(dead_code, clippy::too_many_arguments)]
+        trait #mut_trait #trait_generics #where_clause {
+            #(#mut_sigs;)*
+        }
+
+        #(#wrapper_impls)*
+    })
+}
+
+/// Generate the `{TraitName}Mock` struct for the `mock` option, which
+/// implements the trait by returning a canned value from each method and
+/// counting its calls in a `{TraitName}MockCalls`. Methods without an output
+/// only count their calls. Every item in the trait needs to be a supported
+/// method, and the outputs can't borrow from anything, since they're stored
+/// in the mock.
+fn mock_impls(trait_type: &ItemTrait, idents: &Idents) -> syn::Result<TokenStream2> {
+    if !trait_type.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &trait_type.generics,
+            "`mock` isn't supported for traits with generic parameters",
+        ));
+    }
+    let trait_ident = &trait_type.ident;
+    let vis = &trait_type.vis;
+    let mock = idents.mock_struct();
+    let mock_calls = idents.mock_calls_struct();
+    let (sigs, method_idents, _) = forwarded_methods(trait_type, "mock")?;
+    if let Some(sig) = sigs.iter().find(|sig| !sig.generics.params.is_empty()) {
+        return Err(syn::Error::new_spanned(
+            sig,
+            "`mock` isn't supported for methods with generic parameters",
+        ));
+    }
+
+    let (returning_idents, returning_outputs): (Vec<&Ident>, Vec<&Type>) = sigs
+        .iter()
+        .filter_map(|sig| match &sig.output {
+            ReturnType::Default => None,
+            ReturnType::Type(_, ty) => Some((&sig.ident, &**ty)),
+        })
+        .unzip();
+    let bodies = sigs.iter().map(|sig| {
+        let method = &sig.ident;
+        let count = quote! { self.calls.#method.set(self.calls.#method.get() + 1); };
+        match &sig.output {
+            ReturnType::Default => count,
+            ReturnType::Type(..) => {
+                let message = format!("no return value set for `{}::{}`", mock, method);
+                quote! {
+                    #count
+                    match &self.#method {
+                        Some(output) => ::core::clone::Clone::clone(output),
+                        None => panic!(#message),
+                    }
+                }
+            }
+        }
+    });
+    let mock_doc = format!(
+        "A mock `{}` for tests, returning the value set for each method (see \
+         `zero_v::mock_node!`) and counting its calls.",
+        trait_ident
+    );
+    let mock_calls_doc = format!("The number of calls made to each method of a `{}`.", mock);
+
+    Ok(quote! {
+        #[doc = #mock_doc]
+        #[derive(Default)]
+        #vis struct #mock {
+            #(
+                pub #returning_idents: Option<#returning_outputs>,
+            )*
+            pub calls: #mock_calls,
+        }
+
+        #[doc = #mock_calls_doc]
+        #[derive(Debug, Default)]
+        #vis struct #mock_calls {
+            #(
+                pub #method_idents: ::core::cell::Cell<usize>,
+            )*
+        }
+
+        #[automatically_derived]
+        #[allow(unused_variables, clippy::too_many_arguments)]
+        impl #trait_ident for #mock {
+            #(
+                #sigs {
+                    #bodies
+                }
+            )*
+        }
+    })
+}
+
+/// The ways a supported method can receive the object it's called on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SelfKind {
+    /// `&self`
+    Ref,
+    /// `&mut self`
+    Mut,
+    /// `self` (or `mut self`)
+    Value,
+    /// `self: Rc<Self>`
+    Rc,
+    /// `self: Arc<Self>`
+    Arc,
+    /// `self: Pin<&mut Self>`
+    PinMut,
+}
+
+/// Work out how a method receives its object, if it's a method at all and
+/// the receiver is one the macro can call through.
+pub(crate) fn self_kind(sig: &Signature) -> Option<SelfKind> {
+    match sig.inputs.first()? {
+        FnArg::Receiver(receiver) => Some(match (&receiver.reference, receiver.mutability) {
+            (Some(_), None) => SelfKind::Ref,
+            (Some(_), Some(_)) => SelfKind::Mut,
+            (None, _) => SelfKind::Value,
+        }),
+        FnArg::Typed(PatType { pat, ty, .. }) => {
+            match &**pat {
+                Pat::Ident(pat) if pat.ident == "self" => {}
+                _ => return None,
+            }
+            let segment = match &**ty {
+                Type::Path(TypePath { qself: None, path }) => path.segments.last()?,
+                _ => return None,
+            };
+            let inner = match &segment.arguments {
+                PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+                    match args.args.first()? {
+                        GenericArgument::Type(inner) => inner,
+                        _ => return None,
+                    }
+                }
+                _ => return None,
+            };
+            let (kind, inner) = if segment.ident == "Rc" {
+                (SelfKind::Rc, inner)
+            } else if segment.ident == "Arc" {
+                (SelfKind::Arc, inner)
+            } else if segment.ident == "Pin" {
+                match inner {
+                    Type::Reference(reference) if reference.mutability.is_some() => {
+                        (SelfKind::PinMut, &*reference.elem)
+                    }
+                    _ => return None,
+                }
+            } else {
+                return None;
+            };
+            if is_self(inner) {
+                Some(kind)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Check whether the macro can generate code for a method. Methods which
+/// don't take `&self` are skipped, as are methods with bounds on `Self` (e.g.
+/// `where Self: Clone`), which can't be called on an arbitrary object in the
+/// composite since the generated code only knows each object implements the
+/// trait. The exception is `Self: Sized`, which always holds for objects
+/// stored in a composite.
+pub(crate) fn is_supported(sig: &Signature) -> bool {
+    // Only methods taking &self can be executed through a shared reference
+    // to the composite.
+    self_kind(sig) == Some(SelfKind::Ref) && has_supported_self_bounds(&sig.generics)
+}
+
+/// Check whether the macro can generate code for a `&mut self` method,
+/// which is called on each object in turn through the generated mutator
+/// trait. On top of the restrictions on `&self` methods, these can't have
+/// generic parameters or outputs mentioning `Self`.
+pub(crate) fn is_supported_mut(sig: &Signature) -> bool {
+    self_kind(sig) == Some(SelfKind::Mut) && is_supported_per_object(sig)
+}
+
+/// Check whether the macro can generate code for a method taking `self` by
+/// value (or in a fresh `Rc` or `Arc`), which is called once on each object
+/// as the composite is taken apart by the generated consume trait. These
+/// have the same restrictions as `&mut self` methods.
+pub(crate) fn is_supported_consuming(sig: &Signature) -> bool {
+    matches!(
+        self_kind(sig),
+        Some(SelfKind::Value | SelfKind::Rc | SelfKind::Arc)
+    ) && is_supported_per_object(sig)
+}
+
+/// Check whether the macro can generate code for a `self: Pin<&mut Self>`
+/// method, which is called on each object of a pinned composite through the
+/// generated pinned trait. These have the same restrictions as `&mut self`
+/// methods.
+pub(crate) fn is_supported_pinned(sig: &Signature) -> bool {
+    self_kind(sig) == Some(SelfKind::PinMut) && is_supported_per_object(sig)
+}
+
+fn is_supported_per_object(sig: &Signature) -> bool {
+    sig.generics.params.is_empty() && has_supported_self_bounds(&sig.generics) && !is_lending(sig)
+}
+
+fn has_supported_self_bounds(generics: &Generics) -> bool {
+    self_bounds(generics).all(|bound| match bound {
+        TypeParamBound::Trait(TraitBound {
+            modifier: TraitBoundModifier::None,
+            path,
+            ..
+        }) => path.is_ident("Sized"),
+        _ => false,
+    })
+}
+
+fn self_bounds(generics: &Generics) -> impl Iterator<Item = &TypeParamBound> {
+    generics
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| where_clause.predicates.iter())
+        .filter_map(|predicate| match predicate {
+            WherePredicate::Type(predicate) if is_self(&predicate.bounded_ty) => {
+                Some(predicate.bounds.iter())
+            }
+            _ => None,
+        })
+        .flatten()
+}
+
+/// A copy of some method generics without any bounds on `Self`, which would
+/// refer to the wrong type if copied into a generated method.
+fn without_self_bounds(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    if let Some(where_clause) = &mut generics.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| match predicate {
+                WherePredicate::Type(predicate) => !is_self(&predicate.bounded_ty),
+                _ => true,
+            })
+            .cloned()
+            .collect();
+    }
+    generics
+}
+
+fn is_self(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path.is_ident("Self"),
+        _ => false,
+    }
+}
+
+/// Check whether a method's output mentions `Self` (e.g. a generic
+/// associated type like `Self::View<'a>`), in which case objects in a
+/// composite each produce a different output type.
+pub(crate) fn is_lending(sig: &Signature) -> bool {
+    fn mentions_self(tokens: TokenStream2) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == "Self",
+            TokenTree::Group(group) => mentions_self(group.stream()),
+            _ => false,
+        })
+    }
+
+    match &sig.output {
+        ReturnType::Default => false,
+        ReturnType::Type(_, ty) => mentions_self(quote! { #ty }),
+    }
+}
+
+/// Replace `Self` in a type with another type. Where `Self` is followed by a
+/// path (`Self::View<'a>`), it's replaced with `with_path` instead, so the
+/// associated type can be qualified with the trait it belongs to.
+fn replace_self(
+    tokens: TokenStream2,
+    with_type: &TokenStream2,
+    with_path: &TokenStream2,
+) -> TokenStream2 {
+    let mut tokens = tokens.into_iter().peekable();
+    let mut replaced = TokenStream2::new();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "Self" => {
+                let followed_by_path = match tokens.peek() {
+                    Some(TokenTree::Punct(punct)) => {
+                        punct.as_char() == ':' && punct.spacing() == Spacing::Joint
+                    }
+                    _ => false,
+                };
+                if followed_by_path {
+                    replaced.extend(with_path.clone());
+                } else {
+                    replaced.extend(with_type.clone());
+                }
+            }
+            TokenTree::Group(group) => {
+                let stream = replace_self(group.stream(), with_type, with_path);
+                let mut new_group = Group::new(group.delimiter(), stream);
+                new_group.set_span(group.span());
+                replaced.extend(Some(TokenTree::Group(new_group)));
+            }
+            token => replaced.extend(Some(token)),
+        }
+    }
+    replaced
+}
+
+/// Replace every occurrence of an identifier in some tokens with other
+/// tokens.
+fn replace_ident(tokens: TokenStream2, ident: &str, with: &TokenStream2) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Ident(token_ident) if token_ident == ident => with.clone(),
+            TokenTree::Group(group) => {
+                let stream = replace_ident(group.stream(), ident, with);
+                let mut new_group = Group::new(group.delimiter(), stream);
+                new_group.set_span(group.span());
+                TokenStream2::from(TokenTree::Group(new_group))
+            }
+            token => TokenStream2::from(token),
+        })
+        .collect()
+}
+
+/// Check whether a generic parameter is used anywhere in some tokens.
+fn mentions(tokens: TokenStream2, param: &GenericParam) -> bool {
+    let ident = match param {
+        GenericParam::Type(ty) => &ty.ident,
+        GenericParam::Lifetime(lifetime) => &lifetime.lifetime.ident,
+        GenericParam::Const(constant) => &constant.ident,
+    };
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(token_ident) => &token_ident == ident,
+        TokenTree::Group(group) => mentions(group.stream(), param),
+        _ => false,
+    })
+}
+
+/// Strip the bounds and defaults from a generic parameter, for declaring it
+/// on a generated struct which doesn't need them.
+fn without_bounds(mut param: GenericParam) -> GenericParam {
+    match &mut param {
+        GenericParam::Type(ty) => {
+            ty.colon_token = None;
+            ty.bounds.clear();
+            ty.eq_token = None;
+            ty.default = None;
+        }
+        GenericParam::Lifetime(lifetime) => {
+            lifetime.colon_token = None;
+            lifetime.bounds.clear();
+        }
+        GenericParam::Const(constant) => {
+            constant.eq_token = None;
+            constant.default = None;
+        }
+    }
+    param
+}
+
+/// The type parameters (as opposed to lifetimes or consts) of a trait.
+fn type_params(generics: &Generics) -> Vec<Ident> {
+    generics
+        .params
+        .iter()
+        .filter_map(|p| match p {
+            GenericParam::Type(t) => Some(t.ident.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Check whether a type is (syntactically) an `Option<T>`.
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => match path.segments.last() {
+            Some(segment) => {
+                segment.ident == "Option"
+                    && matches!(segment.arguments, PathArguments::AngleBracketed(_))
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Check whether a type is one of the primitive integer or float types.
+fn is_primitive_number(ty: &Type) -> bool {
+    const NUMBERS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+        "f32", "f64",
+    ];
+
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => match path.get_ident() {
+            Some(ident) => NUMBERS.iter().any(|number| ident == number),
+            None => false,
+        },
+        _ => false,
     }
 }
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use zero_v::{compose, zero_v};
+
+#[zero_v(trait_types, args)]
+trait Score {
+    fn score(&self, base: usize, bonus: usize, multiplier: usize, penalty: usize) -> usize;
+}
+
+struct Flat;
+
+impl Score for Flat {
+    fn score(&self, base: usize, bonus: usize, multiplier: usize, penalty: usize) -> usize {
+        (base + bonus) * multiplier - penalty
+    }
+}
+
+struct Doubled;
+
+impl Score for Doubled {
+    fn score(&self, base: usize, bonus: usize, multiplier: usize, penalty: usize) -> usize {
+        2 * (base + bonus) * multiplier - penalty
+    }
+}
+
+#[test]
+fn test_args_struct() {
+    let scorers = compose!(Flat, Doubled);
+
+    let args = ScoreArgs {
+        base: 2,
+        multiplier: 3,
+        ..Default::default()
+    };
+    assert_eq!(scorers.iter_score_with(args).collect::<Vec<_>>(), vec![6, 12]);
+    assert_eq!(
+        scorers.iter_score_with(ScoreArgs { bonus: 1, ..args }).collect::<Vec<_>>(),
+        scorers.iter_score(2, 1, 3, 0).collect::<Vec<_>>()
+    );
+}
+
+#[zero_v(trait_types, args)]
+trait Lookup<'a, K, V: Copy> {
+    fn lookup(&self, table: &'a HashMap<K, V>, fallback: V) -> V;
+    fn describe(&self, width: usize) -> String;
+}
+
+struct Key(&'static str);
+
+impl<'a> Lookup<'a, &'static str, usize> for Key {
+    fn lookup(&self, table: &'a HashMap<&'static str, usize>, fallback: usize) -> usize {
+        table.get(self.0).copied().unwrap_or(fallback)
+    }
+
+    fn describe(&self, width: usize) -> String {
+        format!("{:>width$}", self.0, width = width)
+    }
+}
+
+#[test]
+fn test_args_struct_with_generics() {
+    let table = vec![("one", 1)].into_iter().collect::<HashMap<_, _>>();
+    let keys = compose!(Key("one"), Key("two"));
+
+    let args = LookupArgs {
+        table: &table,
+        fallback: 0,
+    };
+    assert_eq!(keys.iter_lookup_with(args).collect::<Vec<_>>(), vec![1, 0]);
+    // DescribeArgs doesn't mention any of the trait's parameters.
+    let describe: DescribeArgs = Default::default();
+    assert_eq!(keys.iter_describe_with(describe).collect::<Vec<_>>(), vec!["one", "two"]);
+}
@@ -0,0 +1,65 @@
+use zero_v::{compose, zero_v};
+
+#[zero_v(trait_types, blocks)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+#[derive(Clone, Copy)]
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+struct Doubler;
+
+impl IntOp for Doubler {
+    fn execute(&self, input: usize) -> usize {
+        input * 2
+    }
+}
+
+#[test]
+fn test_array_elements_are_levels() {
+    let ops = compose!(Doubler, [Adder(1), Adder(2), Adder(3)], Doubler);
+    assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 2, 3, 4, 2]);
+    assert_eq!(ops.execute_at_level(1, 3), Some(4));
+    assert_eq!(ops.iter_execute_range(2.., 1).count(), 3);
+    assert_eq!(ops.try_fold_execute(0, 1, |acc, x| Ok::<_, ()>(acc + x)), Ok(13));
+
+    let audit = ops.audit();
+    assert_eq!(audit.nodes.len(), 5);
+    assert_eq!(audit.nodes[3].level, 3);
+}
+
+#[test]
+fn test_arrays_of_copies() {
+    let ops = compose!([Adder(1); 32]);
+    assert_eq!(ops.iter_execute(0).sum::<usize>(), 32);
+    assert_eq!(compose!([Adder(1); 0], Doubler).iter_execute(3).collect::<Vec<_>>(), vec![6]);
+}
+
+mod without_blocks {
+    use zero_v::{compose, zero_v};
+
+    // Without `blocks`, the trait can be implemented for an array itself.
+    #[zero_v(trait_types)]
+    trait IntOp {
+        fn execute(&self, input: usize) -> usize;
+    }
+
+    impl IntOp for [usize; 2] {
+        fn execute(&self, input: usize) -> usize {
+            input + self[0] + self[1]
+        }
+    }
+
+    #[test]
+    fn test_trait_can_be_implemented_for_arrays() {
+        let ops = compose!([1, 2], [3, 4]);
+        assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![4, 8]);
+    }
+}
@@ -0,0 +1,30 @@
+use zero_v::{compose, compose_balanced, zero_v};
+
+#[zero_v(trait_types)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+struct Adder<const N: usize>;
+
+impl<const N: usize> IntOp for Adder<N> {
+    fn execute(&self, input: usize) -> usize {
+        input + N
+    }
+}
+
+#[test]
+fn test_balanced_composites_keep_their_order() {
+    let ops = compose_balanced!(
+        Adder::<0>, Adder::<1>, Adder::<2>, Adder::<3>, Adder::<4>, Adder::<5>, Adder::<6>
+    );
+    assert_eq!(ops.iter_execute(10).collect::<Vec<_>>(), (10..17).collect::<Vec<_>>());
+    assert_eq!(ops.len(), 2);
+}
+
+#[test]
+fn test_small_balanced_composites() {
+    assert_eq!(compose_balanced!(), compose!());
+    assert_eq!(compose_balanced!(1), compose!(1));
+    assert_eq!(compose_balanced!(1, 2, 3), compose!(compose!(1, 2), 3));
+}
@@ -0,0 +1,47 @@
+use zero_v::{compose_from_spec, zero_v};
+
+#[zero_v(trait_types)]
+trait Stage {
+    fn run(&self, input: i64) -> i64;
+}
+
+struct Scale(i64);
+
+impl Stage for Scale {
+    fn run(&self, input: i64) -> i64 {
+        input * self.0
+    }
+}
+
+struct Offset(i64);
+
+impl Stage for Offset {
+    fn run(&self, input: i64) -> i64 {
+        input + self.0
+    }
+}
+
+#[test]
+fn test_inline_spec() {
+    let stages = compose_from_spec! {
+        registry {
+            "double" => Scale(2),
+            "offset" => Offset(-1),
+        },
+        spec = ["offset", "double"],
+    };
+    assert_eq!(stages.iter_run(5).collect::<Vec<_>>(), vec![4, 10]);
+}
+
+#[test]
+fn test_file_spec() {
+    let stages = compose_from_spec! {
+        registry {
+            "double" => Scale(2),
+            "offset" => Offset(-1),
+            "unused" => Offset(100),
+        },
+        spec = "tests/specs/pipeline.spec",
+    };
+    assert_eq!(stages.iter_run(5).collect::<Vec<_>>(), vec![10, 4, 10]);
+}
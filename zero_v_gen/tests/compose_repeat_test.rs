@@ -0,0 +1,59 @@
+use zero_v::{compose, compose_repeat, zero_v};
+
+#[zero_v(trait_types)]
+trait Stage {
+    fn run(&self, input: f64) -> f64;
+}
+
+struct Smoother {
+    alpha: f64,
+}
+
+impl Smoother {
+    fn new(alpha: f64) -> Self {
+        Self { alpha }
+    }
+}
+
+impl Stage for Smoother {
+    fn run(&self, input: f64) -> f64 {
+        input * self.alpha
+    }
+}
+
+#[test]
+fn test_compose_repeat() {
+    let stages = compose_repeat!(Smoother::new(0.5); 4);
+    assert_eq!(stages.len(), 4);
+
+    let outputs: Vec<f64> = stages.iter_run(2.0).collect();
+    assert_eq!(outputs, vec![1.0; 4]);
+}
+
+#[test]
+fn test_compose_repeat_matches_compose() {
+    assert_eq!(compose_repeat!(7; 3), compose!(7, 7, 7));
+    assert_eq!(compose_repeat!(7; 0), compose!());
+}
+
+struct Scaler<const N: usize>;
+
+impl<const N: usize> Stage for Scaler<N> {
+    fn run(&self, input: f64) -> f64 {
+        input * N as f64
+    }
+}
+
+#[test]
+fn test_compose_repeat_sequence() {
+    let stages = compose_repeat!(Scaler::<#i> for i in 1..4);
+    assert_eq!(stages.iter_run(1.0).collect::<Vec<_>>(), vec![1.0, 2.0, 3.0]);
+    assert_eq!(compose_repeat!(#i + #i for i in 0..=2), compose!(0, 2, 4));
+}
+
+#[test]
+fn test_compose_repetition_form() {
+    let stages = compose!(Smoother::new(0.5), for n in 2..4 { Scaler::<#n> }, Scaler::<4>);
+    assert_eq!(stages.iter_run(2.0).collect::<Vec<_>>(), vec![1.0, 4.0, 6.0, 8.0]);
+    assert_eq!(compose!(for n in 0..=1 { #n }), compose!(0, 1));
+}
@@ -0,0 +1,100 @@
+use zero_v::{compose, zero_v, Toggle};
+
+#[derive(Debug, PartialEq)]
+struct Report {
+    name: &'static str,
+    total: u32,
+}
+
+#[zero_v(trait_types, optional, blocks)]
+trait Stage {
+    fn process(&self, input: u32) -> u32;
+    fn finalize(self, runs: u32) -> Report;
+    fn close(self);
+}
+
+struct Adder(u32);
+
+impl Stage for Adder {
+    fn process(&self, input: u32) -> u32 {
+        input + self.0
+    }
+
+    fn finalize(self, runs: u32) -> Report {
+        Report {
+            name: "adder",
+            total: self.0 * runs,
+        }
+    }
+
+    fn close(self) {}
+}
+
+struct Doubler;
+
+impl Stage for Doubler {
+    fn process(&self, input: u32) -> u32 {
+        input * 2
+    }
+
+    fn finalize(self, _runs: u32) -> Report {
+        Report {
+            name: "doubler",
+            total: 0,
+        }
+    }
+
+    fn close(self) {}
+}
+
+#[test]
+fn test_into_iter() {
+    let stages = compose!(Adder(1), Doubler, Adder(3));
+    assert_eq!(stages.iter_process(2).collect::<Vec<_>>(), vec![3, 4, 5]);
+
+    let reports = stages.into_iter_finalize(10).map(|report| report.total);
+    assert_eq!(reports.collect::<Vec<_>>(), vec![10, 0, 30]);
+}
+
+#[test]
+fn test_for_each_into() {
+    let mut names = Vec::new();
+    compose!(Doubler, Adder(2)).for_each_finalize_into(1, |report| names.push(report.name));
+    assert_eq!(names, vec!["doubler", "adder"]);
+
+    let mut closed = 0;
+    compose!(Doubler, Adder(2)).for_each_close_into(|()| closed += 1);
+    assert_eq!(closed, 2);
+}
+
+#[test]
+fn test_into_iter_wrappers() {
+    let stages = compose!(
+        Some(Adder(1)),
+        None::<Adder>,
+        Toggle::disabled(Adder(2)),
+        Toggle::new(Adder(3)),
+        [Adder(4), Adder(5)],
+        compose!(Adder(6), Doubler),
+    );
+    let totals = stages.into_iter_finalize(1).map(|report| report.total);
+    assert_eq!(totals.collect::<Vec<_>>(), vec![1, 3, 4, 5, 6, 0]);
+}
+
+#[test]
+fn test_into_iter_tuple() {
+    let reports = (Adder(2), Doubler).into_iter_finalize(2);
+    assert_eq!(
+        reports.collect::<Vec<_>>(),
+        vec![
+            Report {
+                name: "adder",
+                total: 4
+            },
+            Report {
+                name: "doubler",
+                total: 0
+            },
+        ]
+    );
+}
@@ -0,0 +1,120 @@
+use zero_v::{compose, zero_v};
+
+#[zero_v(trait_types, dyn_in_debug, optional, blocks)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+    fn describe(&self, input: usize) -> String;
+}
+
+#[derive(Clone, Copy)]
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+
+    fn describe(&self, input: usize) -> String {
+        format!("{} + {}", input, self.0)
+    }
+}
+
+struct Multiplier(usize);
+
+impl IntOp for Multiplier {
+    fn execute(&self, input: usize) -> usize {
+        input * self.0
+    }
+
+    fn describe(&self, input: usize) -> String {
+        format!("{} * {}", input, self.0)
+    }
+}
+
+impl zero_v::Priority for Adder {
+    const PRIORITY: usize = 1;
+}
+
+impl zero_v::Priority for Multiplier {
+    const PRIORITY: usize = 0;
+}
+
+#[test]
+fn test_dyn_in_debug_iterates_every_node() {
+    let ops = compose!(Adder(1), compose!(Multiplier(2)), None::<Adder>, [Adder(3); 2]);
+
+    assert_eq!(ops.iter_execute(4).collect::<Vec<_>>(), vec![5, 8, 7, 7]);
+    assert_eq!(ops.iter_execute_range(1..3, 4).collect::<Vec<_>>(), vec![8]);
+    assert_eq!(ops.iter_execute_ref(&4).sum::<usize>(), 27);
+
+    let ops = compose!(Adder(1), Multiplier(2), Adder(3));
+    assert_eq!(
+        ops.iter_describe_by_priority(4).collect::<Vec<_>>(),
+        vec!["4 * 2", "4 + 1", "4 + 3"]
+    );
+}
+
+// In debug builds the level methods come from the level trait itself, through
+// dyn_at_level, so a level which only hands out trait objects gets them all.
+#[cfg(debug_assertions)]
+struct Levels(Adder, Multiplier);
+
+#[cfg(debug_assertions)]
+impl IntOpAtLevel for Levels {
+    const LEVEL_COUNT: usize = 2;
+
+    fn level_count(&self) -> usize {
+        Self::LEVEL_COUNT
+    }
+
+    fn type_name_at_level(&self, _level: usize) -> Option<&'static str> {
+        None
+    }
+
+    fn dyn_at_level(&self, level: usize) -> Option<&(dyn IntOp + '_)> {
+        match level {
+            0 => Some(&self.0),
+            1 => Some(&self.1),
+            _ => None,
+        }
+    }
+
+    fn audit_levels(
+        &self,
+        _origin: *const u8,
+        _level: usize,
+        _depth: usize,
+        _nodes: &mut Vec<zero_v::NodeAudit>,
+    ) {
+    }
+
+    fn visit_nodes_mut<Mutator: IntOpMutator>(&mut self, _mutator: &mut Mutator) {}
+
+    fn execute_try_fold_at_level<Acc, Error, Fold: FnMut(Acc, usize) -> Result<Acc, Error>>(
+        &self,
+        _input: usize,
+        acc: Acc,
+        _fold: &mut Fold,
+    ) -> Result<Acc, Error> {
+        Ok(acc)
+    }
+
+    fn describe_try_fold_at_level<Acc, Error, Fold: FnMut(Acc, String) -> Result<Acc, Error>>(
+        &self,
+        _input: usize,
+        acc: Acc,
+        _fold: &mut Fold,
+    ) -> Result<Acc, Error> {
+        Ok(acc)
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_debug_level_methods_use_vtables() {
+    let levels = Levels(Adder(1), Multiplier(2));
+
+    assert_eq!(levels.execute_at_level(4, 0), Some(5));
+    assert_eq!(levels.describe_at_level(4, 1), Some("4 * 2".to_string()));
+    assert_eq!(levels.execute_at_level(4, 2), None);
+}
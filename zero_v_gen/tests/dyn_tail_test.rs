@@ -0,0 +1,51 @@
+use zero_v::{compose, zero_v, DynTail};
+
+#[zero_v(trait_types, dyn_tail)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+struct Multiplier(usize);
+
+impl IntOp for Multiplier {
+    fn execute(&self, input: usize) -> usize {
+        input * self.0
+    }
+}
+
+fn plugins() -> DynTail<dyn IntOp> {
+    vec![Box::new(Multiplier(2)) as Box<dyn IntOp>, Box::new(Adder(3))].into()
+}
+
+#[test]
+fn test_dyn_tail_runs_after_the_static_objects() {
+    let ops = compose!(Adder(1), compose!(Multiplier(3)), ..plugins());
+    assert_eq!(ops.iter_execute(2).collect::<Vec<_>>(), vec![3, 6, 4, 5]);
+    assert_eq!(ops.iter_execute_ref(&2).sum::<usize>(), 18);
+    assert_eq!(ops.try_fold_execute(0, 2, |acc, x| Ok::<_, ()>(acc + x)), Ok(18));
+    assert_eq!(ops.len(), 2);
+}
+
+#[test]
+fn test_dyn_tail_objects_have_levels() {
+    let ops = compose!(Adder(1), compose!(Multiplier(3)), ..plugins());
+    let outputs = ops
+        .iter_levels()
+        .map(|level| ops.execute_at(2, level))
+        .collect::<Vec<_>>();
+    assert_eq!(outputs, vec![Some(3), Some(6), Some(4), Some(5)]);
+}
+
+#[test]
+fn test_empty_dyn_tail_adds_nothing() {
+    let ops = compose!(Adder(1), ..DynTail::<dyn IntOp>::new());
+    assert_eq!(ops.iter_execute(2).collect::<Vec<_>>(), vec![3]);
+}
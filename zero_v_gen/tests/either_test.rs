@@ -0,0 +1,39 @@
+use zero_v::{compose, zero_v, Either};
+
+#[zero_v(trait_types, either)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+struct Multiplier(usize);
+
+impl IntOp for Multiplier {
+    fn execute(&self, input: usize) -> usize {
+        input * self.0
+    }
+}
+
+fn scaler(multiply: bool) -> Either<Multiplier, Adder> {
+    if multiply {
+        Either::Left(Multiplier(3))
+    } else {
+        Either::Right(Adder(3))
+    }
+}
+
+#[test]
+fn test_either_calls_the_active_object() {
+    let multiplied = compose!(Adder(1), scaler(true));
+    assert_eq!(multiplied.iter_execute(2).collect::<Vec<_>>(), vec![3, 6]);
+
+    let added = compose!(Adder(1), scaler(false));
+    assert_eq!(added.iter_execute(2).collect::<Vec<_>>(), vec![3, 5]);
+}
@@ -0,0 +1,25 @@
+use zero_v::{compose, zero_v};
+
+#[zero_v(trait_types)]
+trait Extractor {
+    fn extract(&self, input: usize) -> Option<usize>;
+}
+
+struct Threshold(usize);
+
+impl Extractor for Threshold {
+    fn extract(&self, input: usize) -> Option<usize> {
+        input.checked_sub(self.0)
+    }
+}
+
+#[test]
+fn test_flatten_skips_none() {
+    let extractors = compose!(Threshold(5), Threshold(30), Threshold(20), Threshold(25));
+
+    let results: Vec<usize> = extractors.iter_extract_flatten(20).collect();
+    assert_eq!(results, vec![15, 0]);
+
+    let unflattened: Vec<Option<usize>> = extractors.iter_extract(20).collect();
+    assert_eq!(unflattened, vec![Some(15), None, Some(0), None]);
+}
@@ -1,4 +1,4 @@
-use zero_v::compose;
+use zero_v::{assert_outputs_eq, compose};
 use zero_v_gen::zero_v;
 
 #[zero_v(trait_types)]
@@ -122,6 +122,225 @@ fn test_fn_generics() {
     assert_eq!(results, vec![10, 10 << 1, 10 + 2, 10 * 3, 10 >> 2]);
 }
 
+#[test]
+fn test_range() {
+    let ops = compose!(
+        Adder::new(1),
+        Multiplier::new(2),
+        Adder::new(3),
+        RShifter::new(1)
+    );
+
+    let results = ops.iter_execute_1_range(2.., 10).collect::<Vec<_>>();
+    assert_eq!(results, vec![10 + 3, 10 >> 1]);
+
+    let results = ops.iter_execute_1_range(1..=2, 10).collect::<Vec<_>>();
+    assert_eq!(results, vec![10 * 2, 10 + 3]);
+
+    assert_eq!(ops.iter_execute_2_range(4.., 1, 2).count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_range_out_of_bounds() {
+    let ops = compose!(Adder::new(1), Multiplier::new(2));
+    ops.iter_execute_1_range(1..3, 10);
+}
+
+#[test]
+fn test_step() {
+    let ops = compose!(
+        Adder::new(1),
+        Multiplier::new(2),
+        Adder::new(3),
+        RShifter::new(1),
+        Adder::new(5)
+    );
+
+    let evens = ops.iter_execute_1_step(0, 2, 10).collect::<Vec<_>>();
+    assert_eq!(evens, vec![10 + 1, 10 + 3, 10 + 5]);
+
+    let odds = ops.iter_execute_1_step(1, 2, 10).collect::<Vec<_>>();
+    assert_eq!(odds, vec![10 * 2, 10 >> 1]);
+
+    let all = ops.iter_execute_1_step(0, 1, 10).collect::<Vec<_>>();
+    assert_eq!(all, ops.iter_execute_1(10).collect::<Vec<_>>());
+
+    assert_eq!(ops.iter_execute_1_step(5, 3, 10).count(), 0);
+    assert_eq!(ops.iter_execute_1_step(6, 1, 10).count(), 0);
+    assert_eq!(ops.iter_execute_1_step(usize::MAX, 1, 10).count(), 0);
+}
+
+#[test]
+fn test_at_const() {
+    let ops = compose!(Adder::new(1), Multiplier::new(2), RShifter::new(1));
+
+    assert_eq!(ops.execute_1_at_const::<0>(10), 10 + 1);
+    assert_eq!(ops.execute_1_at_const::<1>(10), 10 * 2);
+    assert_eq!(ops.execute_2_at_const::<2>(16, 2), 16 >> 2 >> 1);
+}
+
+#[test]
+fn test_at_typed_level() {
+    let ops = compose!(Adder::new(1), Multiplier::new(2), RShifter::new(1));
+
+    let outputs = ops
+        .iter_levels()
+        .map(|level| ops.execute_1_at(10, level))
+        .collect::<Vec<_>>();
+    assert_eq!(outputs, vec![Some(10 + 1), Some(10 * 2), Some(10 >> 1)]);
+
+    let last = ops.iter_levels().next_back().unwrap();
+    assert_eq!(ops.execute_2_at(16, 2, last), ops.execute_2_at_level(16, 2, 2));
+}
+
+#[test]
+fn test_levels_of_nested_composites() {
+    let ops = compose!(
+        compose!(Adder::new(10), Multiplier::new(20)),
+        RShifter::new(1)
+    );
+
+    let outputs = ops
+        .iter_levels()
+        .map(|level| ops.execute_1_at(2, level))
+        .collect::<Vec<_>>();
+    let expected = ops.iter_execute_1(2).map(Some).collect::<Vec<_>>();
+    assert_eq!(outputs, expected);
+    assert_eq!(outputs.len(), 3);
+}
+
+#[test]
+fn test_level_of() {
+    type Ops = zero_v::Composite<zero_v::Node<Adder, zero_v::Node<Multiplier, ()>>>;
+    const MULTIPLIER: usize = <Ops as IntOpLevelOf<Multiplier, _>>::LEVEL;
+    assert_eq!(MULTIPLIER, 1);
+
+    let ops = compose!(Adder::new(1), Multiplier::new(2));
+    let level = Ops::level_of::<Multiplier, _>();
+    assert_eq!(level.value(), MULTIPLIER);
+    assert_eq!(ops.execute_1_at(10, level), Some(10 * 2));
+}
+
+#[test]
+fn test_level_of_after_nested_composite() {
+    type Inner = zero_v::Composite<zero_v::Node<Adder, zero_v::Node<Adder, ()>>>;
+    type Ops = zero_v::Composite<
+        zero_v::Node<Inner, zero_v::Node<Multiplier, zero_v::Node<RShifter, ()>>>,
+    >;
+    let ops: Ops = compose!(
+        compose!(Adder::new(10), Adder::new(20)),
+        Multiplier::new(2),
+        RShifter::new(1)
+    );
+
+    let level = Ops::level_of::<Multiplier, _>();
+    assert_eq!(level.value(), 2);
+    assert_eq!(ops.execute_1_at(5, level), Some(5 * 2));
+    assert_eq!(Ops::level_of::<RShifter, _>().value(), 3);
+}
+
+#[test]
+fn test_step_between_levels() {
+    type Inner = zero_v::Composite<zero_v::Node<Multiplier, zero_v::Node<Adder, ()>>>;
+    type Ops = zero_v::Composite<
+        zero_v::Node<Adder, zero_v::Node<Inner, zero_v::Node<RShifter, ()>>>,
+    >;
+    let ops: Ops = compose!(
+        Adder::new(1),
+        compose!(Multiplier::new(2), Adder::new(3)),
+        RShifter::new(1)
+    );
+
+    let first = Ops::level_of::<Adder, _>();
+    let last = Ops::level_of::<RShifter, _>();
+    assert_eq!(first.next().map(|level| level.value()), Some(1));
+    assert_eq!(ops.execute_1_at(8, first.next().unwrap()), Some(8 * 2));
+    assert_eq!(last.prev().and_then(|level| level.next()), Some(last));
+    assert_eq!(last.next(), None);
+}
+
+#[test]
+fn test_reset() {
+    let ops = compose!(Adder::new(1), Multiplier::new(2), RShifter::new(1));
+
+    let mut iter = ops.iter_execute_1_step(1, 1, 8);
+    assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![8 * 2, 8 >> 1]);
+    iter.reset();
+    assert_eq!(iter.collect::<Vec<_>>(), vec![8 * 2, 8 >> 1]);
+
+    let mut iter = ops.iter_execute_1_by_priority(8);
+    assert_eq!(iter.next(), Some(8 * 2));
+    iter.reset();
+    assert_eq!(iter.collect::<Vec<_>>(), vec![8 * 2, 8 + 1, 8 >> 1]);
+}
+
+#[test]
+fn test_ref_iter() {
+    let ops = compose!(Adder::new(1), Multiplier::new(2), RShifter::new(1));
+    let (input_1, input_2) = (8, 2);
+
+    let mut iter = ops.iter_execute_2_ref(&input_1, &input_2);
+    for _ in 0..3 {
+        assert_eq!(iter.by_ref().sum::<usize>(), (8 + 2 + 1) + (8 * 2 * 2) + (8 >> 2 >> 1));
+        iter.reset();
+    }
+}
+
+#[test]
+fn test_bind() {
+    let ops = compose!(Adder::new(1), Multiplier::new(2), RShifter::new(1));
+
+    let bound = ops.bind_execute_2(8, 2);
+    for _ in 0..3 {
+        let results = (&bound).into_iter().collect::<Vec<_>>();
+        assert_eq!(results, vec![8 + 2 + 1, 8 * 2 * 2, 8 >> 2 >> 1]);
+    }
+
+    let mut total = 0;
+    for output in &bound {
+        total += output;
+    }
+    assert_eq!(total, bound.into_iter().sum::<usize>());
+}
+
+#[test]
+fn test_chain() {
+    let first = compose!(Adder::new(1), Multiplier::new(2));
+    let second = compose!(RShifter::new(1), LShifter::new(1), Adder::new(3));
+
+    let results = first.chain_execute_1(&second, 10).collect::<Vec<_>>();
+    assert_eq!(results, vec![10 + 1, 10 * 2, 10 >> 1, 10 << 1, 10 + 3]);
+
+    let results = second.chain_execute_2(&first, 4, 1).collect::<Vec<_>>();
+    assert_eq!(results, vec![4 >> 1 >> 1, 4 << 1 << 1, 4 + 1 + 3, 4 + 1 + 1, 4 * 2]);
+}
+
+#[test]
+fn test_nested_composites() {
+    let inner = compose!(Multiplier::new(2), LShifter::new(1));
+    let ops = compose!(Adder::new(1), inner, compose!(), RShifter::new(1));
+
+    let results = ops.iter_execute_1(8).collect::<Vec<_>>();
+    assert_eq!(results, vec![8 + 1, 8 * 2, 8 << 1, 8 >> 1]);
+
+    let results = ops.iter_execute_1_range(1..3, 8).collect::<Vec<_>>();
+    assert_eq!(results, vec![8 * 2, 8 << 1]);
+
+    assert_eq!(ops.execute_1_at_level(8, 3), Some(8 >> 1));
+    assert_eq!(ops.execute_1_at_level(8, 4), None);
+}
+
+#[test]
+fn test_nested_and_spliced_composites_match() {
+    let bundle = || compose!(Multiplier::new(2), LShifter::new(1));
+    let nested = compose!(Adder::new(1), bundle(), RShifter::new(1));
+    let spliced = compose!(Adder::new(1), ..bundle(), RShifter::new(1));
+
+    assert!(nested.iter_execute_1(8).eq(spliced.iter_execute_1(8)));
+    assert_eq!((nested.len(), spliced.len()), (3, 4));
+}
+
 #[test]
 fn test_mixing_by_manual_iteration() {
     let ops = compose!(Adder::new(1), Multiplier::new(2));
@@ -133,3 +352,116 @@ fn test_mixing_by_manual_iteration() {
     }
     println!("{:?}", outputs);
 }
+
+#[test]
+fn test_assert_outputs_eq() {
+    let ops = compose!(Adder::new(1), compose!(Multiplier::new(2)), RShifter::new(1));
+
+    assert_outputs_eq!(ops.iter_execute_1(8), [9, 16, 4]);
+    assert_outputs_eq!(ops.iter_execute_2_ref(&8, &2), vec![11, 32, 1]);
+}
+
+#[test]
+#[should_panic(expected = "outputs differ at index 1 (int_op_test::Multiplier)")]
+fn test_assert_outputs_eq_names_mismatched_node() {
+    let ops = compose!(Adder::new(1), Multiplier::new(2), RShifter::new(1));
+    assert_outputs_eq!(ops.iter_execute_1(8), [9, 17, 4]);
+}
+
+#[test]
+#[should_panic(expected = "unexpected output at index 2 (int_op_test::RShifter)")]
+fn test_assert_outputs_eq_names_extra_node() {
+    let ops = compose!(Adder::new(1), Multiplier::new(2), RShifter::new(1));
+    assert_outputs_eq!(ops.iter_execute_1(8), [9, 16]);
+}
+
+impl zero_v::Priority for Adder {
+    const PRIORITY: usize = 3;
+}
+
+impl zero_v::Priority for Multiplier {
+    const PRIORITY: usize = 1;
+}
+
+impl zero_v::Priority for RShifter {
+    const PRIORITY: usize = 3;
+}
+
+#[test]
+fn test_by_priority() {
+    let ops = compose!(Adder::new(1), RShifter::new(1), Multiplier::new(2));
+
+    // Lower priorities run first, and ties keep composition order.
+    assert_eq!(
+        ops.iter_execute_1_by_priority(8).collect::<Vec<_>>(),
+        vec![8 * 2, 8 + 1, 8 >> 1]
+    );
+
+    let ops = compose!(
+        Adder::new(1),
+        Multiplier::new(2),
+        Adder::new(3),
+        RShifter::new(1)
+    );
+    assert_eq!(
+        ops.iter_execute_2_by_priority(3, 2).collect::<Vec<_>>(),
+        vec![3 * 2 * 2, 3 + 2 + 1, 3 + 2 + 3, 3 >> 2 >> 1]
+    );
+}
+
+#[test]
+fn test_try_fold() {
+    let ops = compose!(Adder::new(1), compose!(Multiplier::new(2)), RShifter::new(1));
+
+    let sum = ops.try_fold_execute_1(0, 8, |acc, out| Ok::<_, ()>(acc + out));
+    assert_eq!(sum, Ok(9 + 16 + 4));
+
+    // The fold stops at the first error, so the last node is never reached.
+    let mut seen = vec![];
+    let checked = ops.try_fold_execute_1(0, 8, |acc: usize, out| {
+        seen.push(out);
+        if out > 10 {
+            Err(out)
+        } else {
+            Ok(acc + out)
+        }
+    });
+    assert_eq!(checked, Err(16));
+    assert_eq!(seen, vec![9, 16]);
+}
+
+#[test]
+fn test_extend() {
+    let ops = compose!(Adder::new(1), compose!(Multiplier::new(2)), RShifter::new(1));
+
+    let mut outputs = vec![0];
+    ops.extend_execute_1(&mut outputs, 8);
+    ops.extend_execute_2(&mut outputs, 8, 2);
+    assert_eq!(outputs, vec![0, 9, 16, 4, 11, 32, 1]);
+}
+
+#[test]
+fn test_audit() {
+    let ops = compose!(Adder::new(1), compose!(Multiplier::new(2), RShifter::new(1)));
+    let audit = ops.audit();
+
+    assert_eq!(audit.size, std::mem::size_of_val(&ops));
+    let nodes = audit
+        .nodes
+        .iter()
+        .map(|node| (node.level, node.type_name, node.size, node.depth))
+        .collect::<Vec<_>>();
+    let usize_size = std::mem::size_of::<usize>();
+    assert_eq!(
+        nodes,
+        vec![
+            (0, "int_op_test::Adder", usize_size, 0),
+            (1, "int_op_test::Multiplier", usize_size, 1),
+            (2, "int_op_test::RShifter", usize_size, 1),
+        ]
+    );
+
+    let mut offsets = audit.nodes.iter().map(|node| node.offset).collect::<Vec<_>>();
+    offsets.sort_unstable();
+    assert_eq!(offsets, vec![0, usize_size, 2 * usize_size]);
+}
@@ -0,0 +1,73 @@
+use std::fmt::Display;
+
+use zero_v::compose;
+use zero_v_gen::zero_v;
+
+#[zero_v(trait_types)]
+trait Viewer {
+    type View<'a>: Display
+    where
+        Self: 'a;
+
+    fn view<'a>(&'a self, input: &'a str) -> Self::View<'a>;
+    fn width(&self, scale: usize) -> usize;
+}
+
+struct Prefix(String);
+
+impl Viewer for Prefix {
+    type View<'a> = &'a str;
+
+    fn view<'a>(&'a self, input: &'a str) -> Self::View<'a> {
+        match input.strip_prefix(self.0.as_str()) {
+            Some(rest) => rest,
+            None => input,
+        }
+    }
+
+    fn width(&self, scale: usize) -> usize {
+        self.0.len() * scale
+    }
+}
+
+struct Repeat(usize);
+
+impl Viewer for Repeat {
+    type View<'a> = String;
+
+    fn view<'a>(&'a self, input: &'a str) -> Self::View<'a> {
+        input.repeat(self.0)
+    }
+
+    fn width(&self, scale: usize) -> usize {
+        self.0 * scale
+    }
+}
+
+struct Collect(Vec<(usize, String)>);
+
+impl ViewerViewVisitor for Collect {
+    fn visit<'a, TraitType: Viewer + 'a>(&mut self, level: usize, output: TraitType::View<'a>) {
+        self.0.push((level, output.to_string()));
+    }
+}
+
+#[test]
+fn test_for_each_lending_method() {
+    let viewers = compose!(Prefix("ab".into()), Repeat(2), compose!(Prefix("a".into())));
+
+    let mut collect = Collect(vec![]);
+    viewers.for_each_view("abc", &mut collect);
+    assert_eq!(
+        collect.0,
+        vec![
+            (0, "c".to_string()),
+            (1, "abcabc".to_string()),
+            (2, "bc".to_string())
+        ]
+    );
+
+    // Methods which don't borrow from the object still get iterators.
+    let widths = viewers.iter_width(3).collect::<Vec<_>>();
+    assert_eq!(widths, vec![6, 6, 3]);
+}
@@ -0,0 +1,46 @@
+#![deny(warnings, clippy::all, clippy::pedantic)]
+
+use zero_v::compose;
+use zero_v_gen::zero_v;
+
+#[zero_v(trait_types)]
+#[allow(clippy::too_many_arguments)]
+trait Mix {
+    fn mix(&self, red: u8, green: u8, blue: u8, cyan: u8, magenta: u8, yellow: u8, black: u8) -> u32;
+    fn maybe(&self) -> Option<(u8, (u16, u32), [u64; 2])>;
+}
+
+// A second trait in the same module, to check the generated items don't
+// clash with each other (or with a diagnostic of the trait's own).
+#[zero_v(trait_types)]
+#[diagnostic::on_unimplemented(note = "only weighable objects can be weighed")]
+trait Weigh {
+    fn weigh(&self) -> u8;
+}
+
+struct Sum;
+
+impl Mix for Sum {
+    #[allow(clippy::too_many_arguments)]
+    fn mix(&self, red: u8, green: u8, blue: u8, cyan: u8, magenta: u8, yellow: u8, black: u8) -> u32 {
+        [red, green, blue, cyan, magenta, yellow, black].iter().map(|&x| u32::from(x)).sum()
+    }
+
+    fn maybe(&self) -> Option<(u8, (u16, u32), [u64; 2])> {
+        None
+    }
+}
+
+impl Weigh for Sum {
+    fn weigh(&self) -> u8 {
+        1
+    }
+}
+
+#[test]
+fn test_generated_code_is_lint_clean() {
+    let mixes = compose!(Sum, Sum);
+    assert_eq!(mixes.iter_mix(1, 2, 3, 4, 5, 6, 7).sum::<u32>(), 56);
+    assert_eq!(mixes.iter_maybe_flatten().count(), 0);
+    assert_eq!(mixes.iter_weigh().sum::<u8>(), 2);
+}
@@ -0,0 +1,20 @@
+use zero_v::{compose, zero_v};
+
+#[zero_v(trait_types, max_nodes = 3)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+#[test]
+fn test_composites_within_budget() {
+    let ops = compose!(Adder(1), compose!(Adder(2), Adder(3)));
+    assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 3, 4]);
+}
@@ -0,0 +1,33 @@
+use zero_v::{compose, mock_node, zero_v};
+
+#[zero_v(trait_types, mock)]
+trait Plugin {
+    fn transform(&self, input: usize) -> usize;
+    fn name(&self) -> String;
+    fn reset(&self);
+}
+
+#[test]
+fn test_mocks_return_canned_values() {
+    let plugins = compose!(
+        mock_node!(PluginMock { transform: 1, name: "first".to_string() }),
+        mock_node!(PluginMock { transform: 2 })
+    );
+
+    assert_eq!(plugins.iter_transform(10).collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(plugins.iter_transform(20).sum::<usize>(), 3);
+    plugins.iter_reset().for_each(drop);
+
+    let first = plugins.head().data();
+    assert_eq!(first.name(), "first");
+    assert_eq!(first.calls.transform.get(), 2);
+    assert_eq!(first.calls.name.get(), 1);
+    assert_eq!(first.calls.reset.get(), 1);
+}
+
+#[test]
+#[should_panic(expected = "no return value set for `PluginMock::name`")]
+fn test_mocks_panic_without_a_value() {
+    let plugins = compose!(mock_node!(PluginMock { transform: 1 }));
+    plugins.iter_name().for_each(drop);
+}
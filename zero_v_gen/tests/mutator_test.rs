@@ -0,0 +1,113 @@
+use zero_v::{compose, zero_v};
+
+#[zero_v(trait_types)]
+trait Gain {
+    fn apply(&self, input: i32) -> i32;
+    fn scale(&mut self, factor: i32);
+}
+
+struct Amplifier(i32);
+
+impl Gain for Amplifier {
+    fn apply(&self, input: i32) -> i32 {
+        input * self.0
+    }
+
+    fn scale(&mut self, factor: i32) {
+        self.0 *= factor;
+    }
+}
+
+struct Offset(i32);
+
+impl Gain for Offset {
+    fn apply(&self, input: i32) -> i32 {
+        input + self.0
+    }
+
+    fn scale(&mut self, factor: i32) {
+        self.0 *= factor;
+    }
+}
+
+struct Scale(i32, usize);
+
+impl GainMutator for Scale {
+    fn visit<TraitType: Gain>(&mut self, node: &mut TraitType) {
+        node.scale(self.0);
+        self.1 += 1;
+    }
+}
+
+#[test]
+fn test_for_each_node_mut() {
+    let mut nodes = compose!(Amplifier(2), Offset(3), compose!(Amplifier(-1)));
+    assert_eq!(nodes.iter_apply(5).collect::<Vec<_>>(), vec![10, 8, -5]);
+
+    let mut scale = Scale(2, 0);
+    nodes.for_each_node_mut(&mut scale);
+    assert_eq!(scale.1, 3);
+    assert_eq!(nodes.iter_apply(5).collect::<Vec<_>>(), vec![20, 11, -10]);
+}
+
+#[zero_v(trait_types, optional)]
+trait Accumulator {
+    fn total(&self) -> i32;
+    fn add(&mut self, value: i32) -> i32;
+    fn reset(&mut self);
+}
+
+struct Sum(i32);
+
+impl Accumulator for Sum {
+    fn total(&self) -> i32 {
+        self.0
+    }
+
+    fn add(&mut self, value: i32) -> i32 {
+        self.0 += value;
+        self.0
+    }
+
+    fn reset(&mut self) {
+        self.0 = 0;
+    }
+}
+
+struct Count(i32);
+
+impl Accumulator for Count {
+    fn total(&self) -> i32 {
+        self.0
+    }
+
+    fn add(&mut self, _value: i32) -> i32 {
+        self.0 += 1;
+        self.0
+    }
+
+    fn reset(&mut self) {
+        self.0 = 0;
+    }
+}
+
+#[test]
+fn test_mut_methods() {
+    let mut accumulators = compose!(Sum(0), compose!(Count(0)), None::<Sum>, Sum(10));
+
+    assert_eq!(accumulators.iter_add_mut(5).collect::<Vec<_>>(), vec![5, 1, 15]);
+
+    let mut outputs = vec![];
+    accumulators.for_each_add_mut(2, |output| outputs.push(output));
+    assert_eq!(outputs, vec![7, 2, 17]);
+    assert_eq!(accumulators.iter_total().collect::<Vec<_>>(), vec![7, 2, 17]);
+
+    assert_eq!(accumulators.iter_reset_mut().count(), 3);
+    assert_eq!(accumulators.iter_total().sum::<i32>(), 0);
+}
+
+#[test]
+fn test_mut_methods_on_tuples() {
+    let mut accumulators = (Sum(1), Count(1));
+    assert_eq!(accumulators.iter_add_mut(3).collect::<Vec<_>>(), vec![4, 2]);
+}
@@ -0,0 +1,55 @@
+use zero_v::{compose, compose_named, zero_v};
+
+#[zero_v(trait_types, optional)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+struct Offset;
+struct Bonus;
+
+#[test]
+fn test_named_objects_are_executed() {
+    let mut ops = compose!(Adder(1), ..compose_named!(Offset => Adder(2), Bonus => Adder(3)));
+    assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 3, 4]);
+
+    ops.get_named_mut::<Bonus, _>().0 = 10;
+    assert_eq!(ops.get_named::<Offset, _>().0, 2);
+    assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 3, 11]);
+}
+
+mod without_optional {
+    use zero_v::{compose, compose_named, zero_v};
+
+    // Named objects are forwarded to without the `optional` option.
+    #[zero_v(trait_types)]
+    trait IntOp {
+        fn execute(&self, input: usize) -> usize;
+    }
+
+    struct Adder(usize);
+
+    impl IntOp for Adder {
+        fn execute(&self, input: usize) -> usize {
+            input + self.0
+        }
+    }
+
+    struct Offset;
+
+    #[test]
+    fn test_named_objects_are_executed() {
+        let ops = compose!(Adder(1), ..compose_named!(Offset => Adder(2)));
+        assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(ops.execute_at_level(1, 1), Some(3));
+        assert_eq!(ops.get_named::<Offset, _>().0, 2);
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use zero_v::compose;
+use zero_v_gen::zero_v;
+
+#[zero_v(trait_types)]
+trait Lookup<'a, K, V>
+where
+    K: Eq + Hash,
+    V: Copy,
+{
+    fn lookup(&self, map: &'a HashMap<K, Vec<V>>) -> Option<V>;
+    fn lookup_all(&self, maps: &'a [(K, Option<V>)], fallback: V) -> Vec<(usize, V)>;
+}
+
+struct Key(&'static str);
+
+impl<'a, V: Copy> Lookup<'a, &'static str, V> for Key {
+    fn lookup(&self, map: &'a HashMap<&'static str, Vec<V>>) -> Option<V> {
+        map.get(self.0).and_then(|values| values.first().copied())
+    }
+
+    fn lookup_all(&self, maps: &'a [(&'static str, Option<V>)], fallback: V) -> Vec<(usize, V)> {
+        maps.iter()
+            .enumerate()
+            .filter(|(_, (key, _))| *key == self.0)
+            .map(|(i, (_, value))| (i, value.unwrap_or(fallback)))
+            .collect()
+    }
+}
+
+#[test]
+fn test_nested_generic_arguments() {
+    let keys = compose!(Key("a"), Key("b"), Key("c"));
+
+    let mut map = HashMap::new();
+    map.insert("a", vec![1, 2]);
+    map.insert("c", vec![3]);
+    let values = keys.iter_lookup(&map).collect::<Vec<_>>();
+    assert_eq!(values, vec![Some(1), None, Some(3)]);
+    let values = keys.iter_lookup_flatten(&map).collect::<Vec<_>>();
+    assert_eq!(values, vec![1, 3]);
+
+    let pairs = [("a", Some(1)), ("b", None), ("a", None)];
+    let values = keys.iter_lookup_all(&pairs, 0).collect::<Vec<_>>();
+    assert_eq!(values, vec![vec![(0, 1), (2, 0)], vec![(1, 0)], vec![]]);
+}
+
+mod outputs {
+    use zero_v::compose;
+    use zero_v_gen::zero_v;
+
+    #[zero_v(trait_types)]
+    trait Label<'a, const N: usize> {
+        fn label(&self, index: usize) -> &'a str;
+        fn window(&self, values: [u8; N]) -> Option<[u8; N]>;
+    }
+
+    struct Labels(&'static [&'static str]);
+
+    impl<const N: usize> Label<'static, N> for Labels {
+        fn label(&self, index: usize) -> &'static str {
+            self.0[index]
+        }
+
+        fn window(&self, values: [u8; N]) -> Option<[u8; N]> {
+            if values.len() <= self.0.len() {
+                Some(values)
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_generics_only_in_outputs() {
+        let labels = compose!(Labels(&["a", "b"]), Labels(&["c", "d", "e"]));
+
+        let values = IterLabel::<'static, 2, _>::iter_label(&labels, 1).collect::<Vec<_>>();
+        assert_eq!(values, vec!["b", "d"]);
+
+        let values = labels.iter_window([1, 2, 3]).collect::<Vec<_>>();
+        assert_eq!(values, vec![None, Some([1, 2, 3])]);
+    }
+}
@@ -0,0 +1,53 @@
+// The code the macros generate has to build in no_std crates, so this test
+// crate opts out of std (but keeps alloc, which zero_v needs).
+#![no_std]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+
+use zero_v::{compose, zero_v, DynTail, Either, RefCellNode};
+
+#[zero_v(trait_types, refs, either, dyn_tail, shared, args, max_nodes = 8)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+#[zero_v(fn_generics, IntOp as IntOps)]
+fn sum_execute(input: usize, ops: &IntOps) -> usize {
+    ops.iter_execute(input).sum()
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+struct Counter(usize);
+
+impl IntOpMut for Counter {
+    fn execute(&mut self, input: usize) -> usize {
+        self.0 += 1;
+        input + self.0
+    }
+}
+
+#[test]
+fn test_generated_code_builds_without_std() {
+    let plugins: DynTail<dyn IntOp> = Vec::from([Box::new(Adder(4)) as Box<dyn IntOp>]).into();
+    let ops = compose!(
+        Adder(1),
+        Rc::new(Adder(2)),
+        Either::<Adder, Adder>::Right(Adder(3)),
+        RefCellNode::new(Counter(0)),
+        ..plugins
+    );
+    assert_eq!(ops.iter_execute(0).collect::<Vec<_>>(), Vec::from([1, 2, 3, 1, 4]));
+    assert_eq!(sum_execute(0, &ops), 12);
+    assert_eq!(ops.audit().nodes.len(), 5);
+}
@@ -0,0 +1,57 @@
+use zero_v::{compose, zero_v};
+
+#[zero_v(trait_types, optional)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+fn configured(value: Option<usize>) -> Option<Adder> {
+    value.map(Adder)
+}
+
+#[test]
+fn test_empty_options_are_skipped() {
+    let ops = compose!(Adder(1), configured(None), Adder(3), configured(Some(4)));
+    assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 4, 5]);
+    assert_eq!(ops.iter_execute_ref(&1).collect::<Vec<_>>(), vec![2, 4, 5]);
+    assert_eq!(ops.iter_execute_step(1, 2, 1).collect::<Vec<_>>(), vec![5]);
+    assert_eq!(ops.try_fold_execute(0, 1, |acc, x| Ok::<_, ()>(acc + x)), Ok(11));
+}
+
+#[test]
+fn test_empty_options_still_take_up_a_level() {
+    let ops = compose!(configured(None), Adder(2));
+    assert_eq!(ops.execute_at_level(1, 0), None);
+    assert_eq!(ops.execute_at_level(1, 1), Some(3));
+    assert_eq!(compose!(configured(None)).iter_execute(1).next(), None);
+}
+
+mod without_optional {
+    use zero_v::{compose, zero_v};
+
+    // Without `optional`, the trait can be implemented for an Option itself.
+    #[zero_v(trait_types)]
+    trait IntOp {
+        fn execute(&self, input: usize) -> usize;
+    }
+
+    impl IntOp for Option<usize> {
+        fn execute(&self, input: usize) -> usize {
+            input + self.unwrap_or(0)
+        }
+    }
+
+    #[test]
+    fn test_trait_can_be_implemented_for_options() {
+        let ops = compose!(Some(1), None);
+        assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 1]);
+    }
+}
@@ -0,0 +1,81 @@
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use zero_v::{compose, zero_v};
+
+#[zero_v(trait_types, optional)]
+trait Task {
+    fn id(&self) -> u32;
+    fn share(self: Rc<Self>) -> (u32, usize);
+    fn spawn(self: Arc<Self>, offset: u32) -> u32;
+    fn step(self: Pin<&mut Self>, amount: u32) -> u32;
+}
+
+// Opts out of Unpin, so step can only be called through a pin.
+struct Counter {
+    id: u32,
+    count: u32,
+    _pinned: PhantomPinned,
+}
+
+impl Counter {
+    fn new(id: u32) -> Self {
+        Self {
+            id,
+            count: 0,
+            _pinned: PhantomPinned,
+        }
+    }
+}
+
+impl Task for Counter {
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    fn share(self: Rc<Self>) -> (u32, usize) {
+        (self.id, Rc::strong_count(&self))
+    }
+
+    fn spawn(self: Arc<Self>, offset: u32) -> u32 {
+        self.id + offset
+    }
+
+    fn step(self: Pin<&mut Self>, amount: u32) -> u32 {
+        // Safety: count isn't structurally pinned.
+        let this = unsafe { self.get_unchecked_mut() };
+        this.count += amount;
+        this.count
+    }
+}
+
+#[test]
+fn test_rc_receiver() {
+    let tasks = compose!(Counter::new(1), Counter::new(2));
+    assert_eq!(tasks.iter_id().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(tasks.into_iter_share().collect::<Vec<_>>(), vec![(1, 1), (2, 1)]);
+}
+
+#[test]
+fn test_arc_receiver() {
+    let mut ids = Vec::new();
+    compose!(Counter::new(1), Some(Counter::new(2)), None::<Counter>)
+        .for_each_spawn_into(10, |id| ids.push(id));
+    assert_eq!(ids, vec![11, 12]);
+}
+
+#[test]
+fn test_pin_receiver() {
+    let mut tasks = Box::pin(compose!(
+        Counter::new(1),
+        Some(Counter::new(2)),
+        compose!(Counter::new(3)),
+    ));
+    assert_eq!(tasks.as_mut().iter_step_pinned(1).collect::<Vec<_>>(), vec![1, 1, 1]);
+
+    let mut counts = Vec::new();
+    tasks.as_mut().for_each_step_pinned(2, |count| counts.push(count));
+    assert_eq!(counts, vec![3, 3, 3]);
+}
@@ -0,0 +1,37 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use zero_v::{compose, compose_refs, zero_v};
+
+#[zero_v(trait_types, refs)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+#[test]
+fn test_composites_of_references() {
+    let (first, second) = (Adder(1), Adder(2));
+    let ops = compose_refs!(&first, &second);
+    assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 3]);
+
+    let mixed = compose!(Adder(3), &first);
+    assert_eq!(mixed.iter_execute(1).collect::<Vec<_>>(), vec![4, 2]);
+}
+
+#[test]
+fn test_composites_of_smart_pointers() {
+    let shared = Rc::new(Adder(2));
+    let ops = compose!(Box::new(Adder(1)), Rc::clone(&shared), Arc::new(Adder(3)));
+    assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 3, 4]);
+
+    let boxed: Box<dyn IntOp> = Box::new(Adder(4));
+    assert_eq!(compose!(boxed).iter_execute(1).collect::<Vec<_>>(), vec![5]);
+}
@@ -0,0 +1,23 @@
+use zero_v::{compose, zero_v, Repeat};
+
+#[zero_v(trait_types, blocks)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+#[test]
+fn test_repeats_run_once_per_level() {
+    let ops = compose!(Adder(1), Repeat::<_, 3>::new(Adder(2)), Adder(3));
+    assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 3, 3, 3, 4]);
+    assert_eq!(ops.execute_at_level(1, 4), Some(4));
+    assert_eq!(ops.try_fold_execute(1, 1, |acc, x| Ok::<_, ()>(acc * x)), Ok(216));
+    assert_eq!(ops.audit().nodes.len(), 3);
+}
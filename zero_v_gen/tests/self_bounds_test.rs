@@ -0,0 +1,68 @@
+use zero_v::compose;
+use zero_v_gen::zero_v;
+
+#[zero_v(trait_types)]
+trait Shape {
+    fn area(&self) -> f64;
+    fn scaled_area(&self, scale: f64) -> f64
+    where
+        Self: Sized;
+    fn doubled(&self) -> Self
+    where
+        Self: Sized;
+    // Bounds on Self other than Sized can't be checked for every object in a
+    // composite, so these aren't generated for composites.
+    fn duplicate(&self) -> Self
+    where
+        Self: Sized + Clone;
+    fn describe(&self, prefix: &'static str) -> String
+    where
+        Self: std::fmt::Debug;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+    fn scaled_area(&self, scale: f64) -> f64 {
+        self.area() * scale * scale
+    }
+    fn doubled(&self) -> Self {
+        Square(self.0 * 2.0)
+    }
+    fn duplicate(&self) -> Self {
+        self.clone()
+    }
+    fn describe(&self, prefix: &'static str) -> String {
+        format!("{}{:?}", prefix, self)
+    }
+}
+
+struct Areas(Vec<f64>);
+
+impl ShapeDoubledVisitor for Areas {
+    fn visit<TraitType: Shape>(&mut self, _level: usize, output: TraitType) {
+        self.0.push(output.area());
+    }
+}
+
+#[test]
+fn test_self_bounds() {
+    let shapes = compose!(Square(1.0), Square(2.0));
+    assert_eq!(shapes.iter_area().collect::<Vec<_>>(), vec![1.0, 4.0]);
+    assert_eq!(
+        shapes.iter_scaled_area(2.0).collect::<Vec<_>>(),
+        vec![4.0, 16.0]
+    );
+
+    let mut areas = Areas(vec![]);
+    shapes.for_each_doubled(&mut areas);
+    assert_eq!(areas.0, vec![4.0, 16.0]);
+
+    let square = Square(3.0);
+    assert_eq!(square.duplicate(), square);
+    assert_eq!(square.describe("a "), "a Square(3.0)");
+}
@@ -0,0 +1,89 @@
+use zero_v::{compose, zero_v};
+
+#[zero_v(trait_types, shadow, optional)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+    fn describe(&self, input: usize) -> String;
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+
+    fn describe(&self, input: usize) -> String {
+        format!("{} + {}", input, self.0)
+    }
+}
+
+struct Multiplier(usize);
+
+impl IntOp for Multiplier {
+    fn execute(&self, input: usize) -> usize {
+        input * self.0
+    }
+
+    fn describe(&self, input: usize) -> String {
+        format!("{} * {}", input, self.0)
+    }
+}
+
+impl zero_v::Priority for Adder {
+    const PRIORITY: usize = 1;
+}
+
+impl zero_v::Priority for Multiplier {
+    const PRIORITY: usize = 0;
+}
+
+#[test]
+fn test_shadow_execution_matches() {
+    let ops = compose!(Adder(1), compose!(Multiplier(2)), Adder(3));
+
+    assert_eq!(ops.iter_execute(4).collect::<Vec<_>>(), vec![5, 8, 7]);
+    assert_eq!(ops.iter_execute_ref(&4).sum::<usize>(), 20);
+
+    let ops = compose!(Adder(1), Multiplier(2), Adder(3));
+    assert_eq!(
+        ops.iter_describe_by_priority(4).collect::<Vec<_>>(),
+        vec!["4 * 2", "4 + 1", "4 + 3"]
+    );
+}
+
+#[test]
+fn test_shadow_execution_skips_empty_options() {
+    let ops = compose!(Adder(1), None::<Adder>, Some(Multiplier(2)), Adder(3));
+
+    assert_eq!(ops.iter_execute(4).collect::<Vec<_>>(), vec![5, 8, 7]);
+    assert_eq!(
+        ops.iter_describe(4).collect::<Vec<_>>(),
+        vec!["4 + 1", "4 * 2", "4 + 3"]
+    );
+}
+
+#[cfg(all(feature = "shadow", debug_assertions))]
+#[test]
+#[should_panic(expected = "static and dynamic dispatch diverged at level 0")]
+fn test_shadow_execution_catches_divergence() {
+    use std::cell::Cell;
+
+    // An object whose output changes every time it's executed, so the
+    // static and dynamic executions disagree.
+    struct Inconsistent(Cell<usize>);
+
+    impl IntOp for Inconsistent {
+        fn execute(&self, input: usize) -> usize {
+            self.0.set(self.0.get() + 1);
+            input + self.0.get()
+        }
+
+        fn describe(&self, input: usize) -> String {
+            input.to_string()
+        }
+    }
+
+    let ops = compose!(Inconsistent(Cell::new(0)));
+    ops.iter_execute(1).for_each(drop);
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+use std::thread;
+
+use zero_v::{compose, zero_v, MutexNode, RefCellNode};
+
+#[zero_v(trait_types, shared)]
+trait Counter {
+    fn count(&self, by: usize) -> usize;
+    fn reset_to(&self, value: usize);
+}
+
+#[derive(Default)]
+struct Running(usize);
+
+impl CounterMut for Running {
+    fn count(&mut self, by: usize) -> usize {
+        self.0 += by;
+        self.0
+    }
+
+    fn reset_to(&mut self, value: usize) {
+        self.0 = value;
+    }
+}
+
+struct Fixed(usize);
+
+impl Counter for Fixed {
+    fn count(&self, _by: usize) -> usize {
+        self.0
+    }
+
+    fn reset_to(&self, _value: usize) {}
+}
+
+#[test]
+fn test_refcell_nodes_keep_state() {
+    let counters = compose!(RefCellNode::new(Running(0)), Fixed(7), RefCellNode::new(Running(10)));
+
+    assert_eq!(counters.iter_count(1).collect::<Vec<_>>(), vec![1, 7, 11]);
+    assert_eq!(counters.iter_count(2).collect::<Vec<_>>(), vec![3, 7, 13]);
+    counters.iter_reset_to(0).for_each(drop);
+    assert_eq!(counters.iter_count(1).collect::<Vec<_>>(), vec![1, 7, 1]);
+}
+
+#[test]
+fn test_mutex_nodes_are_shared_across_threads() {
+    let counters = Arc::new(compose!(MutexNode::new(Running(0)), Fixed(7)));
+
+    let handles = (0..4)
+        .map(|_| {
+            let counters = Arc::clone(&counters);
+            thread::spawn(move || counters.iter_count(1).for_each(drop))
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(counters.iter_count(0).collect::<Vec<_>>(), vec![4, 7]);
+}
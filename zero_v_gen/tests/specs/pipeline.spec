@@ -0,0 +1,4 @@
+// The default pipeline.
+"double",
+"offset",
+"double",
@@ -0,0 +1,56 @@
+use zero_v::{compose, zero_v, DynTail, Repeat};
+
+#[zero_v(trait_types, to_dyn_vec, optional, blocks, dyn_tail)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+struct Multiplier(usize);
+
+impl IntOp for Multiplier {
+    fn execute(&self, input: usize) -> usize {
+        input * self.0
+    }
+}
+
+fn execute_all(ops: &[&dyn IntOp], input: usize) -> Vec<usize> {
+    ops.iter().map(|op| op.execute(input)).collect()
+}
+
+#[test]
+fn test_to_dyn_vec_lists_every_object() {
+    let ops = compose!(Adder(1), compose!(Multiplier(2)), None::<Adder>, Adder(3));
+    let objects = ops.to_dyn_vec();
+
+    assert_eq!(objects.len(), 3);
+    assert_eq!(execute_all(&objects, 4), ops.iter_execute(4).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_to_dyn_vec_on_tuples() {
+    let ops = (Adder(1), Multiplier(2));
+    assert_eq!(execute_all(&ops.to_dyn_vec(), 4), vec![5, 8]);
+}
+
+#[test]
+fn test_to_dyn_vec_on_blocks_and_dyn_tails() {
+    let mut plugins = DynTail::<dyn IntOp>::new();
+    plugins.push(Box::new(Multiplier(3)));
+    let ops = compose!(
+        [Adder(1), Adder(2)],
+        compose!(Repeat::<_, 2>::new(Multiplier(2)), Some(Adder(5))),
+        ..plugins
+    );
+    let objects = ops.to_dyn_vec();
+
+    assert_eq!(objects.len(), 6);
+    assert_eq!(execute_all(&objects, 4), vec![5, 6, 8, 8, 9, 12]);
+}
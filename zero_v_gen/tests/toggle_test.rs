@@ -0,0 +1,25 @@
+use zero_v::{compose, zero_v, Toggle};
+
+#[zero_v(trait_types, optional)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+#[test]
+fn test_disabled_toggles_are_skipped() {
+    let mut ops = compose!(Adder(1), Toggle::new(Adder(2)), Toggle::disabled(Adder(3)));
+    assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 3]);
+
+    ops.get_mut::<1>().set_enabled(false);
+    ops.get_mut::<2>().set_enabled(true);
+    assert_eq!(ops.iter_execute(1).collect::<Vec<_>>(), vec![2, 4]);
+    assert_eq!(ops.execute_at_level(1, 1), None);
+}
@@ -0,0 +1,90 @@
+use zero_v::{compose, zero_v, Composite, TupleComposite};
+
+#[zero_v(trait_types, tuples = 32)]
+trait IntOp {
+    fn execute(&self, input: usize) -> usize;
+}
+
+#[zero_v(fn_generics, IntOp as IntOps)]
+fn sum_map(input: usize, ops: &IntOps) -> usize {
+    ops.iter_execute(input).sum()
+}
+
+struct Adder(usize);
+
+impl IntOp for Adder {
+    fn execute(&self, input: usize) -> usize {
+        input + self.0
+    }
+}
+
+struct Multiplier(usize);
+
+impl IntOp for Multiplier {
+    fn execute(&self, input: usize) -> usize {
+        input * self.0
+    }
+}
+
+#[test]
+fn test_tuples_can_be_iterated() {
+    let ops = (Adder(1), Multiplier(2), Adder(3));
+    assert_eq!(ops.iter_execute(10).collect::<Vec<_>>(), vec![11, 20, 13]);
+    assert_eq!(sum_map(10, &(Adder(1), Multiplier(2))), 31);
+    assert_eq!(sum_map(10, &(Adder(1),)), 11);
+}
+
+#[test]
+fn test_tuples_match_composites() {
+    let tuple = (Adder(1), Multiplier(2), Adder(3));
+    let composite = compose!(Adder(1), Multiplier(2), Adder(3));
+    assert_eq!(
+        tuple.iter_execute(5).collect::<Vec<_>>(),
+        composite.iter_execute(5).collect::<Vec<_>>()
+    );
+    assert_eq!(tuple.execute_at_level(5, 1), Some(10));
+    assert_eq!(tuple.execute_at_level(5, 3), None);
+    assert_eq!(tuple.audit().nodes.len(), 3);
+}
+
+#[test]
+fn test_tuples_can_back_composites() {
+    let ops = Composite::new((Adder(1), Multiplier(2)));
+    assert_eq!(ops.iter_execute(10).collect::<Vec<_>>(), vec![11, 20]);
+}
+
+#[test]
+fn test_tuple_composites_up_to_32_objects() {
+    let ops = TupleComposite::new((
+        Adder(0), Adder(1), Adder(2), Adder(3), Adder(4), Adder(5), Adder(6), Adder(7),
+        Adder(8), Adder(9), Adder(10), Adder(11), Adder(12), Adder(13), Adder(14), Adder(15),
+        Adder(16), Adder(17), Adder(18), Adder(19), Adder(20), Adder(21), Adder(22), Adder(23),
+        Adder(24), Adder(25), Adder(26), Adder(27), Adder(28), Adder(29), Adder(30), Adder(31),
+    ));
+    assert_eq!(ops.iter_execute(0).collect::<Vec<_>>(), (0..32).collect::<Vec<_>>());
+    assert_eq!(ops.len(), 32);
+}
+
+mod default_tuples {
+    use zero_v::zero_v;
+
+    #[zero_v(trait_types)]
+    trait IntOp {
+        fn execute(&self, input: usize) -> usize;
+    }
+
+    impl IntOp for usize {
+        fn execute(&self, input: usize) -> usize {
+            input + self
+        }
+    }
+
+    #[test]
+    fn test_tuples_up_to_12_objects_by_default() {
+        let ops = (
+            0usize, 1usize, 2usize, 3usize, 4usize, 5usize, 6usize, 7usize, 8usize, 9usize,
+            10usize, 11usize,
+        );
+        assert_eq!(ops.iter_execute(0).collect::<Vec<_>>(), (0..12).collect::<Vec<_>>());
+    }
+}